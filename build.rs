@@ -0,0 +1,24 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_config(cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml")).unwrap())
+            .generate()
+            .expect("Unable to generate bindings")
+            .write_to_file(format!("{crate_dir}/include/nutriscore.h"));
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+    }
+    #[cfg(feature = "grpc")]
+    {
+        let descriptors = protox::compile(["proto/nutriscore.proto"], ["proto"])
+            .expect("Unable to parse the proto file");
+        tonic_prost_build::configure()
+            .build_client(false)
+            .compile_fds(descriptors)
+            .expect("Unable to generate the gRPC service");
+        println!("cargo:rerun-if-changed=proto/nutriscore.proto");
+    }
+}
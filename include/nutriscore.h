@@ -0,0 +1,64 @@
+#ifndef NUTRISCORE_H
+#define NUTRISCORE_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define NUTRISCORE_ALGORITHM_2017 2017
+
+#define NUTRISCORE_ALGORITHM_2023 2023
+
+#define NUTRISCORE_CATEGORY_DRINKS 0
+
+#define NUTRISCORE_CATEGORY_MILK_DRINKS 1
+
+#define NUTRISCORE_CATEGORY_CHEESE 2
+
+#define NUTRISCORE_CATEGORY_OILS_AND_FATS 3
+
+#define NUTRISCORE_CATEGORY_RED_MEAT 4
+
+#define NUTRISCORE_CATEGORY_OTHER 5
+
+/**
+ * The total score and the letter from `'A'` to `'E'`.
+ *
+ * The letter is `'\0'` if the algorithm or the category was invalid.
+ */
+typedef struct NutriscoreResult {
+  ptrdiff_t score;
+  char letter;
+} NutriscoreResult;
+
+/**
+ * The nutritional values per 100g or 100ml, see `Nutrition`.
+ */
+typedef struct NutriscoreNutrition {
+  float energy;
+  float fat;
+  float saturated_fats;
+  float sugar;
+  float proteins;
+  float salt;
+  float fibers;
+  bool contains_sweeteners;
+} NutriscoreNutrition;
+
+/**
+ * Calculates the Nutri-Score of a product.
+ *
+ * `algorithm` is one of the `NUTRISCORE_ALGORITHM_*` constants and `category` one of the
+ * `NUTRISCORE_CATEGORY_*` constants. `fruits` is the percentage of fruits, vegetables and nuts.
+ */
+struct NutriscoreResult nutriscore_calculate(uint32_t algorithm,
+                                             uint32_t category,
+                                             struct NutriscoreNutrition nutrition,
+                                             float fruits,
+                                             bool is_water);
+
+#endif  /* NUTRISCORE_H */
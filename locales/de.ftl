@@ -0,0 +1,232 @@
+# Eingaben
+prompt-name = Produktname (Tab ergänzt ein gespeichertes, leer für keinen)
+prompt-energy-kj = Energie (kJ)
+prompt-energy-kcal = Energie (kcal)
+prompt-fat = Fett
+prompt-saturated-fats = Gesättigte Fettsäuren
+prompt-carbohydrates = Kohlenhydrate
+prompt-sugar = Zucker
+prompt-proteins = Eiweiß
+prompt-salt = Salz
+prompt-sodium = Natrium (mg)
+prompt-fibers = Ballaststoffe
+prompt-fruits = Anteil an Obst und Gemüse in Prozent
+prompt-category = Kategorie
+prompt-drink = Welche Art von Getränk ist es
+drink-water = Wasser
+drink-mineral-water = Mineralwasser
+drink-sparkling-water = Sprudelwasser
+drink-flavored-water = Wasser mit Geschmack
+drink-other = Anderes Getränk
+prompt-alcohol = Alkoholgehalt
+prompt-sweeteners = Enthält es Süßstoffe
+prompt-if-unknown = { $prompt } (leer lassen, wenn unbekannt)
+prompt-salt-or-sodium = Salz (leer lassen, um Natrium in mg einzugeben)
+prompt-what-if = Einen Wert ändern, z. B. "set sugar to 8" (leer zum Beenden)
+
+back-hint = Mit "back" geht es zurück zur vorigen Frage, mit "quit" werden die Antworten gespeichert, um später mit --resume weiterzumachen.
+back = ← zurück
+quit = ✕ speichern und beenden
+session-saved = Die Antworten sind in { $path } gespeichert, weiter geht es mit --resume { $path }
+yes = ja
+no = nein
+
+# Obst-Assistent
+wizard-intro = Gib die Zutaten des Rezepts ein.
+wizard-mass = Masse der Zutat in g (leer lassen, wenn fertig)
+wizard-kind = Art der Zutat
+wizard-concentration = Wie oft ist sie konzentriert, z. B. 2 für doppelt konzentriert
+wizard-result = Anteil an Obst und Gemüse: { $fruits } %
+
+# Kategorien und Arten von Zutaten
+category-drinks = Getränke
+category-milk-drinks = Milchgetränke
+category-cheese = Käse
+category-oils-and-fats = Öle und Fette
+category-red-meat = Rotes Fleisch
+category-other = Sonstiges
+ingredient-fruits = Obst, Gemüse, Hülsenfrüchte oder Nüsse
+ingredient-dried = Getrocknetes Obst, Gemüse oder Hülsenfrüchte
+ingredient-concentrated = Konzentriertes Obst oder Gemüse
+ingredient-other = Andere Zutat
+
+# Namen der Bestandteile
+component-energy = Energie
+component-sugar = Zucker
+component-fats = Fett
+component-sodium = Natrium
+component-salt = Salz
+component-sweeteners = Süßstoffe
+component-fruits-vegs = Obst & Gemüse
+component-fibers = Ballaststoffe
+component-protein = Eiweiß
+component-fat = Fett
+component-saturates = Gesättigte Fettsäuren
+component-sugars = Zucker
+component-category = Kategorie
+component-saturates-of-fat = Gesättigte Fettsäuren im Fett
+
+# Namen der Nährstoffe in einem Satz
+nutrient-energy = Energie
+nutrient-sugar = Zucker
+nutrient-saturated-fats = gesättigte Fettsäuren
+nutrient-salt = Salz
+nutrient-fruits-vegs = Obst und Gemüse
+nutrient-fibers = Ballaststoffe
+nutrient-protein = Eiweiß
+nutrient-fat = Fett
+nutrient-proteins = Eiweiß
+
+# Meldungen
+missing-value = kein Wert für { $value } angegeben
+missing-value-help = als Argument angeben oder --non-interactive weglassen, um danach gefragt zu werden
+invalid-number = { $text } ist keine Zahl
+unknown-unit = unbekannte Einheit { $unit }, erlaubt sind kJ, kcal, g, mg, µg und %
+wrong-unit = { $text } lässt sich nicht in { $unit } umrechnen
+warning = Warnung: { $message }
+total-score = Gesamtwertung:
+not-counted-2017 =
+    Die negativen Punkte { $negative } sind mehr als 10 und die Obstpunkte { $fruits } weniger als 5.
+    Ballaststoffe und Eiweiß werden nicht gezählt!
+not-counted-2023 =
+    Die negativen Punkte { $negative } sind zu hoch.
+    Eiweiß wird nicht gezählt!
+breakdown-nutrient = Nährstoff
+breakdown-value = Wert
+breakdown-band = Bereich
+breakdown-points = Punkte
+breakdown-max = Max
+breakdown-not-counted = (nicht gezählt)
+breakdown-not-scored = (nicht bewertet)
+breakdown-negative = Negativ
+breakdown-positive = Positiv
+breakdown-total = Gesamt
+range-unknown = Unbekannt: { $values }
+range-fibers = Ballaststoffe
+range-fruits = Obst und Gemüse
+range-best = Bester Fall:
+range-worst = Schlechtester Fall:
+range-result = Wertung zwischen { $best } und { $worst }, mögliche Noten:
+accessible-negative = { $name }: { $points } von { $max } negativen Punkten.
+accessible-positive = { $name }: { $points } von { $max } positiven Punkten.
+accessible-not-counted = { $name }: { $points } von { $max } positiven Punkten, nicht gezählt.
+accessible-grade = Nutri-Score { $grade }, Gesamtwertung { $score }.
+accessible-model = { $result }, Gesamtwertung { $score }.
+accessible-range = Die Note ist eine von { $grades }, die Gesamtwertung liegt zwischen { $best } und { $worst }.
+advice-current = Aktuelle Note: { $grade } (Wertung { $score })
+advice-none = Keine Änderung eines einzelnen Nährstoffs führt zu einer besseren Note.
+advice-intro = Für eine bessere Note ändere eines davon:
+advice-increase = { $nutrient } um { $by } { $unit } auf { $to } { $unit } erhöhen
+advice-reduce = { $nutrient } um { $by } { $unit } auf { $to } { $unit } senken
+sensitivity-intro = Abstand zu den Grenzwerten, unter dem unteren gibt es einen Punkt weniger und über dem oberen einen mehr:
+sensitivity-above = { $by } { $unit } über { $cutoff } { $unit }
+sensitivity-below = { $by } { $unit } unter { $cutoff } { $unit }
+uncertainty-grades = Noten innerhalb der Toleranzen der Werte, von { $samples } zufälligen Produkten:
+uncertainty-range = Punktzahl zwischen { $best } ({ $best-grade }) und { $worst } ({ $worst-grade })
+uncertainty-stable = Die Note { $grade } ist innerhalb der Toleranzen stabil.
+uncertainty-unstable = Die Note { $grade } ist instabil, innerhalb der Toleranzen kann sie { $best-grade } bis { $worst-grade } sein.
+what-if-not-understood = { $command } wurde nicht verstanden
+what-if-unknown-nutrient = Unbekannter Nährstoff { $name }
+what-if-undone = Die Änderung wird rückgängig gemacht.
+terminal = { $prompt } kann nicht abgefragt werden
+terminal-help = den Wert auf der Kommandozeile angeben oder --non-interactive verwenden, um sofort abzubrechen
+unsupported-locale = Die Locale { $locale } wird nicht unterstützt, es wird Englisch verwendet.
+assuming-zero = Kein Wert für { $name }, es wird 0 angenommen
+cached-answer = { $key } kann nicht abgerufen werden, die gespeicherte Antwort wird verwendet.
+source-clipboard = der Zwischenablage
+no-nutrition-table = keine Nährwerttabelle in { $source } gefunden
+not-found-in = Nicht gefunden in { $source }: { $names }
+traffic-lights-per-100 = Ampel pro 100 g bzw. ml:
+traffic-lights-per-portion = Ampel pro Portion von { $portion } g bzw. ml:
+light-green = grün
+light-amber = gelb
+light-red = rot
+keyhole-eligible = Das Produkt kann das Keyhole-Siegel für { $group } tragen.
+keyhole-not-eligible = Das Produkt kann das Keyhole-Siegel für { $group } nicht tragen, wegen:
+keyhole-at-most = { $amount } { $unit }, höchstens { $limit } { $unit }
+keyhole-at-least = { $amount } { $unit }, mindestens { $limit } { $unit }
+jurisdiction-france = Frankreich
+jurisdiction-belgium = Belgien
+jurisdiction-germany = Deutschland
+jurisdiction-luxembourg = Luxemburg
+jurisdiction-netherlands = den Niederlanden
+jurisdiction-spain = Spanien
+jurisdiction-switzerland = der Schweiz
+jurisdiction-not-valid = Die Note des Algorithmus { $algorithm } ist in { $country } am { $date } nicht gültig.
+jurisdiction-transition = In { $country } dürfen Noten des Algorithmus 2017 noch bis { $until } verwendet werden.
+jurisdiction-guidance-france = Das Siegel ist freiwillig; Hersteller registrieren sich bei Santé publique France.
+jurisdiction-guidance-belgium = Das Siegel ist freiwillig; Hersteller registrieren sich beim FÖD Volksgesundheit.
+jurisdiction-guidance-germany = Das Siegel ist freiwillig; Hersteller registrieren sich bei der Bundesanstalt für Landwirtschaft und Ernährung (BLE).
+jurisdiction-guidance-luxembourg = Das Siegel ist freiwillig; Hersteller registrieren sich bei Santé publique France.
+jurisdiction-guidance-netherlands = Das Siegel ist freiwillig und wurde erst mit dem Algorithmus 2023 eingeführt.
+jurisdiction-guidance-spain = Das Siegel ist freiwillig; Hersteller registrieren sich bei Santé publique France.
+jurisdiction-guidance-switzerland = Das Siegel ist freiwillig; Hersteller registrieren sich bei Santé publique France.
+nova-group = NOVA-Gruppe { $group }: { $description }
+nova-markers = Wegen: { $markers }
+nova-1 = unverarbeitetes oder minimal verarbeitetes Lebensmittel
+nova-2 = verarbeitete Küchenzutat
+nova-3 = verarbeitetes Lebensmittel
+nova-4 = hochverarbeitetes Lebensmittel
+prompt-eco-category = Art des Lebensmittels für den Eco-Score
+prompt-packaging = Verpackung
+prompt-origin = Herkunft der Zutaten
+prompt-labels = Siegel, durch Kommas getrennt ({ $labels }), leer für keine
+prompt-palm-oil = Enthält es nicht als nachhaltig zertifiziertes Palmöl?
+unknown-label = Unbekanntes Siegel { $label }
+eco-title = Eco-Score:
+eco-baseline = Grundwert für { $category }
+eco-labels = Siegel
+eco-origin = Herkunft
+eco-packaging = Verpackung
+eco-palm-oil = Palmöl
+eco-total = Gesamt
+eco-grade = Eco-Score { $grade }
+eco-category-beef = Rind
+eco-category-lamb = Lamm
+eco-category-pork = Schwein
+eco-category-poultry = Geflügel
+eco-category-fish = Fisch und Meeresfrüchte
+eco-category-cheese = Käse
+eco-category-milk = Milch
+eco-category-yoghurt = Joghurt
+eco-category-eggs = Eier
+eco-category-butter = Butter
+eco-category-oils = Öle
+eco-category-cereals = Getreide, Brot und Nudeln
+eco-category-legumes = Hülsenfrüchte
+eco-category-vegetables = Gemüse
+eco-category-fruits = Obst
+eco-category-nuts = Nüsse und Samen
+eco-category-chocolate = Schokolade
+eco-category-coffee = Kaffee und Tee
+eco-category-soft-drinks = Erfrischungsgetränke
+eco-category-water = Wasser
+eco-category-other = Sonstiges
+packaging-bulk = Lose, ohne Verpackung
+packaging-paper = Papier oder Karton
+packaging-glass = Glas
+packaging-metal = Metall
+packaging-plastic = Recycelbarer Kunststoff
+packaging-non-recyclable = Nicht recycelbare Verbundstoffe
+origin-local = Aus dem Land, in dem es verkauft wird
+origin-europe = Aus Europa
+origin-world = Von außerhalb Europas
+origin-unknown = Unbekannt
+not-applicable-alcohol = { $alcohol } % Alkohol: Der Nutri-Score gilt nicht für Getränke mit mehr als { $limit } % Alkohol
+
+# Problems with the values, see Product::validate
+issue-energy-negative = Der Brennwert darf nicht negativ sein, angegeben: { $energy }
+issue-energy-too-high = Der Brennwert darf nicht mehr als { $max } kJ pro 100g betragen, angegeben: { $energy }
+issue-negative = { $name } darf nicht negativ sein, angegeben: { $value }
+issue-too-much = { $name } darf nicht mehr als 100g pro 100g betragen, angegeben: { $value }
+issue-saturates-above-fat = Gesättigte Fettsäuren ({ $saturated-fats }) dürfen nicht mehr als das Fett ({ $fat }) sein
+issue-sugar-above-carbohydrates = Zucker ({ $sugar }) darf nicht mehr als die Kohlenhydrate ({ $carbohydrates }) sein
+issue-total-carbohydrates = Fett, Kohlenhydrate, Eiweiß, Salz und Ballaststoffe ergeben zusammen { $total }g, mehr als 100g
+issue-total-sugar = Fett, Zucker, Eiweiß, Salz und Ballaststoffe ergeben zusammen { $total }g, mehr als 100g
+issue-energy-low-carbohydrates =
+    Der Brennwert von { $energy } kJ ist viel niedriger als die { $minimum } kJ aus Fett, Eiweiß und Kohlenhydraten, wurde er in kcal eingegeben?
+issue-energy-low-sugar =
+    Der Brennwert von { $energy } kJ ist viel niedriger als die { $minimum } kJ aus Fett, Eiweiß und Zucker, wurde er in kcal eingegeben?
+issue-energy-high = Der Brennwert von { $energy } kJ ist viel höher als die { $estimate } kJ, die aus den anderen Werten geschätzt werden
+issue-ratio-without-fat = Die gesättigten Fettsäuren werden als Anteil am Fett bewertet, das nicht 0 sein darf, angegeben: { $fat }
+issue-fruits-out-of-range = Der Anteil an Obst und Gemüse muss zwischen 0 und 100 liegen, angegeben: { $fruits }
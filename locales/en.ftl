@@ -0,0 +1,232 @@
+# Prompts
+prompt-name = Product name (Tab completes a saved one, empty for none)
+prompt-energy-kj = Energy (kJ)
+prompt-energy-kcal = Energy (kcal)
+prompt-fat = Fats
+prompt-saturated-fats = Saturated fats
+prompt-carbohydrates = Carbohydrates
+prompt-sugar = Sugar
+prompt-proteins = Protein
+prompt-salt = Salt
+prompt-sodium = Sodium (mg)
+prompt-fibers = Fibers
+prompt-fruits = Percentage of fruits and vegetables
+prompt-category = Category
+prompt-drink = What kind of drink is it
+drink-water = Water
+drink-mineral-water = Mineral water
+drink-sparkling-water = Sparkling water
+drink-flavored-water = Flavored water
+drink-other = Other drink
+prompt-alcohol = Alcohol by volume
+prompt-sweeteners = Does it contain non-nutritive sweeteners
+prompt-if-unknown = { $prompt } (leave empty if unknown)
+prompt-salt-or-sodium = Salt (leave empty to enter sodium in mg)
+prompt-what-if = Change a value, e.g. "set sugar to 8" (empty to quit)
+
+back-hint = Enter "back" to return to the previous question, or "quit" to save the answers and continue later with --resume.
+back = ← back
+quit = ✕ save and quit
+session-saved = The answers are saved in { $path }, continue with --resume { $path }
+yes = yes
+no = no
+
+# The fruits wizard
+wizard-intro = Enter the ingredients of the recipe.
+wizard-mass = Mass of the ingredient in g (leave empty when done)
+wizard-kind = Kind of ingredient
+wizard-concentration = How many times is it concentrated, e.g. 2 for double concentrated
+wizard-result = Percentage of fruits and vegetables: { $fruits }
+
+# Categories and kinds of ingredients
+category-drinks = Drinks
+category-milk-drinks = Milk drinks
+category-cheese = Cheese
+category-oils-and-fats = Oils and fats
+category-red-meat = Red meat
+category-other = Other
+ingredient-fruits = Fruits, vegetables, pulses or nuts
+ingredient-dried = Dried fruits, vegetables or pulses
+ingredient-concentrated = Concentrated fruits or vegetables
+ingredient-other = Other ingredient
+
+# Names of the components
+component-energy = Energy
+component-sugar = Sugar
+component-fats = Fats
+component-sodium = Sodium
+component-salt = Salt
+component-sweeteners = Sweeteners
+component-fruits-vegs = Fruits & Vegs
+component-fibers = Fibers
+component-protein = Protein
+component-fat = Fat
+component-saturates = Saturates
+component-sugars = Sugars
+component-category = Category
+component-saturates-of-fat = Saturates of fat
+
+# Names of nutrients within a sentence
+nutrient-energy = energy
+nutrient-sugar = sugar
+nutrient-saturated-fats = saturated fats
+nutrient-salt = salt
+nutrient-fruits-vegs = fruits and vegetables
+nutrient-fibers = fibers
+nutrient-protein = protein
+nutrient-fat = fat
+nutrient-proteins = proteins
+
+# Messages
+missing-value = no value given for { $value }
+missing-value-help = give it as an argument, or leave out --non-interactive to be asked for it
+invalid-number = { $text } is not a number
+unknown-unit = unknown unit { $unit }, use kJ, kcal, g, mg, µg or %
+wrong-unit = { $text } cannot be converted into { $unit }
+warning = Warning: { $message }
+total-score = Total Score:
+not-counted-2017 =
+    The negative score { $negative } is more than 10 and the fruit score { $fruits } is less than 5.
+    Fibers and Proteins will not be counted!
+not-counted-2023 =
+    The negative score { $negative } is too high.
+    Proteins will not be counted!
+breakdown-nutrient = Nutrient
+breakdown-value = Value
+breakdown-band = Band
+breakdown-points = Points
+breakdown-max = Max
+breakdown-not-counted = (not counted)
+breakdown-not-scored = (not scored)
+breakdown-negative = Negative
+breakdown-positive = Positive
+breakdown-total = Total
+range-unknown = Unknown: { $values }
+range-fibers = fibers
+range-fruits = fruits and vegetables
+range-best = Best case:
+range-worst = Worst case:
+range-result = Score between { $best } and { $worst }, possible grades:
+accessible-negative = { $name }: { $points } of { $max } negative points.
+accessible-positive = { $name }: { $points } of { $max } positive points.
+accessible-not-counted = { $name }: { $points } of { $max } positive points, not counted.
+accessible-grade = Nutri-Score { $grade }, total score { $score }.
+accessible-model = { $result }, total score { $score }.
+accessible-range = The grade is one of { $grades }, the total score is between { $best } and { $worst }.
+advice-current = Current grade: { $grade } (score { $score })
+advice-none = No single nutrient change leads to a better grade.
+advice-intro = For a better grade, change one of:
+advice-increase = increase { $nutrient } by { $by } { $unit } to { $to } { $unit }
+advice-reduce = reduce { $nutrient } by { $by } { $unit } to { $to } { $unit }
+sensitivity-intro = Distance to the cutoffs, a point fewer below the lower one and a point more above the upper one:
+sensitivity-above = { $by } { $unit } above { $cutoff } { $unit }
+sensitivity-below = { $by } { $unit } below { $cutoff } { $unit }
+uncertainty-grades = Grades within the tolerances of the values, of { $samples } random products:
+uncertainty-range = Score between { $best } ({ $best-grade }) and { $worst } ({ $worst-grade })
+uncertainty-stable = The grade { $grade } is stable within the tolerances.
+uncertainty-unstable = The grade { $grade } is unstable, within the tolerances it can be { $best-grade } to { $worst-grade }.
+what-if-not-understood = Could not understand { $command }
+what-if-unknown-nutrient = Unknown nutrient { $name }
+what-if-undone = The change is undone.
+terminal = cannot ask for { $prompt }
+terminal-help = give the value on the command line, or use --non-interactive to fail right away
+unsupported-locale = The locale { $locale } is not supported, using English.
+assuming-zero = No value for { $name }, assuming 0
+cached-answer = Cannot fetch { $key }, using the cached answer.
+source-clipboard = the clipboard
+no-nutrition-table = no nutrition table found in { $source }
+not-found-in = Not found in { $source }: { $names }
+traffic-lights-per-100 = Traffic lights per 100 g or ml:
+traffic-lights-per-portion = Traffic lights per portion of { $portion } g or ml:
+light-green = green
+light-amber = amber
+light-red = red
+keyhole-eligible = The product can have the Keyhole label for { $group }.
+keyhole-not-eligible = The product cannot have the Keyhole label for { $group }, because of:
+keyhole-at-most = { $amount } { $unit }, at most { $limit } { $unit }
+keyhole-at-least = { $amount } { $unit }, at least { $limit } { $unit }
+jurisdiction-france = France
+jurisdiction-belgium = Belgium
+jurisdiction-germany = Germany
+jurisdiction-luxembourg = Luxembourg
+jurisdiction-netherlands = the Netherlands
+jurisdiction-spain = Spain
+jurisdiction-switzerland = Switzerland
+jurisdiction-not-valid = The grade of the { $algorithm } algorithm is not valid in { $country } on { $date }.
+jurisdiction-transition = In { $country }, grades of the 2017 algorithm may still be used until { $until }.
+jurisdiction-guidance-france = The label is voluntary; manufacturers register with Santé publique France.
+jurisdiction-guidance-belgium = The label is voluntary; manufacturers register with the FPS Public Health.
+jurisdiction-guidance-germany = The label is voluntary; manufacturers register with the Federal Office for Agriculture and Food (BLE).
+jurisdiction-guidance-luxembourg = The label is voluntary; manufacturers register with Santé publique France.
+jurisdiction-guidance-netherlands = The label is voluntary and was introduced with the 2023 algorithm only.
+jurisdiction-guidance-spain = The label is voluntary; manufacturers register with Santé publique France.
+jurisdiction-guidance-switzerland = The label is voluntary; manufacturers register with Santé publique France.
+nova-group = NOVA group { $group }: { $description }
+nova-markers = Because of: { $markers }
+nova-1 = unprocessed or minimally processed food
+nova-2 = processed culinary ingredient
+nova-3 = processed food
+nova-4 = ultra-processed food
+prompt-eco-category = Kind of food for the Eco-Score
+prompt-packaging = Packaging
+prompt-origin = Origin of the ingredients
+prompt-labels = Labels, separated by commas ({ $labels }), empty for none
+prompt-palm-oil = Does it contain palm oil that is not certified as sustainable?
+unknown-label = Unknown label { $label }
+eco-title = Eco-Score:
+eco-baseline = Baseline for { $category }
+eco-labels = Labels
+eco-origin = Origin
+eco-packaging = Packaging
+eco-palm-oil = Palm oil
+eco-total = Total
+eco-grade = Eco-Score { $grade }
+eco-category-beef = Beef
+eco-category-lamb = Lamb
+eco-category-pork = Pork
+eco-category-poultry = Poultry
+eco-category-fish = Fish and seafood
+eco-category-cheese = Cheese
+eco-category-milk = Milk
+eco-category-yoghurt = Yoghurt
+eco-category-eggs = Eggs
+eco-category-butter = Butter
+eco-category-oils = Oils
+eco-category-cereals = Cereals, bread and pasta
+eco-category-legumes = Legumes
+eco-category-vegetables = Vegetables
+eco-category-fruits = Fruits
+eco-category-nuts = Nuts and seeds
+eco-category-chocolate = Chocolate
+eco-category-coffee = Coffee and tea
+eco-category-soft-drinks = Soft drinks
+eco-category-water = Water
+eco-category-other = Other
+packaging-bulk = Loose, without packaging
+packaging-paper = Paper or cardboard
+packaging-glass = Glass
+packaging-metal = Metal
+packaging-plastic = Recyclable plastic
+packaging-non-recyclable = Mixed materials that cannot be recycled
+origin-local = From the country where it is sold
+origin-europe = From Europe
+origin-world = From outside Europe
+origin-unknown = Unknown
+not-applicable-alcohol = { $alcohol }% alcohol: the Nutri-Score is not applicable to drinks with more than { $limit }% alcohol
+
+# Problems with the values, see Product::validate
+issue-energy-negative = Energy must not be negative, got { $energy }
+issue-energy-too-high = Energy must not be more than { $max } kJ per 100g, got { $energy }
+issue-negative = { $name } must not be negative, got { $value }
+issue-too-much = { $name } must not be more than 100g per 100g, got { $value }
+issue-saturates-above-fat = Saturated fats ({ $saturated-fats }) must not be more than fats ({ $fat })
+issue-sugar-above-carbohydrates = Sugar ({ $sugar }) must not be more than carbohydrates ({ $carbohydrates })
+issue-total-carbohydrates = Fats, carbohydrates, proteins, salt and fibers add up to { $total }g, more than 100g
+issue-total-sugar = Fats, sugar, proteins, salt and fibers add up to { $total }g, more than 100g
+issue-energy-low-carbohydrates =
+    Energy of { $energy } kJ is much lower than the { $minimum } kJ that fats, proteins and carbohydrates provide, was it entered in kcal?
+issue-energy-low-sugar =
+    Energy of { $energy } kJ is much lower than the { $minimum } kJ that fats, proteins and sugar provide, was it entered in kcal?
+issue-energy-high = Energy of { $energy } kJ is much higher than the { $estimate } kJ estimated from the other values
+issue-ratio-without-fat = The saturated fats are scored as a share of the fat, which must not be 0, got { $fat }
+issue-fruits-out-of-range = The percentage of fruits and vegetables must be between 0 and 100, got { $fruits }
@@ -0,0 +1,232 @@
+# Questions
+prompt-name = Nom du produit (Tab complète un produit enregistré, vide si aucun)
+prompt-energy-kj = Énergie (kJ)
+prompt-energy-kcal = Énergie (kcal)
+prompt-fat = Matières grasses
+prompt-saturated-fats = Acides gras saturés
+prompt-carbohydrates = Glucides
+prompt-sugar = Sucres
+prompt-proteins = Protéines
+prompt-salt = Sel
+prompt-sodium = Sodium (mg)
+prompt-fibers = Fibres
+prompt-fruits = Pourcentage de fruits et légumes
+prompt-category = Catégorie
+prompt-drink = Quel type de boisson est-ce
+drink-water = Eau
+drink-mineral-water = Eau minérale
+drink-sparkling-water = Eau gazeuse
+drink-flavored-water = Eau aromatisée
+drink-other = Autre boisson
+prompt-alcohol = Teneur en alcool
+prompt-sweeteners = Contient-il des édulcorants
+prompt-if-unknown = { $prompt } (laisser vide si inconnu)
+prompt-salt-or-sodium = Sel (laisser vide pour saisir le sodium en mg)
+prompt-what-if = Modifier une valeur, p. ex. "set sugar to 8" (vide pour quitter)
+
+back-hint = Entrez "back" pour revenir à la question précédente, ou "quit" pour enregistrer les réponses et continuer plus tard avec --resume.
+back = ← retour
+quit = ✕ enregistrer et quitter
+session-saved = Les réponses sont enregistrées dans { $path }, continuez avec --resume { $path }
+yes = oui
+no = non
+
+# L'assistant des fruits
+wizard-intro = Saisissez les ingrédients de la recette.
+wizard-mass = Masse de l'ingrédient en g (laisser vide pour terminer)
+wizard-kind = Type d'ingrédient
+wizard-concentration = Combien de fois est-il concentré, p. ex. 2 pour double concentré
+wizard-result = Pourcentage de fruits et légumes : { $fruits }
+
+# Catégories et types d'ingrédients
+category-drinks = Boissons
+category-milk-drinks = Boissons lactées
+category-cheese = Fromages
+category-oils-and-fats = Huiles et matières grasses
+category-red-meat = Viande rouge
+category-other = Autre
+ingredient-fruits = Fruits, légumes, légumineuses ou fruits à coque
+ingredient-dried = Fruits, légumes ou légumineuses séchés
+ingredient-concentrated = Fruits ou légumes concentrés
+ingredient-other = Autre ingrédient
+
+# Noms des composantes
+component-energy = Énergie
+component-sugar = Sucres
+component-fats = Graisses
+component-sodium = Sodium
+component-salt = Sel
+component-sweeteners = Édulcorants
+component-fruits-vegs = Fruits & lég.
+component-fibers = Fibres
+component-protein = Protéines
+component-fat = Matières grasses
+component-saturates = Acides gras saturés
+component-sugars = Sucres
+component-category = Catégorie
+component-saturates-of-fat = Acides gras saturés des graisses
+
+# Noms des nutriments dans une phrase
+nutrient-energy = énergie
+nutrient-sugar = sucres
+nutrient-saturated-fats = acides gras saturés
+nutrient-salt = sel
+nutrient-fruits-vegs = fruits et légumes
+nutrient-fibers = fibres
+nutrient-protein = protéines
+nutrient-fat = matières grasses
+nutrient-proteins = protéines
+
+# Messages
+missing-value = aucune valeur donnée pour { $value }
+missing-value-help = donnez-la en argument, ou omettez --non-interactive pour qu’elle soit demandée
+invalid-number = { $text } n'est pas un nombre
+unknown-unit = unité { $unit } inconnue, utilisez kJ, kcal, g, mg, µg ou %
+wrong-unit = { $text } ne peut pas être converti en { $unit }
+warning = Attention : { $message }
+total-score = Score total :
+not-counted-2017 =
+    Les points négatifs { $negative } dépassent 10 et les points des fruits { $fruits } sont inférieurs à 5.
+    Les fibres et les protéines ne sont pas comptées !
+not-counted-2023 =
+    Les points négatifs { $negative } sont trop élevés.
+    Les protéines ne sont pas comptées !
+breakdown-nutrient = Nutriment
+breakdown-value = Valeur
+breakdown-band = Plage
+breakdown-points = Points
+breakdown-max = Max
+breakdown-not-counted = (non compté)
+breakdown-not-scored = (non noté)
+breakdown-negative = Négatif
+breakdown-positive = Positif
+breakdown-total = Total
+range-unknown = Inconnu : { $values }
+range-fibers = fibres
+range-fruits = fruits et légumes
+range-best = Meilleur cas :
+range-worst = Pire cas :
+range-result = Score entre { $best } et { $worst }, notes possibles :
+accessible-negative = { $name } : { $points } sur { $max } points négatifs.
+accessible-positive = { $name } : { $points } sur { $max } points positifs.
+accessible-not-counted = { $name } : { $points } sur { $max } points positifs, non comptés.
+accessible-grade = Nutri-Score { $grade }, score total { $score }.
+accessible-model = { $result }, score total { $score }.
+accessible-range = La note est l'une de { $grades }, le score total est entre { $best } et { $worst }.
+advice-current = Note actuelle : { $grade } (score { $score })
+advice-none = Aucune modification d'un seul nutriment n'améliore la note.
+advice-intro = Pour une meilleure note, modifiez l'un de ces nutriments :
+advice-increase = augmenter { $nutrient } de { $by } { $unit } à { $to } { $unit }
+advice-reduce = réduire { $nutrient } de { $by } { $unit } à { $to } { $unit }
+sensitivity-intro = Écart aux seuils, un point de moins sous le seuil inférieur et un de plus au-dessus du seuil supérieur :
+sensitivity-above = { $by } { $unit } au-dessus de { $cutoff } { $unit }
+sensitivity-below = { $by } { $unit } en dessous de { $cutoff } { $unit }
+uncertainty-grades = Notes dans les tolérances des valeurs, sur { $samples } produits aléatoires :
+uncertainty-range = Score entre { $best } ({ $best-grade }) et { $worst } ({ $worst-grade })
+uncertainty-stable = La note { $grade } est stable dans les tolérances.
+uncertainty-unstable = La note { $grade } est instable, dans les tolérances elle peut aller de { $best-grade } à { $worst-grade }.
+what-if-not-understood = { $command } n'a pas été compris
+what-if-unknown-nutrient = Nutriment inconnu { $name }
+what-if-undone = La modification est annulée.
+terminal = impossible de demander { $prompt }
+terminal-help = donnez la valeur sur la ligne de commande, ou utilisez --non-interactive pour échouer tout de suite
+unsupported-locale = La locale { $locale } n'est pas prise en charge, l'anglais est utilisé.
+assuming-zero = Aucune valeur pour { $name }, 0 est supposé
+cached-answer = Impossible de récupérer { $key }, la réponse en cache est utilisée.
+source-clipboard = le presse-papiers
+no-nutrition-table = aucun tableau nutritionnel trouvé dans { $source }
+not-found-in = Introuvable dans { $source } : { $names }
+traffic-lights-per-100 = Feux tricolores pour 100 g ou ml :
+traffic-lights-per-portion = Feux tricolores par portion de { $portion } g ou ml :
+light-green = vert
+light-amber = orange
+light-red = rouge
+keyhole-eligible = Le produit peut porter le label Keyhole pour { $group }.
+keyhole-not-eligible = Le produit ne peut pas porter le label Keyhole pour { $group }, à cause de :
+keyhole-at-most = { $amount } { $unit }, au plus { $limit } { $unit }
+keyhole-at-least = { $amount } { $unit }, au moins { $limit } { $unit }
+jurisdiction-france = France
+jurisdiction-belgium = Belgique
+jurisdiction-germany = Allemagne
+jurisdiction-luxembourg = Luxembourg
+jurisdiction-netherlands = Pays-Bas
+jurisdiction-spain = Espagne
+jurisdiction-switzerland = Suisse
+jurisdiction-not-valid = { $country } : la note de l'algorithme { $algorithm } n'est pas valable le { $date }.
+jurisdiction-transition = { $country } : les notes de l'algorithme 2017 peuvent encore être utilisées jusqu'au { $until }.
+jurisdiction-guidance-france = Le logo est facultatif ; les fabricants s'enregistrent auprès de Santé publique France.
+jurisdiction-guidance-belgium = Le logo est facultatif ; les fabricants s'enregistrent auprès du SPF Santé publique.
+jurisdiction-guidance-germany = Le logo est facultatif ; les fabricants s'enregistrent auprès de l'Office fédéral de l'agriculture et de l'alimentation (BLE).
+jurisdiction-guidance-luxembourg = Le logo est facultatif ; les fabricants s'enregistrent auprès de Santé publique France.
+jurisdiction-guidance-netherlands = Le logo est facultatif et n'a été introduit qu'avec l'algorithme 2023.
+jurisdiction-guidance-spain = Le logo est facultatif ; les fabricants s'enregistrent auprès de Santé publique France.
+jurisdiction-guidance-switzerland = Le logo est facultatif ; les fabricants s'enregistrent auprès de Santé publique France.
+nova-group = Groupe NOVA { $group } : { $description }
+nova-markers = En raison de : { $markers }
+nova-1 = aliment brut ou peu transformé
+nova-2 = ingrédient culinaire transformé
+nova-3 = aliment transformé
+nova-4 = aliment ultra-transformé
+prompt-eco-category = Type d'aliment pour l'Eco-Score
+prompt-packaging = Emballage
+prompt-origin = Origine des ingrédients
+prompt-labels = Labels, séparés par des virgules ({ $labels }), vide si aucun
+prompt-palm-oil = Contient-il de l'huile de palme non certifiée durable ?
+unknown-label = Label inconnu { $label }
+eco-title = Eco-Score :
+eco-baseline = Base pour { $category }
+eco-labels = Labels
+eco-origin = Origine
+eco-packaging = Emballage
+eco-palm-oil = Huile de palme
+eco-total = Total
+eco-grade = Eco-Score { $grade }
+eco-category-beef = Bœuf
+eco-category-lamb = Agneau
+eco-category-pork = Porc
+eco-category-poultry = Volaille
+eco-category-fish = Poisson et fruits de mer
+eco-category-cheese = Fromage
+eco-category-milk = Lait
+eco-category-yoghurt = Yaourt
+eco-category-eggs = Œufs
+eco-category-butter = Beurre
+eco-category-oils = Huiles
+eco-category-cereals = Céréales, pain et pâtes
+eco-category-legumes = Légumineuses
+eco-category-vegetables = Légumes
+eco-category-fruits = Fruits
+eco-category-nuts = Fruits à coque et graines
+eco-category-chocolate = Chocolat
+eco-category-coffee = Café et thé
+eco-category-soft-drinks = Boissons sucrées
+eco-category-water = Eau
+eco-category-other = Autre
+packaging-bulk = En vrac, sans emballage
+packaging-paper = Papier ou carton
+packaging-glass = Verre
+packaging-metal = Métal
+packaging-plastic = Plastique recyclable
+packaging-non-recyclable = Matériaux mixtes non recyclables
+origin-local = Du pays où il est vendu
+origin-europe = D'Europe
+origin-world = Hors d'Europe
+origin-unknown = Inconnue
+not-applicable-alcohol = { $alcohol } % d’alcool : le Nutri-Score ne s’applique pas aux boissons de plus de { $limit } % d’alcool
+
+# Problems with the values, see Product::validate
+issue-energy-negative = L'énergie ne doit pas être négative, valeur : { $energy }
+issue-energy-too-high = L'énergie ne doit pas dépasser { $max } kJ pour 100g, valeur : { $energy }
+issue-negative = { $name } ne doit pas être négatif, valeur : { $value }
+issue-too-much = { $name } ne doit pas dépasser 100g pour 100g, valeur : { $value }
+issue-saturates-above-fat = Les acides gras saturés ({ $saturated-fats }) ne doivent pas dépasser les matières grasses ({ $fat })
+issue-sugar-above-carbohydrates = Les sucres ({ $sugar }) ne doivent pas dépasser les glucides ({ $carbohydrates })
+issue-total-carbohydrates = Matières grasses, glucides, protéines, sel et fibres font { $total }g au total, plus de 100g
+issue-total-sugar = Matières grasses, sucres, protéines, sel et fibres font { $total }g au total, plus de 100g
+issue-energy-low-carbohydrates =
+    L'énergie de { $energy } kJ est bien inférieure aux { $minimum } kJ des matières grasses, des protéines et des glucides, a-t-elle été saisie en kcal ?
+issue-energy-low-sugar =
+    L'énergie de { $energy } kJ est bien inférieure aux { $minimum } kJ des matières grasses, des protéines et des sucres, a-t-elle été saisie en kcal ?
+issue-energy-high = L'énergie de { $energy } kJ est bien supérieure aux { $estimate } kJ estimés à partir des autres valeurs
+issue-ratio-without-fat = Les acides gras saturés sont évalués comme part des matières grasses, qui ne doivent pas être 0, valeur : { $fat }
+issue-fruits-out-of-range = Le pourcentage de fruits et légumes doit être entre 0 et 100, valeur : { $fruits }
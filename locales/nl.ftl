@@ -0,0 +1,232 @@
+# Vragen
+prompt-name = Productnaam (Tab vult een opgeslagen product aan, leeg voor geen)
+prompt-energy-kj = Energie (kJ)
+prompt-energy-kcal = Energie (kcal)
+prompt-fat = Vetten
+prompt-saturated-fats = Verzadigde vetten
+prompt-carbohydrates = Koolhydraten
+prompt-sugar = Suikers
+prompt-proteins = Eiwitten
+prompt-salt = Zout
+prompt-sodium = Natrium (mg)
+prompt-fibers = Vezels
+prompt-fruits = Percentage groenten en fruit
+prompt-category = Categorie
+prompt-drink = Wat voor drank is het
+drink-water = Water
+drink-mineral-water = Mineraalwater
+drink-sparkling-water = Bruiswater
+drink-flavored-water = Water met smaak
+drink-other = Andere drank
+prompt-alcohol = Alcoholpercentage
+prompt-sweeteners = Bevat het zoetstoffen
+prompt-if-unknown = { $prompt } (leeg laten als onbekend)
+prompt-salt-or-sodium = Zout (leeg laten om natrium in mg in te voeren)
+prompt-what-if = Een waarde wijzigen, bv. "set sugar to 8" (leeg om te stoppen)
+
+back-hint = Voer "back" in om terug te gaan naar de vorige vraag, of "quit" om de antwoorden op te slaan en later verder te gaan met --resume.
+back = ← terug
+quit = ✕ opslaan en stoppen
+session-saved = De antwoorden zijn opgeslagen in { $path }, ga verder met --resume { $path }
+yes = ja
+no = nee
+
+# De fruitassistent
+wizard-intro = Voer de ingrediënten van het recept in.
+wizard-mass = Massa van het ingrediënt in g (leeg laten als je klaar bent)
+wizard-kind = Soort ingrediënt
+wizard-concentration = Hoe vaak is het geconcentreerd, bv. 2 voor dubbel geconcentreerd
+wizard-result = Percentage groenten en fruit: { $fruits }
+
+# Categorieën en soorten ingrediënten
+category-drinks = Dranken
+category-milk-drinks = Melkdranken
+category-cheese = Kaas
+category-oils-and-fats = Oliën en vetten
+category-red-meat = Rood vlees
+category-other = Overig
+ingredient-fruits = Fruit, groenten, peulvruchten of noten
+ingredient-dried = Gedroogd fruit, groenten of peulvruchten
+ingredient-concentrated = Geconcentreerd fruit of groenten
+ingredient-other = Ander ingrediënt
+
+# Namen van de onderdelen
+component-energy = Energie
+component-sugar = Suikers
+component-fats = Vetten
+component-sodium = Natrium
+component-salt = Zout
+component-sweeteners = Zoetstoffen
+component-fruits-vegs = Groente&fruit
+component-fibers = Vezels
+component-protein = Eiwitten
+component-fat = Vet
+component-saturates = Verzadigd vet
+component-sugars = Suikers
+component-category = Categorie
+component-saturates-of-fat = Verzadigd vet van het vet
+
+# Namen van voedingsstoffen in een zin
+nutrient-energy = energie
+nutrient-sugar = suikers
+nutrient-saturated-fats = verzadigde vetten
+nutrient-salt = zout
+nutrient-fruits-vegs = groenten en fruit
+nutrient-fibers = vezels
+nutrient-protein = eiwitten
+nutrient-fat = vetten
+nutrient-proteins = eiwitten
+
+# Meldingen
+missing-value = geen waarde opgegeven voor { $value }
+missing-value-help = geef hem als argument op, of laat --non-interactive weg om ernaar gevraagd te worden
+invalid-number = { $text } is geen getal
+unknown-unit = onbekende eenheid { $unit }, gebruik kJ, kcal, g, mg, µg of %
+wrong-unit = { $text } kan niet worden omgerekend naar { $unit }
+warning = Waarschuwing: { $message }
+total-score = Totaalscore:
+not-counted-2017 =
+    De negatieve score { $negative } is hoger dan 10 en de fruitscore { $fruits } is lager dan 5.
+    Vezels en eiwitten worden niet meegeteld!
+not-counted-2023 =
+    De negatieve score { $negative } is te hoog.
+    Eiwitten worden niet meegeteld!
+breakdown-nutrient = Voedingsstof
+breakdown-value = Waarde
+breakdown-band = Bereik
+breakdown-points = Punten
+breakdown-max = Max
+breakdown-not-counted = (niet meegeteld)
+breakdown-not-scored = (niet beoordeeld)
+breakdown-negative = Negatief
+breakdown-positive = Positief
+breakdown-total = Totaal
+range-unknown = Onbekend: { $values }
+range-fibers = vezels
+range-fruits = groenten en fruit
+range-best = Beste geval:
+range-worst = Slechtste geval:
+range-result = Score tussen { $best } en { $worst }, mogelijke letters:
+accessible-negative = { $name }: { $points } van { $max } negatieve punten.
+accessible-positive = { $name }: { $points } van { $max } positieve punten.
+accessible-not-counted = { $name }: { $points } van { $max } positieve punten, niet meegeteld.
+accessible-grade = Nutri-Score { $grade }, totaalscore { $score }.
+accessible-model = { $result }, totaalscore { $score }.
+accessible-range = De letter is een van { $grades }, de totaalscore ligt tussen { $best } en { $worst }.
+advice-current = Huidige letter: { $grade } (score { $score })
+advice-none = Geen enkele wijziging van één voedingsstof leidt tot een betere letter.
+advice-intro = Wijzig voor een betere letter een van deze:
+advice-increase = { $nutrient } met { $by } { $unit } verhogen tot { $to } { $unit }
+advice-reduce = { $nutrient } met { $by } { $unit } verlagen tot { $to } { $unit }
+sensitivity-intro = Afstand tot de grenswaarden, onder de onderste een punt minder en boven de bovenste een punt meer:
+sensitivity-above = { $by } { $unit } boven { $cutoff } { $unit }
+sensitivity-below = { $by } { $unit } onder { $cutoff } { $unit }
+uncertainty-grades = Letters binnen de toleranties van de waarden, van { $samples } willekeurige producten:
+uncertainty-range = Score tussen { $best } ({ $best-grade }) en { $worst } ({ $worst-grade })
+uncertainty-stable = De letter { $grade } is stabiel binnen de toleranties.
+uncertainty-unstable = De letter { $grade } is instabiel, binnen de toleranties kan die { $best-grade } tot { $worst-grade } zijn.
+what-if-not-understood = { $command } werd niet begrepen
+what-if-unknown-nutrient = Onbekende voedingsstof { $name }
+what-if-undone = De wijziging wordt ongedaan gemaakt.
+terminal = kan niet vragen naar { $prompt }
+terminal-help = geef de waarde op de opdrachtregel, of gebruik --non-interactive om meteen te stoppen
+unsupported-locale = De locale { $locale } wordt niet ondersteund, Engels wordt gebruikt.
+assuming-zero = Geen waarde voor { $name }, 0 wordt aangenomen
+cached-answer = { $key } kan niet worden opgehaald, het opgeslagen antwoord wordt gebruikt.
+source-clipboard = het klembord
+no-nutrition-table = geen voedingstabel gevonden in { $source }
+not-found-in = Niet gevonden in { $source }: { $names }
+traffic-lights-per-100 = Verkeerslichten per 100 g of ml:
+traffic-lights-per-portion = Verkeerslichten per portie van { $portion } g of ml:
+light-green = groen
+light-amber = oranje
+light-red = rood
+keyhole-eligible = Het product kan het Keyhole-logo voor { $group } dragen.
+keyhole-not-eligible = Het product kan het Keyhole-logo voor { $group } niet dragen, vanwege:
+keyhole-at-most = { $amount } { $unit }, hoogstens { $limit } { $unit }
+keyhole-at-least = { $amount } { $unit }, minstens { $limit } { $unit }
+jurisdiction-france = Frankrijk
+jurisdiction-belgium = België
+jurisdiction-germany = Duitsland
+jurisdiction-luxembourg = Luxemburg
+jurisdiction-netherlands = Nederland
+jurisdiction-spain = Spanje
+jurisdiction-switzerland = Zwitserland
+jurisdiction-not-valid = De score van het algoritme { $algorithm } is in { $country } op { $date } niet geldig.
+jurisdiction-transition = In { $country } mogen scores van het algoritme 2017 nog tot { $until } gebruikt worden.
+jurisdiction-guidance-france = Het logo is vrijwillig; fabrikanten registreren zich bij Santé publique France.
+jurisdiction-guidance-belgium = Het logo is vrijwillig; fabrikanten registreren zich bij de FOD Volksgezondheid.
+jurisdiction-guidance-germany = Het logo is vrijwillig; fabrikanten registreren zich bij het Bundesanstalt für Landwirtschaft und Ernährung (BLE).
+jurisdiction-guidance-luxembourg = Het logo is vrijwillig; fabrikanten registreren zich bij Santé publique France.
+jurisdiction-guidance-netherlands = Het logo is vrijwillig en werd pas met het algoritme 2023 ingevoerd.
+jurisdiction-guidance-spain = Het logo is vrijwillig; fabrikanten registreren zich bij Santé publique France.
+jurisdiction-guidance-switzerland = Het logo is vrijwillig; fabrikanten registreren zich bij Santé publique France.
+nova-group = NOVA-groep { $group }: { $description }
+nova-markers = Vanwege: { $markers }
+nova-1 = onbewerkt of minimaal bewerkt voedsel
+nova-2 = bewerkt culinair ingrediënt
+nova-3 = bewerkt voedsel
+nova-4 = ultrabewerkt voedsel
+prompt-eco-category = Soort voedsel voor de Eco-Score
+prompt-packaging = Verpakking
+prompt-origin = Herkomst van de ingrediënten
+prompt-labels = Keurmerken, gescheiden door komma's ({ $labels }), leeg voor geen
+prompt-palm-oil = Bevat het palmolie die niet als duurzaam gecertificeerd is?
+unknown-label = Onbekend keurmerk { $label }
+eco-title = Eco-Score:
+eco-baseline = Basis voor { $category }
+eco-labels = Keurmerken
+eco-origin = Herkomst
+eco-packaging = Verpakking
+eco-palm-oil = Palmolie
+eco-total = Totaal
+eco-grade = Eco-Score { $grade }
+eco-category-beef = Rund
+eco-category-lamb = Lam
+eco-category-pork = Varken
+eco-category-poultry = Gevogelte
+eco-category-fish = Vis en zeevruchten
+eco-category-cheese = Kaas
+eco-category-milk = Melk
+eco-category-yoghurt = Yoghurt
+eco-category-eggs = Eieren
+eco-category-butter = Boter
+eco-category-oils = Oliën
+eco-category-cereals = Granen, brood en pasta
+eco-category-legumes = Peulvruchten
+eco-category-vegetables = Groenten
+eco-category-fruits = Fruit
+eco-category-nuts = Noten en zaden
+eco-category-chocolate = Chocolade
+eco-category-coffee = Koffie en thee
+eco-category-soft-drinks = Frisdranken
+eco-category-water = Water
+eco-category-other = Overig
+packaging-bulk = Los, zonder verpakking
+packaging-paper = Papier of karton
+packaging-glass = Glas
+packaging-metal = Metaal
+packaging-plastic = Recyclebaar plastic
+packaging-non-recyclable = Gemengde materialen die niet recyclebaar zijn
+origin-local = Uit het land waar het verkocht wordt
+origin-europe = Uit Europa
+origin-world = Van buiten Europa
+origin-unknown = Onbekend
+not-applicable-alcohol = { $alcohol }% alcohol: de Nutri-Score geldt niet voor dranken met meer dan { $limit }% alcohol
+
+# Problems with the values, see Product::validate
+issue-energy-negative = De energie mag niet negatief zijn, opgegeven: { $energy }
+issue-energy-too-high = De energie mag niet meer dan { $max } kJ per 100g zijn, opgegeven: { $energy }
+issue-negative = { $name } mag niet negatief zijn, opgegeven: { $value }
+issue-too-much = { $name } mag niet meer dan 100g per 100g zijn, opgegeven: { $value }
+issue-saturates-above-fat = Verzadigde vetten ({ $saturated-fats }) mogen niet meer zijn dan de vetten ({ $fat })
+issue-sugar-above-carbohydrates = Suikers ({ $sugar }) mogen niet meer zijn dan de koolhydraten ({ $carbohydrates })
+issue-total-carbohydrates = Vetten, koolhydraten, eiwitten, zout en vezels zijn samen { $total }g, meer dan 100g
+issue-total-sugar = Vetten, suikers, eiwitten, zout en vezels zijn samen { $total }g, meer dan 100g
+issue-energy-low-carbohydrates =
+    De energie van { $energy } kJ is veel lager dan de { $minimum } kJ uit vetten, eiwitten en koolhydraten, is die in kcal ingevoerd?
+issue-energy-low-sugar =
+    De energie van { $energy } kJ is veel lager dan de { $minimum } kJ uit vetten, eiwitten en suikers, is die in kcal ingevoerd?
+issue-energy-high = De energie van { $energy } kJ is veel hoger dan de { $estimate } kJ die uit de andere waarden wordt geschat
+issue-ratio-without-fat = De verzadigde vetten worden beoordeeld als aandeel van het vet, dat niet 0 mag zijn, opgegeven: { $fat }
+issue-fruits-out-of-range = Het percentage groenten en fruit moet tussen 0 en 100 liggen, opgegeven: { $fruits }
@@ -0,0 +1,180 @@
+use crate::{calculate_nutriscore, Algorithm, Category, Nutrition};
+use serde::Serialize;
+
+/// A change of a single nutrient that improves the grade, see [`advise`].
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct Advice {
+    /// Name of the nutrient.
+    pub nutrient: &'static str,
+    /// Unit of `from` and `to`.
+    pub unit: &'static str,
+    /// The current value.
+    pub from: f32,
+    /// The value needed for a better grade.
+    pub to: f32,
+}
+
+struct Input {
+    nutrition: Nutrition,
+    fruits: f32,
+}
+
+type Field = fn(&mut Input) -> &mut f32;
+
+// name, unit, field, whether the value has to increase, upper limit, rounding precision
+const FIELDS: [(&str, &str, Field, bool, f32, f32); 7] = [
+    ("Energy", "kJ", |i| &mut i.nutrition.energy, false, 0.0, 1.0),
+    ("Sugar", "g", |i| &mut i.nutrition.sugar, false, 0.0, 10.0),
+    (
+        "Saturated fats",
+        "g",
+        |i| &mut i.nutrition.saturated_fats,
+        false,
+        0.0,
+        10.0,
+    ),
+    ("Salt", "g", |i| &mut i.nutrition.salt, false, 0.0, 100.0),
+    ("Fruits & Vegs", "%", |i| &mut i.fruits, true, 100.0, 10.0),
+    (
+        "Fibers",
+        "g",
+        |i| &mut i.nutrition.fibers,
+        true,
+        100.0,
+        10.0,
+    ),
+    (
+        "Protein",
+        "g",
+        |i| &mut i.nutrition.proteins,
+        true,
+        100.0,
+        10.0,
+    ),
+];
+
+/// Finds the smallest change of each nutrient on its own that leads to a better grade.
+///
+/// Nutrients that cannot improve the grade are left out, so the result is empty for an A.
+#[must_use]
+pub fn advise(
+    algorithm: Algorithm,
+    cat: Category,
+    nutrition: &Nutrition,
+    fruits_value: f32,
+    is_water: bool,
+) -> Vec<Advice> {
+    let letter = |input: &Input| {
+        let score = calculate_nutriscore(algorithm, cat, &input.nutrition, input.fruits);
+        cat.score_to_letter(algorithm, score.total, is_water)
+    };
+    let current = letter(&Input {
+        nutrition: *nutrition,
+        fruits: fruits_value,
+    });
+    let mut advice = Vec::new();
+    for (nutrient, unit, field, increase, limit, precision) in FIELDS {
+        let mut input = Input {
+            nutrition: *nutrition,
+            fruits: fruits_value,
+        };
+        let from = *field(&mut input);
+        let mut better = |value: f32| {
+            *field(&mut input) = value;
+            letter(&input) < current
+        };
+        let limit = if increase { limit.max(from) } else { limit };
+        if !better(limit) {
+            continue;
+        }
+        // binary search for the value closest to `from` that still improves the grade
+        let (mut worse, mut good) = (from, limit);
+        for _ in 0..64 {
+            let mid = f32::midpoint(worse, good);
+            if better(mid) {
+                good = mid;
+            } else {
+                worse = mid;
+            }
+        }
+        // the scores are calculated with rounded values, so advise a value that is rounded already
+        let step = if increase { 1.0 } else { -1.0 };
+        let mut units = (good * precision).round();
+        while !better(units / precision) {
+            units += step;
+        }
+        let to = units / precision;
+        advice.push(Advice {
+            nutrient,
+            unit,
+            from,
+            to,
+        });
+    }
+    advice
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Category::Other;
+
+    fn letter(algorithm: Algorithm, input: &Input) -> char {
+        let score = calculate_nutriscore(algorithm, Other, &input.nutrition, input.fruits);
+        Other.score_to_letter(algorithm, score.total, false)
+    }
+
+    #[test]
+    fn an_a_needs_no_advice() {
+        let nutrition = Nutrition {
+            fibers: 5.0,
+            ..Nutrition::default()
+        };
+        assert!(advise(Algorithm::V2017, Other, &nutrition, 0.0, false).is_empty());
+    }
+
+    #[test]
+    fn advises_the_cutoff_of_the_better_grade() {
+        // 4 points for the sugar are a C, 13.5 g are 2 points and a B
+        let nutrition = Nutrition {
+            sugar: 20.0,
+            ..Nutrition::default()
+        };
+        let advice = advise(Algorithm::V2017, Other, &nutrition, 0.0, false);
+        let sugar = advice.iter().find(|a| a.nutrient == "Sugar").unwrap();
+        assert_eq!((sugar.from, sugar.to), (20.0, 13.5));
+    }
+
+    #[test]
+    fn every_advice_improves_the_grade() {
+        // 3 points and a C, one point from a B that every nutrient but the saturated fats
+        // without points can make up for
+        let nutrition = Nutrition {
+            energy: 700.0,
+            saturated_fats: 0.5,
+            sugar: 5.0,
+            proteins: 3.0,
+            salt: 0.3,
+            fibers: 1.0,
+            ..Nutrition::default()
+        };
+        let input = Input {
+            nutrition,
+            fruits: 10.0,
+        };
+        let current = letter(Algorithm::V2023, &input);
+        let advice = advise(Algorithm::V2023, Other, &nutrition, input.fruits, false);
+        assert_eq!(advice.len(), FIELDS.len() - 1, "{advice:?}");
+        for a in advice {
+            let (_, _, field, increase, _, _) =
+                FIELDS.into_iter().find(|f| f.0 == a.nutrient).unwrap();
+            let mut changed = Input {
+                nutrition,
+                fruits: input.fruits,
+            };
+            *field(&mut changed) = a.to;
+            assert!(letter(Algorithm::V2023, &changed) < current, "{a:?}");
+            assert_eq!(a.to > a.from, increase, "{a:?}");
+        }
+    }
+}
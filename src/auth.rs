@@ -0,0 +1,123 @@
+use crate::config::Config;
+use nutriscore::off::RateLimit;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// An API key with the requests of the last minute.
+struct Key {
+    /// The name of the key in the config file or its line in the keys file, for the logs.
+    name: String,
+    secret: String,
+    limit: RateLimit,
+}
+
+/// Why a request is not answered.
+pub enum Denied {
+    /// The request has no key or an unknown one.
+    Unauthorized,
+    /// The key has used up its requests, with the time until the next one is allowed.
+    RateLimited(Duration),
+}
+
+impl Denied {
+    pub fn message(&self) -> String {
+        match self {
+            Self::Unauthorized => "a valid API key is required".to_owned(),
+            Self::RateLimited(wait) => format!(
+                "the rate limit of the API key is reached, retry in {}s",
+                retry_after(*wait)
+            ),
+        }
+    }
+}
+
+/// Whole seconds to wait, rounded up, for a `Retry-After` header.
+pub fn retry_after(wait: Duration) -> u64 {
+    wait.as_secs() + u64::from(wait.subsec_nanos() > 0)
+}
+
+/// Compares two secrets in a time that does not depend on where they differ.
+fn same(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// The API keys of the server and how many requests each may send per minute.
+pub struct Keys(Vec<Key>);
+
+impl Keys {
+    /// The keys of the config file and of a file with one key per line, optionally followed by
+    /// its requests per minute. Keys without a limit get `per_minute`. `None` if there are no
+    /// keys, so the server is open.
+    pub fn load(config: &Config, file: Option<&Path>, per_minute: u32) -> io::Result<Option<Self>> {
+        let mut keys: Vec<Key> = config
+            .api_keys
+            .iter()
+            .map(|(name, key)| Key {
+                name: name.clone(),
+                secret: key.key.clone(),
+                limit: RateLimit::new(key.requests_per_minute.unwrap_or(per_minute)),
+            })
+            .collect();
+        if let Some(file) = file {
+            for (i, line) in fs::read_to_string(file)?.lines().enumerate() {
+                let line = line.split('#').next().unwrap_or_default();
+                let mut fields = line.split_whitespace();
+                let Some(secret) = fields.next() else {
+                    continue;
+                };
+                let limit = fields
+                    .next()
+                    .map(|limit| {
+                        limit.parse().map_err(|_| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!("{}:{}: {limit:?} is not a number", file.display(), i + 1),
+                            )
+                        })
+                    })
+                    .transpose()?;
+                keys.push(Key {
+                    name: format!("line {}", i + 1),
+                    secret: secret.to_owned(),
+                    limit: RateLimit::new(limit.unwrap_or(per_minute)),
+                });
+            }
+        }
+        Ok((!keys.is_empty()).then_some(Self(keys)))
+    }
+
+    /// Counts a request with `secret` and returns the name of its key, if the key is known and
+    /// within its rate limit.
+    pub fn check(&mut self, secret: Option<&str>) -> Result<&str, Denied> {
+        let secret = secret.ok_or(Denied::Unauthorized)?;
+        let key = self
+            .0
+            .iter_mut()
+            .fold(None, |found, key| {
+                if same(&key.secret, secret) {
+                    Some(key)
+                } else {
+                    found
+                }
+            })
+            .ok_or(Denied::Unauthorized)?;
+        key.limit
+            .spend(Instant::now())
+            .map_err(Denied::RateLimited)?;
+        Ok(&key.name)
+    }
+}
+
+/// The key of an `Authorization: Bearer` or an `X-API-Key` header.
+pub fn secret<'a>(authorization: Option<&'a str>, api_key: Option<&'a str>) -> Option<&'a str> {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or(api_key)
+        .map(str::trim)
+}
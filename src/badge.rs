@@ -0,0 +1,76 @@
+use std::fmt::Write;
+
+/// The colors of the letters A to E in the official logo.
+pub const GRADE_COLORS: [(char, &str); 5] = [
+    ('A', "#038141"),
+    ('B', "#85BB2F"),
+    ('C', "#FECB02"),
+    ('D', "#EE8100"),
+    ('E', "#E63E11"),
+];
+
+const FONT: &str = "font-family=\"Arial, Helvetica, DejaVu Sans, sans-serif\" font-weight=\"bold\"";
+
+/// Renders the Nutri-Score banner with `grade` highlighted as an SVG image.
+///
+/// The banner has no highlighted letter if `grade` is not one of A to E.
+#[must_use]
+pub fn badge_svg(grade: char) -> String {
+    let grade = grade.to_ascii_uppercase();
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"240\" height=\"130\" viewBox=\"0 0 240 130\">\n\
+         <clipPath id=\"letters\"><rect x=\"20\" y=\"50\" width=\"200\" height=\"60\" rx=\"12\"/></clipPath>\n\
+         <rect x=\"2\" y=\"2\" width=\"236\" height=\"126\" rx=\"14\" fill=\"#FFFFFF\" stroke=\"#7F7F7F\" stroke-width=\"3\"/>\n\
+         <text x=\"120\" y=\"32\" text-anchor=\"middle\" {FONT} font-size=\"20\" fill=\"#7F7F7F\">NUTRI-SCORE</text>\n\
+         <g clip-path=\"url(#letters)\">\n"
+    );
+    for (x, (letter, color)) in (20..).step_by(40).zip(GRADE_COLORS) {
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"50\" width=\"40\" height=\"60\" fill=\"{color}\"/>\n\
+             <text x=\"{}\" y=\"92\" text-anchor=\"middle\" {FONT} font-size=\"30\" fill=\"#FFFFFF\" fill-opacity=\"0.6\">{letter}</text>",
+            x + 20
+        );
+    }
+    svg.push_str("</g>\n");
+    if let Some((x, (_, color))) = (12..)
+        .step_by(40)
+        .zip(GRADE_COLORS)
+        .find(|(_, (letter, _))| *letter == grade)
+    {
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"40\" width=\"56\" height=\"80\" rx=\"14\" fill=\"{color}\" stroke=\"#FFFFFF\" stroke-width=\"4\"/>\n\
+             <text x=\"{}\" y=\"97\" text-anchor=\"middle\" {FONT} font-size=\"46\" fill=\"#FFFFFF\">{grade}</text>",
+            x + 28
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders a compact badge in the style of shields.io, reading "Nutri-Score" and the grade.
+#[must_use]
+pub fn shield_svg(grade: char) -> String {
+    let grade = grade.to_ascii_uppercase();
+    let color = GRADE_COLORS
+        .iter()
+        .find(|(letter, _)| *letter == grade)
+        .map_or("#9F9F9F", |(_, color)| color);
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"20\" viewBox=\"0 0 100 20\" role=\"img\" aria-label=\"Nutri-Score: {grade}\">\n\
+         <title>Nutri-Score: {grade}</title>\n\
+         <linearGradient id=\"shine\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#BBBBBB\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>\n\
+         <clipPath id=\"round\"><rect width=\"100\" height=\"20\" rx=\"3\"/></clipPath>\n\
+         <g clip-path=\"url(#round)\">\n\
+         <rect width=\"78\" height=\"20\" fill=\"#555555\"/>\n\
+         <rect x=\"78\" width=\"22\" height=\"20\" fill=\"{color}\"/>\n\
+         <rect width=\"100\" height=\"20\" fill=\"url(#shine)\"/>\n\
+         </g>\n\
+         <g fill=\"#FFFFFF\" text-anchor=\"middle\" font-family=\"Verdana, DejaVu Sans, sans-serif\" font-size=\"11\">\n\
+         <text x=\"39\" y=\"14\">Nutri-Score</text>\n\
+         <text x=\"89\" y=\"14\" font-weight=\"bold\">{grade}</text>\n\
+         </g>\n\
+         </svg>\n"
+    )
+}
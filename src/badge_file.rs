@@ -0,0 +1,42 @@
+use crate::{Badge, BadgeFormat, BadgeStyle};
+use nutriscore::{badge_svg, shield_svg};
+use resvg::{tiny_skia, usvg};
+use std::fs;
+use std::io;
+
+/// Saves the badge for `grade`.
+pub fn write(badge: &Badge, grade: char) -> io::Result<()> {
+    let svg = match badge.style {
+        BadgeStyle::Logo => badge_svg(grade),
+        BadgeStyle::Shield => shield_svg(grade),
+    };
+    match badge.format {
+        BadgeFormat::Svg => fs::write(&badge.out, svg),
+        BadgeFormat::Png => fs::write(&badge.out, png(&svg, badge.width)?),
+    }
+}
+
+/// Parses an SVG image, with the system fonts for its text.
+pub fn parse(svg: &str) -> io::Result<usvg::Tree> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    usvg::Tree::from_str(svg, &options).map_err(io::Error::other)
+}
+
+/// Renders an SVG image as PNG, scaled to the width if there is one.
+pub fn png(svg: &str, width: Option<u32>) -> io::Result<Vec<u8>> {
+    let tree = parse(svg)?;
+    let size = tree.size().to_int_size();
+    let size = width.map_or(Some(size), |width| size.scale_to_width(width));
+    let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "invalid badge width");
+    let size = size.ok_or_else(invalid)?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).ok_or_else(invalid)?;
+    #[allow(clippy::cast_precision_loss)]
+    let scale = size.width() as f32 / tree.size().width();
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    pixmap.encode_png().map_err(io::Error::other)
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+
+/// The lengths of GTIN-8, GTIN-12 (UPC-A), GTIN-13 (EAN-13) and GTIN-14.
+const GTIN_LENGTHS: [usize; 4] = [8, 12, 13, 14];
+
+/// Why a text is not a barcode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BarcodeError {
+    /// The text is neither digits nor a GS1 Digital Link with a GTIN.
+    Invalid(String),
+    /// The last digit of the GTIN does not match the others, with the one it would have to be.
+    CheckDigit { barcode: String, expected: char },
+}
+
+impl fmt::Display for BarcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(text) => write!(
+                f,
+                "{text:?} is neither a barcode nor a GS1 Digital Link with a GTIN"
+            ),
+            Self::CheckDigit { barcode, expected } => write!(
+                f,
+                "the check digit of {barcode} is wrong, it would be {expected}, is a digit mistyped?"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BarcodeError {}
+
+/// The check digit of a GTIN without it, the digits are weighted 3 and 1 from the right.
+fn check_digit(digits: &str) -> char {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .zip([3, 1].into_iter().cycle())
+        .map(|(digit, weight)| u32::from(digit - b'0') * weight)
+        .sum();
+    char::from_digit((10 - sum % 10) % 10, 10).unwrap_or('0')
+}
+
+/// The GTIN in a GS1 Digital Link, the path segment after `01` or `gtin`, like in
+/// `https://id.gs1.org/01/09506000134352/10/ABC`.
+fn digital_link(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let path = rest.split(['?', '#']).next()?;
+    let mut segments = path.split('/').skip(1);
+    while let Some(segment) = segments.next() {
+        if segment == "01" || segment == "gtin" {
+            return segments.next();
+        }
+    }
+    None
+}
+
+/// Reads a barcode or a GS1 Digital Link and returns the GTIN in the form Open Food Facts uses.
+///
+/// That is without extra leading zeros, GTIN-12 and GTIN-14 with a leading zero as 13 digits,
+/// and GTIN-8 padded to 8 digits. Spaces and dashes between the digits are left out.
+///
+/// The check digit of codes with the length of a GTIN is validated. Codes of other lengths, like
+/// the ones shops give their own products, are returned as they are.
+///
+/// # Errors
+///
+/// Returns [`BarcodeError::Invalid`] if the text is not a code or a link with one, and
+/// [`BarcodeError::CheckDigit`] if the check digit does not match.
+pub fn parse_barcode(text: &str) -> Result<String, BarcodeError> {
+    let text = text.trim();
+    let invalid = || BarcodeError::Invalid(text.to_owned());
+    let link = digital_link(text);
+    let code: String = link
+        .unwrap_or(text)
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-'))
+        .collect();
+    if code.is_empty() || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if !GTIN_LENGTHS.contains(&code.len()) {
+        return if link.is_some() {
+            Err(invalid())
+        } else {
+            Ok(code)
+        };
+    }
+    let (digits, check) = code.split_at(code.len() - 1);
+    let expected = check_digit(digits);
+    if !check.starts_with(expected) {
+        return Err(BarcodeError::CheckDigit {
+            barcode: code,
+            expected,
+        });
+    }
+    let significant = code.trim_start_matches('0');
+    let length = match significant.len() {
+        0..=8 => 8,
+        9..=13 => 13,
+        _ => 14,
+    };
+    Ok(format!("{significant:0>length$}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(text: &str) -> String {
+        parse_barcode(text).unwrap()
+    }
+
+    #[test]
+    fn gtins_of_every_length_are_valid() {
+        assert_eq!(parsed("96385074"), "96385074");
+        assert_eq!(parsed("3017620422003"), "3017620422003");
+        assert_eq!(parsed("10012345678902"), "10012345678902");
+    }
+
+    #[test]
+    fn gtins_get_the_length_of_open_food_facts() {
+        // GTIN-12 and GTIN-14 with a leading zero as 13 digits
+        assert_eq!(parsed("036000291452"), "0036000291452");
+        assert_eq!(parsed("09506000134352"), "9506000134352");
+        // and GTIN-8 padded with zeros to 13 digits back to 8
+        assert_eq!(parsed("0000096385074"), "96385074");
+    }
+
+    #[test]
+    fn spaces_and_dashes_are_left_out() {
+        assert_eq!(parsed(" 3 017620 422003 "), "3017620422003");
+        assert_eq!(parsed("9638-5074"), "96385074");
+    }
+
+    #[test]
+    fn the_check_digit_is_validated() {
+        for (code, expected) in [
+            ("96385075", '4'),
+            ("036000291453", '2'),
+            ("3017620422004", '3'),
+            ("10012345678900", '2'),
+        ] {
+            assert_eq!(
+                parse_barcode(code),
+                Err(BarcodeError::CheckDigit {
+                    barcode: code.to_owned(),
+                    expected
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn other_lengths_are_kept() {
+        assert_eq!(parsed("2012345"), "2012345");
+        assert_eq!(parsed("123456789"), "123456789");
+    }
+
+    #[test]
+    fn texts_that_are_not_digits_are_invalid() {
+        for text in ["", " ", "-", "30176204220O3", "barcode"] {
+            assert_eq!(
+                parse_barcode(text),
+                Err(BarcodeError::Invalid(text.trim().to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn digital_links_give_their_gtin() {
+        assert_eq!(
+            parsed("https://id.gs1.org/01/09506000134352/10/ABC"),
+            "9506000134352"
+        );
+        assert_eq!(
+            parsed("https://example.com/gtin/3017620422003?linkType=all"),
+            "3017620422003"
+        );
+        assert_eq!(parsed("http://example.com/01/96385074#info"), "96385074");
+    }
+
+    #[test]
+    fn digital_links_need_a_gtin() {
+        for link in [
+            "https://id.gs1.org/10/ABC",
+            "https://id.gs1.org/01",
+            "https://id.gs1.org/01/2012345",
+            "ftp://id.gs1.org/01/3017620422003",
+        ] {
+            assert_eq!(
+                parse_barcode(link),
+                Err(BarcodeError::Invalid(link.to_owned()))
+            );
+        }
+        assert!(matches!(
+            parse_barcode("https://id.gs1.org/01/3017620422004"),
+            Err(BarcodeError::CheckDigit { .. })
+        ));
+    }
+}
@@ -0,0 +1,365 @@
+use crate::i18n;
+use nutriscore::{calculate_nutriscore, Algorithm, Category, DrinkType, Nutrition, Product, Score};
+use serde::{de, Deserialize, Deserializer};
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct Row {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(deserialize_with = "number")]
+    energy: f32,
+    #[serde(deserialize_with = "number")]
+    fat: f32,
+    #[serde(deserialize_with = "number")]
+    saturated_fats: f32,
+    #[serde(deserialize_with = "number")]
+    sugar: f32,
+    #[serde(default, deserialize_with = "optional_number")]
+    carbohydrates: Option<f32>,
+    #[serde(deserialize_with = "number")]
+    proteins: f32,
+    #[serde(deserialize_with = "number")]
+    salt: f32,
+    #[serde(deserialize_with = "number")]
+    fibers: f32,
+    #[serde(deserialize_with = "number")]
+    fruits: f32,
+    category: String,
+    #[serde(default)]
+    water: bool,
+    #[serde(default)]
+    drink: Option<String>,
+    #[serde(default)]
+    sweeteners: bool,
+    #[serde(default, deserialize_with = "optional_number")]
+    alcohol: Option<f32>,
+}
+
+/// A number with a decimal point, or a decimal comma if the locale uses one.
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    i18n::number(&text).map_err(de::Error::custom)
+}
+
+fn optional_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f32>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    i18n::number(&text).map(Some).map_err(de::Error::custom)
+}
+
+/// Opens a CSV file, which is separated by semicolons if the header contains one, like the
+/// exports of spreadsheets in locales with a decimal comma.
+fn reader(input: &Path) -> io::Result<(csv::Reader<BufReader<File>>, u8)> {
+    let mut file = BufReader::new(File::open(input)?);
+    let start = file.fill_buf()?;
+    let header = start.split(|&b| b == b'\n').next().unwrap_or_default();
+    let delimiter = if header.contains(&b';') { b';' } else { b',' };
+    let reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(file);
+    Ok((reader, delimiter))
+}
+
+impl Row {
+    fn product(self, record: &csv::StringRecord) -> io::Result<Product> {
+        let invalid = |message: String| {
+            let line = record.position().map_or(0, csv::Position::line);
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {line}: {message}"),
+            )
+        };
+        let category: Category = self
+            .category
+            .parse()
+            .map_err(|_| invalid(format!("unknown category {:?}", self.category)))?;
+        let drink: Option<DrinkType> = match self.drink.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(drink) => Some(
+                drink
+                    .parse()
+                    .map_err(|_| invalid(format!("unknown drink {drink:?}")))?,
+            ),
+        };
+        Ok(Product {
+            name: self.name.filter(|name| !name.is_empty()),
+            category,
+            nutrition: Nutrition {
+                energy: self.energy,
+                fat: self.fat,
+                saturated_fats: self.saturated_fats,
+                sugar: self.sugar,
+                carbohydrates: self.carbohydrates,
+                proteins: self.proteins,
+                salt: self.salt,
+                fibers: self.fibers,
+                contains_sweeteners: self.sweeteners,
+            },
+            fruits: self.fruits,
+            water: self.water,
+            drink,
+            alcohol: self.alcohol,
+            ingredients: None,
+            eco: None,
+        })
+    }
+}
+
+/// Reads the products of a CSV file with the line numbers of their rows.
+pub fn products(input: &Path) -> io::Result<Vec<(u64, Product)>> {
+    let (mut reader, _) = reader(input)?;
+    let headers = reader.headers()?.clone();
+    let mut products = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let row: Row = record.deserialize(Some(&headers))?;
+        let line = record.position().map_or(0, csv::Position::line);
+        products.push((line, row.product(&record)?));
+    }
+    Ok(products)
+}
+
+#[cfg(feature = "parquet")]
+fn parquet(algorithm: Algorithm, input: &Path, output: Option<&Path>) -> io::Result<()> {
+    let output = output.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a Parquet file cannot be written to stdout, use --output",
+        )
+    })?;
+    crate::batch_parquet::run(algorithm, input, output)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet(_: Algorithm, _: &Path, _: Option<&Path>) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this build cannot read Parquet files, build it with --features parquet",
+    ))
+}
+
+/// A column of the output.
+enum Column {
+    /// The column of the input with this index.
+    Input(usize),
+    Score,
+    Grade,
+    NegativePoints,
+    PositivePoints,
+    /// The points of the component with this key, see [`key`].
+    Points(String),
+}
+
+/// A component name as part of a column name, like `fruits_vegs` for "Fruits & Vegs".
+fn key(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn unknown_column(name: &str, components: &[String]) -> io::Error {
+    let points: Vec<String> = components.iter().map(|c| format!("{c}_points")).collect();
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "unknown column {name:?}, use a column of the input, score, grade, negative_points, \
+             positive_points or one of {}",
+            points.join(", ")
+        ),
+    )
+}
+
+impl Column {
+    /// Parses the name of a column, the names of `--columns`.
+    fn new(algorithm: Algorithm, name: &str, headers: &csv::StringRecord) -> io::Result<Self> {
+        let mut components: Vec<String> = Vec::new();
+        for category in [Category::Other, Category::Drinks] {
+            let score = calculate_nutriscore(algorithm, category, &Nutrition::default(), 0.0);
+            for c in score.negative.iter().chain(&score.positive) {
+                if !components.contains(&key(c.name)) {
+                    components.push(key(c.name));
+                }
+            }
+        }
+        Ok(match name {
+            "score" => Self::Score,
+            "grade" => Self::Grade,
+            "negative_points" => Self::NegativePoints,
+            "positive_points" => Self::PositivePoints,
+            _ => match name.strip_suffix("_points") {
+                Some(component) if components.iter().any(|c| c == component) => {
+                    Self::Points(component.to_owned())
+                }
+                _ => headers
+                    .iter()
+                    .position(|header| header == name)
+                    .map(Self::Input)
+                    .ok_or_else(|| unknown_column(name, &components))?,
+            },
+        })
+    }
+
+    /// The value of this column for a row, empty for points of components that the product has
+    /// not been scored with and for the results of products without a Nutri-Score.
+    fn value(&self, record: &csv::StringRecord, result: Option<(&Score, char)>) -> String {
+        let Self::Input(index) = self else {
+            return result.map_or_else(String::new, |(score, letter)| self.result(score, letter));
+        };
+        record.get(*index).unwrap_or_default().to_owned()
+    }
+
+    fn result(&self, score: &Score, letter: char) -> String {
+        match self {
+            Self::Input(_) => String::new(),
+            Self::Score => score.total.to_string(),
+            Self::Grade => letter.to_string(),
+            Self::NegativePoints => score.negative_points().to_string(),
+            Self::PositivePoints => score.positive_points().to_string(),
+            Self::Points(component) => score
+                .negative
+                .iter()
+                .chain(&score.positive)
+                .find(|c| key(c.name) == *component)
+                .map(|c| c.points.to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Scores rows with the `headers`, and passes the names of the output columns and then the
+/// output row of every row to `write`. See [`run`] for the columns.
+pub fn score_records(
+    algorithm: Algorithm,
+    headers: &csv::StringRecord,
+    records: impl IntoIterator<Item = io::Result<csv::StringRecord>>,
+    columns: &[String],
+    mut write: impl FnMut(Vec<String>) -> io::Result<()>,
+) -> io::Result<()> {
+    let (names, columns): (Vec<String>, Vec<Column>) = if columns.is_empty() {
+        headers
+            .iter()
+            .enumerate()
+            .map(|(index, header)| (header.to_owned(), Column::Input(index)))
+            .chain([
+                ("score".to_owned(), Column::Score),
+                ("grade".to_owned(), Column::Grade),
+            ])
+            .unzip()
+    } else {
+        columns
+            .iter()
+            .map(|name| Ok((name.clone(), Column::new(algorithm, name, headers)?)))
+            .collect::<io::Result<Vec<_>>>()?
+            .into_iter()
+            .unzip()
+    };
+    write(names)?;
+    let (mut rows, mut not_applicable) = (0_usize, 0_usize);
+    for record in records {
+        let record = record?;
+        let line = record.position().map_or(0, csv::Position::line);
+        let row: Row = record.deserialize(Some(headers))?;
+        let product = row.product(&record)?;
+        let score = product.is_applicable().then(|| product.score(algorithm));
+        let result = score
+            .as_ref()
+            .map(|score| (score, product.letter(algorithm, score)));
+        if let Some((score, letter)) = result {
+            tracing::debug!(line, score = score.total, %letter, "row scored");
+        } else {
+            not_applicable += 1;
+            tracing::debug!(line, "row not applicable");
+        }
+        rows += 1;
+        write(columns.iter().map(|c| c.value(&record, result)).collect())?;
+    }
+    tracing::info!(rows, not_applicable, "batch scored");
+    Ok(())
+}
+
+#[cfg(feature = "xlsx")]
+fn xlsx(
+    algorithm: Algorithm,
+    input: &Path,
+    output: Option<&Path>,
+    sheet: Option<&str>,
+    columns: &[String],
+) -> io::Result<()> {
+    let output = output.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "an Excel workbook cannot be written to stdout, use --output",
+        )
+    })?;
+    crate::batch_xlsx::run(algorithm, input, output, sheet, columns)
+}
+
+#[cfg(not(feature = "xlsx"))]
+fn xlsx(_: Algorithm, _: &Path, _: Option<&Path>, _: Option<&str>, _: &[String]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this build cannot read Excel workbooks, build it with --features xlsx",
+    ))
+}
+
+/// Scores a CSV file, a Parquet file if `input` ends with .parquet or the `sheet` of an Excel
+/// workbook if it ends with .xlsx.
+///
+/// The output has the columns of the input, the score and the grade, or else the `columns`
+/// in their order.
+#[tracing::instrument(skip_all, fields(input = %input.display(), %algorithm))]
+pub fn run(
+    algorithm: Algorithm,
+    input: &Path,
+    output: Option<&Path>,
+    sheet: Option<&str>,
+    columns: &[String],
+) -> io::Result<()> {
+    let extension = |name: &str| {
+        input
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case(name))
+    };
+    if extension("xlsx") {
+        return xlsx(algorithm, input, output, sheet, columns);
+    }
+    if sheet.is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--sheet only works for Excel workbooks",
+        ));
+    }
+    if extension("parquet") {
+        if !columns.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--columns only works for CSV files and Excel workbooks",
+            ));
+        }
+        return parquet(algorithm, input, output);
+    }
+    let (mut reader, delimiter) = reader(input)?;
+    let output: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(output);
+    let headers = reader.headers()?.clone();
+    let records = reader
+        .records()
+        .map(|record| record.map_err(io::Error::from));
+    score_records(algorithm, &headers, records, columns, |row| {
+        writer.write_record(&row).map_err(io::Error::from)
+    })?;
+    writer.flush()
+}
@@ -0,0 +1,37 @@
+use arrow_array::Array;
+use nutriscore::dataframe::{score_dataframe, scored_schema};
+use nutriscore::Algorithm;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// Like the CSV batch mode, but reads and writes Parquet files.
+pub fn run(algorithm: Algorithm, input: &Path, output: &Path) -> io::Result<()> {
+    let builder =
+        ParquetRecordBatchReaderBuilder::try_new(File::open(input)?).map_err(io::Error::other)?;
+    let schema = scored_schema(builder.schema());
+    let reader = builder.build().map_err(io::Error::other)?;
+    let mut writer =
+        ArrowWriter::try_new(File::create(output)?, schema, None).map_err(io::Error::other)?;
+    let mut rows = 0;
+    let mut skipped = 0;
+    for batch in reader {
+        let batch = batch.map_err(io::Error::other)?;
+        let scored = score_dataframe(algorithm, &batch).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {e}", input.display()),
+            )
+        })?;
+        rows += batch.num_rows();
+        skipped += scored.column(scored.num_columns() - 1).null_count();
+        writer.write(&scored).map_err(io::Error::other)?;
+    }
+    writer.close().map_err(io::Error::other)?;
+    if skipped > 0 {
+        eprintln!("{skipped} of {rows} rows have missing values and were not scored");
+    }
+    Ok(())
+}
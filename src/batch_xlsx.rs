@@ -0,0 +1,134 @@
+use crate::batch::score_records;
+use calamine::{open_workbook, Data, Range, Reader, Xlsx};
+use nutriscore::Algorithm;
+use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+use std::io;
+use std::path::Path;
+
+/// The name of the added sheet with the results.
+const RESULTS: &str = "Nutri-Score";
+
+fn xlsx_error(e: XlsxError) -> io::Error {
+    io::Error::other(e)
+}
+
+fn index<T: TryFrom<usize>>(i: usize) -> io::Result<T> {
+    T::try_from(i).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "the sheet is too large"))
+}
+
+/// Writes a cell as a number if it is one, so that the written sheets can still be calculated.
+fn write_cell(sheet: &mut Worksheet, row: u32, column: u16, cell: &Data) -> io::Result<()> {
+    match cell {
+        Data::Empty => return Ok(()),
+        Data::Float(value) => sheet.write_number(row, column, *value),
+        #[allow(clippy::cast_precision_loss)]
+        Data::Int(value) => sheet.write_number(row, column, *value as f64),
+        Data::Bool(value) => sheet.write_boolean(row, column, *value),
+        other => sheet.write_string(row, column, other.to_string()),
+    }
+    .map(drop)
+    .map_err(xlsx_error)
+}
+
+/// A value of the results, as a number or a boolean if it is one.
+fn typed(text: String) -> Data {
+    match text.as_str() {
+        "true" => Data::Bool(true),
+        "false" => Data::Bool(false),
+        _ => text.parse().map_or(Data::String(text), Data::Float),
+    }
+}
+
+/// Copies the values of a sheet, without formatting or formulas.
+fn copy(sheet: &mut Worksheet, range: &Range<Data>) -> io::Result<()> {
+    let (start_row, start_column) = range.start().unwrap_or_default();
+    for (r, cells) in range.rows().enumerate() {
+        for (c, cell) in cells.iter().enumerate() {
+            let row = start_row + index::<u32>(r)?;
+            let column = index::<u16>(usize::try_from(start_column).unwrap_or_default() + c)?;
+            write_cell(sheet, row, column, cell)?;
+        }
+    }
+    Ok(())
+}
+
+/// Scores the products of a sheet like the CSV batch mode, and writes a workbook with the values
+/// of all sheets of the input and a new sheet with the results.
+pub fn run(
+    algorithm: Algorithm,
+    input: &Path,
+    output: &Path,
+    sheet: Option<&str>,
+    columns: &[String],
+) -> io::Result<()> {
+    let invalid = |message: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {message}", input.display()),
+        )
+    };
+    let mut workbook: Xlsx<_> =
+        open_workbook(input).map_err(|e: calamine::XlsxError| invalid(e.to_string()))?;
+    let names = workbook.sheet_names();
+    let name = match sheet {
+        Some(sheet) if names.iter().any(|name| name == sheet) => sheet.to_owned(),
+        Some(sheet) => return Err(invalid(format!("no sheet named {sheet:?}"))),
+        None => names
+            .first()
+            .cloned()
+            .ok_or_else(|| invalid("the workbook has no sheets".to_owned()))?,
+    };
+
+    let mut out = Workbook::new();
+    let mut products = None;
+    for sheet_name in &names {
+        let range = workbook
+            .worksheet_range(sheet_name)
+            .map_err(|e| invalid(e.to_string()))?;
+        copy(
+            out.add_worksheet()
+                .set_name(sheet_name)
+                .map_err(xlsx_error)?,
+            &range,
+        )?;
+        if *sheet_name == name {
+            products = Some(range);
+        }
+    }
+    let products = products.unwrap_or_default();
+    let (start_row, _) = products.start().unwrap_or_default();
+    let mut rows = products.rows().map(|cells| {
+        cells
+            .iter()
+            .map(ToString::to_string)
+            .collect::<csv::StringRecord>()
+    });
+    let headers = rows.next().unwrap_or_default();
+    let records = rows.enumerate().filter_map(|(i, mut record)| {
+        if record.iter().all(str::is_empty) {
+            return None;
+        }
+        // the line of the row, as in the error messages for CSV files
+        let mut position = csv::Position::new();
+        position.set_line(u64::from(start_row) + i as u64 + 2);
+        record.set_position(Some(position));
+        Some(Ok(record))
+    });
+
+    let results = out.add_worksheet();
+    let results_name = if names.iter().any(|name| name == RESULTS) {
+        format!("{RESULTS} results")
+    } else {
+        RESULTS.to_owned()
+    };
+    results.set_name(&results_name).map_err(xlsx_error)?;
+    let mut row = 0;
+    score_records(algorithm, &headers, records, columns, |cells| {
+        for (c, cell) in cells.into_iter().enumerate() {
+            write_cell(results, row, index(c)?, &typed(cell))?;
+        }
+        row += 1;
+        Ok(())
+    })?;
+    out.save(output).map_err(xlsx_error)
+}
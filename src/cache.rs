@@ -0,0 +1,207 @@
+use crate::config;
+use crate::i18n::tr_with;
+use clap::Args;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// How long a cached answer is used before it is fetched again.
+const DEFAULT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The flags of the commands that look products up online.
+#[derive(Args, Debug)]
+pub struct CacheArgs {
+    /// Fetch answers again when they are older than this, like 12h or 30d [default: 7d]
+    #[clap(long, value_name = "DURATION", value_parser = ttl)]
+    cache_ttl: Option<Duration>,
+    /// Neither read nor write the cache in ~/.cache/nutriscore
+    #[clap(long)]
+    no_cache: bool,
+}
+
+/// Parses a duration for `--cache-ttl`.
+fn ttl(text: &str) -> Result<Duration, String> {
+    humantime::parse_duration(text).map_err(|e| format!("{text:?} is not a duration: {e}"))
+}
+
+fn dir() -> Option<PathBuf> {
+    config::user_file("XDG_CACHE_HOME", ".cache", "")
+}
+
+/// Answers of the online databases, one JSON file per lookup in `~/.cache/nutriscore`.
+pub struct Cache {
+    /// `None` with `--no-cache` or without a home directory.
+    dir: Option<PathBuf>,
+    ttl: Duration,
+    /// The answers of [`Cache::get`] that were found and the ones that had to be fetched.
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Cache {
+    /// The cache of the flags, with the `cache-ttl` of the config file as the default.
+    pub fn new(args: &CacheArgs, config_ttl: Option<&str>) -> io::Result<Self> {
+        let ttl = match (args.cache_ttl, config_ttl) {
+            (Some(ttl), _) => ttl,
+            (None, Some(text)) => {
+                self::ttl(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            }
+            (None, None) => DEFAULT_TTL,
+        };
+        Ok(Self {
+            dir: dir().filter(|_| !args.no_cache),
+            ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
+    }
+
+    /// How many answers were found in the cache and how many had to be fetched, for the metrics.
+    pub fn counts(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+
+    /// The file of a lookup, with the characters of `key` that are not allowed in file names
+    /// escaped like in URLs.
+    fn path(&self, source: &str, key: &str) -> Option<PathBuf> {
+        let mut name = String::new();
+        for byte in key.bytes() {
+            if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+                name.push(char::from(byte));
+            } else {
+                let _ = write!(name, "%{byte:02X}");
+            }
+        }
+        Some(self.dir.as_ref()?.join(source).join(name + ".json"))
+    }
+
+    /// The cached answer and whether it is older than the TTL, `None` if there is none that can
+    /// be read.
+    fn read<T: DeserializeOwned>(&self, source: &str, key: &str) -> Option<(T, bool)> {
+        let path = self.path(source, key)?;
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        let value = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+        Some((value, age > self.ttl))
+    }
+
+    fn write<T: Serialize>(&self, source: &str, key: &str, value: &T) -> io::Result<()> {
+        let Some(path) = self.path(source, key) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_vec(value)?)
+    }
+
+    /// The cached answer of `source` for `key`, or else the one of `fetch`, which is cached.
+    ///
+    /// If `fetch` fails with another error than not found, an expired answer is used, so
+    /// products that were looked up before can be scored offline.
+    pub fn get<T, F>(&self, source: &str, key: &str, fetch: F) -> io::Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> io::Result<T>,
+    {
+        let cached = self.read(source, key);
+        let stale = match cached {
+            Some((value, false)) => {
+                tracing::debug!(source, key, "found the answer in the cache");
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(value);
+            }
+            Some((value, true)) => Some(value),
+            None => None,
+        };
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        match fetch() {
+            Ok(value) => {
+                if let Err(e) = self.write(source, key, &value) {
+                    tracing::warn!(error = %e, "cannot write the cache");
+                }
+                Ok(value)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(e),
+            Err(e) => stale.map_or(Err(e), |value| {
+                eprintln!("{}", tr_with("cached-answer", &[("key", key.into())]));
+                Ok(value)
+            }),
+        }
+    }
+}
+
+/// The key of a cache file, with the escaped characters of [`Cache::path`] restored.
+fn key(name: &str) -> Option<String> {
+    let name = name.strip_suffix(".json")?;
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut rest = name.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' && tail.len() >= 2 {
+            let hex = std::str::from_utf8(&tail[..2]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// All cached answers of `source` that can be read, by key, regardless of their age.
+pub fn entries<T: DeserializeOwned>(source: &str) -> io::Result<Vec<(String, T)>> {
+    let Some(dir) = dir() else {
+        return Ok(Vec::new());
+    };
+    let entries = match fs::read_dir(dir.join(source)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut answers = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(key) = path
+            .file_name()
+            .and_then(|name| key(&name.to_string_lossy()))
+        else {
+            continue;
+        };
+        if let Ok(Ok(value)) = fs::read(&path).map(|bytes| serde_json::from_slice(&bytes)) {
+            answers.push((key, value));
+        } else {
+            tracing::debug!(path = %path.display(), "cannot read a cached answer");
+        }
+    }
+    Ok(answers)
+}
+
+/// Deletes all cached answers.
+pub fn clear() -> io::Result<()> {
+    let Some(dir) = dir() else {
+        return Ok(());
+    };
+    let mut count = 0;
+    for source in ["off", "usda"] {
+        let dir = dir.join(source);
+        match fs::read_dir(&dir) {
+            Ok(entries) => count += entries.count(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        }
+        fs::remove_dir_all(dir)?;
+    }
+    println!("Cached answers deleted: {count}");
+    Ok(())
+}
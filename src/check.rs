@@ -0,0 +1,114 @@
+use crate::{batch, product_file, Format};
+use nutriscore::{Algorithm, Product};
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+#[derive(Serialize)]
+struct Offender<'a> {
+    file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u64>,
+    name: Option<&'a str>,
+    score: isize,
+    grade: char,
+}
+
+#[derive(Serialize)]
+struct JsonCheck<'a> {
+    min_grade: char,
+    checked: usize,
+    offenders: &'a [Offender<'a>],
+}
+
+/// The products of a product file, or of every row of a CSV file.
+fn read(path: &Path) -> io::Result<Vec<(Option<u64>, Product)>> {
+    if path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"))
+    {
+        let products = batch::products(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        Ok(products
+            .into_iter()
+            .map(|(line, product)| (Some(line), product))
+            .collect())
+    } else {
+        Ok(vec![(None, product_file::read(path)?)])
+    }
+}
+
+/// Parses a grade from A to E, ignoring the case.
+pub fn grade(text: &str) -> Result<char, String> {
+    match text.to_ascii_uppercase().as_str() {
+        grade @ ("A" | "B" | "C" | "D" | "E") => Ok(grade.chars().next().unwrap_or('E')),
+        _ => Err(format!("{text:?} is not a grade from A to E")),
+    }
+}
+
+/// Scores all products of the files and exits with 1 if any of them is worse than `min_grade`.
+pub fn run(
+    algorithm: Algorithm,
+    format: Format,
+    min_grade: char,
+    files: &[PathBuf],
+) -> io::Result<()> {
+    let mut products = Vec::new();
+    for file in files {
+        for (line, product) in read(file)? {
+            products.push((file, line, product));
+        }
+    }
+    let offenders: Vec<Offender> = products
+        .iter()
+        .filter_map(|(file, line, product)| {
+            let score = product.score(algorithm);
+            let grade = product.letter(algorithm, &score);
+            (grade > min_grade).then(|| Offender {
+                file: file.display().to_string(),
+                line: *line,
+                name: product.name.as_deref(),
+                score: score.total,
+                grade,
+            })
+        })
+        .collect();
+
+    match format {
+        Format::Text => {
+            for o in &offenders {
+                let location = o
+                    .line
+                    .map_or_else(|| o.file.clone(), |line| format!("{}:{line}", o.file));
+                let name = o.name.map(|name| format!(" {name}")).unwrap_or_default();
+                println!("{location}{name}: {} (score {})", o.grade, o.score);
+            }
+            if offenders.is_empty() {
+                println!(
+                    "All {} products have grade {min_grade} or better",
+                    products.len()
+                );
+            } else {
+                println!(
+                    "{} of {} products are worse than {min_grade}",
+                    offenders.len(),
+                    products.len()
+                );
+            }
+        }
+        Format::Json => {
+            let result = JsonCheck {
+                min_grade,
+                checked: products.len(),
+                offenders: &offenders,
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    if !offenders.is_empty() {
+        io::stdout().flush()?;
+        process::exit(1);
+    }
+    Ok(())
+}
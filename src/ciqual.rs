@@ -0,0 +1,166 @@
+use crate::config;
+use nutriscore::Nutrition;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A food of the CIQUAL table of the composition of foods.
+#[derive(Debug)]
+pub struct Food {
+    pub code: String,
+    pub name: String,
+    pub nutrition: Nutrition,
+}
+
+/// The CIQUAL table, read from a CSV export of the English or French Excel file.
+#[derive(Debug)]
+pub struct Table {
+    foods: Vec<Food>,
+}
+
+// Beginnings of the column names in the English and the French table
+const CODE: &[&str] = &["alim_code"];
+const NAME: &[&str] = &["alim_nom_eng", "alim_nom_fr"];
+const ENERGY: &[&str] = &[
+    "Energy, Regulation EU No 1169/2011 (kJ",
+    "Energie, Règlement UE N° 1169/2011 (kJ",
+];
+const FAT: &[&str] = &["Fat (g", "Lipides (g"];
+const SATURATED_FATS: &[&str] = &["FA saturated (g", "AG saturés (g"];
+const SUGAR: &[&str] = &["Sugars (g", "Sucres (g"];
+const CARBOHYDRATES: &[&str] = &["Carbohydrate (g", "Glucides (g"];
+const PROTEINS: &[&str] = &["Protein", "Protéines"];
+const SALT: &[&str] = &["Salt (g", "Sel chlorure de sodium (g"];
+const FIBERS: &[&str] = &["Fibres (g", "Fibres alimentaires (g"];
+
+fn invalid(path: &Path, message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {message}", path.display()),
+    )
+}
+
+/// The default location of the table, ~/.local/share/nutriscore/ciqual.csv.
+pub fn default_path() -> Option<PathBuf> {
+    config::user_file("XDG_DATA_HOME", ".local/share", "ciqual.csv")
+}
+
+/// Parses a value of the table. Traces count as 0, upper bounds like `< 0,5` as the bound and
+/// missing values (`-`) as `None`.
+fn value(text: &str) -> Option<f32> {
+    let text = text.trim().replace(',', ".");
+    match text.as_str() {
+        "" | "-" => None,
+        "traces" => Some(0.0),
+        _ => text.trim_start_matches('<').trim().parse().ok(),
+    }
+}
+
+impl Table {
+    pub fn read(path: Option<&Path>) -> io::Result<Self> {
+        let path = match path {
+            Some(path) => path.to_owned(),
+            None => default_path().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "cannot find the home directory, use --ciqual",
+                )
+            })?,
+        };
+        let text = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "cannot read the CIQUAL table {}: {e}, \
+                     download it from https://ciqual.anses.fr and save it as CSV",
+                    path.display()
+                ),
+            )
+        })?;
+        let text = text.trim_start_matches('\u{feff}');
+        let header = text.lines().next().unwrap_or_default();
+        let delimiter = if header.contains(';') { b';' } else { b',' };
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(text.as_bytes());
+        let headers = reader.headers()?.clone();
+        let column = |names: &[&str]| {
+            headers
+                .iter()
+                .position(|h| names.iter().any(|name| h.trim().starts_with(name)))
+                .ok_or_else(|| invalid(&path, format!("no column {:?}", names[0])))
+        };
+        let code = column(CODE)?;
+        let name = column(NAME)?;
+        let energy = column(ENERGY)?;
+        let fat = column(FAT)?;
+        let saturated_fats = column(SATURATED_FATS)?;
+        let sugar = column(SUGAR)?;
+        let carbohydrates = column(CARBOHYDRATES)?;
+        let proteins = column(PROTEINS)?;
+        let salt = column(SALT)?;
+        let fibers = column(FIBERS)?;
+
+        let mut foods = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let get = |i: usize| record.get(i).and_then(value);
+            foods.push(Food {
+                code: record.get(code).unwrap_or_default().trim().to_owned(),
+                name: record.get(name).unwrap_or_default().trim().to_owned(),
+                nutrition: Nutrition {
+                    energy: get(energy).unwrap_or_default(),
+                    fat: get(fat).unwrap_or_default(),
+                    saturated_fats: get(saturated_fats).unwrap_or_default(),
+                    sugar: get(sugar).unwrap_or_default(),
+                    carbohydrates: get(carbohydrates),
+                    proteins: get(proteins).unwrap_or_default(),
+                    salt: get(salt).unwrap_or_default(),
+                    fibers: get(fibers).unwrap_or_default(),
+                    contains_sweeteners: false,
+                },
+            });
+        }
+        Ok(Self { foods })
+    }
+
+    /// Finds a food by its code, its name or a unique part of its name, ignoring the case.
+    pub fn find(&self, query: &str) -> io::Result<&Food> {
+        let query = query.trim();
+        let lower = query.to_lowercase();
+        if let Some(food) = self
+            .foods
+            .iter()
+            .find(|f| f.code == query || f.name.to_lowercase() == lower)
+        {
+            return Ok(food);
+        }
+        let matches: Vec<&Food> = self
+            .foods
+            .iter()
+            .filter(|f| f.name.to_lowercase().contains(&lower))
+            .collect();
+        match matches.as_slice() {
+            [food] => Ok(food),
+            [] => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no CIQUAL food matches {query:?}"),
+            )),
+            _ => {
+                let names: Vec<String> = matches
+                    .iter()
+                    .take(5)
+                    .map(|f| format!("{} ({})", f.name, f.code))
+                    .collect();
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "{} CIQUAL foods match {query:?}, use the code or a longer name: {}",
+                        matches.len(),
+                        names.join(", ")
+                    ),
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{product_file, Format};
+use nutriscore::{Algorithm, Component, Score};
+use serde::Serialize;
+use std::fmt::Write;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Serialize)]
+struct JsonProduct<'a> {
+    file: String,
+    name: Option<&'a str>,
+    score: isize,
+    grade: char,
+    negative: &'a [Component],
+    positive: &'a [Component],
+}
+
+fn find<'a>(score: &'a Score, name: &str) -> Option<&'a Component> {
+    score
+        .negative
+        .iter()
+        .chain(&score.positive)
+        .find(|c| c.name == name)
+}
+
+const fn effective_points(c: &Component) -> usize {
+    if c.counted {
+        c.points
+    } else {
+        0
+    }
+}
+
+/// One table row, marking the best values with a `*`.
+fn row<T: Copy + Ord + ToString>(
+    label: &str,
+    cells: &[Option<(T, String)>],
+    lower_is_better: bool,
+    width: usize,
+) -> String {
+    let values: Vec<T> = cells.iter().flatten().map(|(v, _)| *v).collect();
+    let best = if lower_is_better {
+        values.iter().min()
+    } else {
+        values.iter().max()
+    };
+    let highlight = values.len() > 1 && values.iter().any(|v| Some(v) != best);
+    let mut row = format!("{label:13}");
+    for cell in cells {
+        let text = match cell {
+            Some((v, text)) if highlight && Some(v) == best => format!("{text}*"),
+            Some((_, text)) => format!("{text} "),
+            None => "- ".to_owned(),
+        };
+        let _ = write!(row, " {text:>width$}");
+    }
+    row
+}
+
+pub fn run(algorithm: Algorithm, format: Format, files: &[PathBuf]) -> io::Result<()> {
+    let products = files
+        .iter()
+        .map(|f| product_file::read(f))
+        .collect::<io::Result<Vec<_>>>()?;
+    let scores: Vec<Score> = products.iter().map(|p| p.score(algorithm)).collect();
+    let letters: Vec<char> = products
+        .iter()
+        .zip(&scores)
+        .map(|(p, s)| p.letter(algorithm, s))
+        .collect();
+    let labels: Vec<String> = products
+        .iter()
+        .zip(files)
+        .map(|(p, f)| p.name.clone().unwrap_or_else(|| f.display().to_string()))
+        .collect();
+
+    match format {
+        Format::Text => {
+            let width = labels.iter().map(|l| l.len() + 1).max().unwrap_or(0).max(6);
+            print!("{:13}", "");
+            for label in &labels {
+                print!(" {label:>width$}", width = width - 1);
+                print!(" ");
+            }
+            println!();
+
+            let mut rows: Vec<(&str, bool)> = Vec::new();
+            for score in &scores {
+                let components = score
+                    .negative
+                    .iter()
+                    .map(|c| (c.name, true))
+                    .chain(score.positive.iter().map(|c| (c.name, false)));
+                for row in components {
+                    if !rows.contains(&row) {
+                        rows.push(row);
+                    }
+                }
+            }
+            for (name, negative) in rows {
+                let cells: Vec<_> = scores
+                    .iter()
+                    .map(|s| {
+                        find(s, name).map(|c| {
+                            let text = if c.counted {
+                                c.points.to_string()
+                            } else {
+                                format!("({})", c.points)
+                            };
+                            (effective_points(c), text)
+                        })
+                    })
+                    .collect();
+                println!("{}", row(name, &cells, negative, width));
+            }
+            let totals: Vec<_> = scores
+                .iter()
+                .map(|s| Some((s.total, s.total.to_string())))
+                .collect();
+            println!("{}", row("Total", &totals, true, width));
+            let grades: Vec<_> = letters.iter().map(|l| Some((*l, l.to_string()))).collect();
+            println!("{}", row("Grade", &grades, true, width));
+            println!("\n* best value, (n) points not counted");
+        }
+        Format::Json => {
+            let result: Vec<JsonProduct> = products
+                .iter()
+                .zip(files)
+                .zip(scores.iter().zip(&letters))
+                .map(|((p, f), (s, l))| JsonProduct {
+                    file: f.display().to_string(),
+                    name: p.name.as_deref(),
+                    score: s.total,
+                    grade: *l,
+                    negative: &s.negative,
+                    positive: &s.positive,
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(points: &[Option<usize>]) -> Vec<Option<(usize, String)>> {
+        points
+            .iter()
+            .map(|p| p.map(|p| (p, p.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn marks_the_best_values() {
+        let cells = cells(&[Some(3), Some(1), None, Some(1)]);
+        assert_eq!(
+            row("Sugar", &cells, true, 3),
+            "Sugar          3   1*  -   1*"
+        );
+        assert_eq!(
+            row("Fibers", &cells, false, 3),
+            "Fibers         3*  1   -   1 "
+        );
+    }
+
+    #[test]
+    fn marks_nothing_without_a_difference() {
+        let cells = cells(&[Some(2), None, Some(2)]);
+        assert_eq!(
+            row("Protein", &cells, false, 3),
+            "Protein        2   -   2 "
+        );
+    }
+
+    #[test]
+    fn uncounted_points_are_no_better() {
+        let score = nutriscore::calculate_nutriscore(
+            Algorithm::V2017,
+            nutriscore::Category::Other,
+            &nutriscore::Nutrition {
+                energy: 3000.0,
+                sugar: 40.0,
+                proteins: 10.0,
+                ..nutriscore::Nutrition::default()
+            },
+            0.0,
+        );
+        let protein = find(&score, "Protein").unwrap();
+        assert_eq!((protein.points, protein.counted), (5, false));
+        assert_eq!(effective_points(protein), 0);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::error;
+use crate::i18n::{tr_with, Lang};
+use crate::jurisdiction::Jurisdiction;
+use crate::{EnergyUnit, Format, NutritionArgs};
+use nutriscore::{
+    Algorithm, Category, CategoryDefinition, CutoffTables, Product, Score, ScoringModel,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Defaults read from `~/.config/nutriscore/config.toml`, overridden by command line flags.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub algorithm: Option<Algorithm>,
+    pub jurisdiction: Option<Jurisdiction>,
+    pub energy_unit: Option<EnergyUnit>,
+    pub category: Option<Category>,
+    pub format: Option<Format>,
+    pub locale: Option<String>,
+    pub decimal_comma: Option<bool>,
+    pub progress_bars: Option<bool>,
+    pub history: Option<bool>,
+    /// The Open Food Facts instance of `lookup`.
+    pub off_url: Option<String>,
+    /// How long looked up products are cached, like `30d`.
+    pub cache_ttl: Option<String>,
+    /// The proxy of the lookups.
+    pub proxy: Option<String>,
+    /// Certificate authorities the lookups trust besides the built-in ones.
+    pub ca_bundle: Option<PathBuf>,
+    pub exit_codes: BTreeMap<char, i32>,
+    /// The API keys of `serve` by name.
+    pub api_keys: BTreeMap<String, ApiKeyConfig>,
+    /// Categories that are not built in, by name, for `--custom-category`.
+    pub categories: BTreeMap<String, CategoryConfig>,
+}
+
+/// An API key of `serve`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ApiKeyConfig {
+    pub key: String,
+    /// Overrides `--key-rate-limit` for this key.
+    pub requests_per_minute: Option<u32>,
+}
+
+/// A category defined in the config file by changing the definition of a built-in one.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CategoryConfig {
+    /// The built-in category whose definition is changed.
+    base: Category,
+    #[serde(default)]
+    cutoffs: CutoffTables,
+    /// The highest scores of the grades A to D.
+    grades: Option<[isize; 4]>,
+    water: Option<bool>,
+    sweeteners: Option<Setting>,
+    protein_limit: Option<Setting>,
+    protein_cap: Option<Setting>,
+}
+
+/// A number of points in a [`CategoryConfig`], or `false` to remove the one of the base
+/// category, like `protein-limit = false` to always count the protein.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(untagged)]
+enum Setting {
+    Points(usize),
+    Enabled(bool),
+}
+
+impl Setting {
+    /// The setting `name` of the definition, `base` if it is not given.
+    fn merge(
+        setting: Option<Self>,
+        name: &str,
+        base: Option<usize>,
+    ) -> Result<Option<usize>, String> {
+        match setting {
+            None => Ok(base),
+            Some(Self::Points(points)) => Ok(Some(points)),
+            Some(Self::Enabled(false)) => Ok(None),
+            Some(Self::Enabled(true)) => Err(format!("{name} has to be a number or false")),
+        }
+    }
+}
+
+impl CategoryConfig {
+    /// The definition of the category in an algorithm, settings that are not given are the ones of
+    /// the base category.
+    fn definition(&self, algorithm: Algorithm) -> Result<CategoryDefinition, String> {
+        let mut definition = self
+            .base
+            .definition(algorithm)
+            .with_tables(self.cutoffs.clone())?;
+        if let Some(grades) = self.grades {
+            if !grades.is_sorted() {
+                return Err("the grades have to be sorted".to_owned());
+            }
+            definition.grades = grades.map(Some);
+        }
+        definition.water = self.water.unwrap_or(definition.water);
+        definition.sweeteners =
+            Setting::merge(self.sweeteners, "sweeteners", definition.sweeteners)?;
+        definition.protein_limit = Setting::merge(
+            self.protein_limit,
+            "protein-limit",
+            definition.protein_limit,
+        )?;
+        definition.protein_cap =
+            Setting::merge(self.protein_cap, "protein-cap", definition.protein_cap)?;
+        Ok(definition)
+    }
+}
+
+/// The Nutri-Score with a category from the config file.
+#[derive(Clone, Debug)]
+pub struct CustomCategory {
+    name: String,
+    algorithm: Algorithm,
+    definition: CategoryDefinition,
+}
+
+impl CustomCategory {
+    /// Looks up a category of the config file.
+    pub fn new(config: &Config, name: &str, algorithm: Algorithm) -> io::Result<Self> {
+        let category = config.categories.get(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the category {name} is not defined in the config file"),
+            )
+        })?;
+        let definition = category.definition(algorithm).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("category {name}: {e}"))
+        })?;
+        Ok(Self {
+            name: name.to_owned(),
+            algorithm,
+            definition,
+        })
+    }
+}
+
+impl ScoringModel for CustomCategory {
+    fn name(&self) -> String {
+        format!("Nutri-Score {} for {}", self.algorithm, self.name)
+    }
+
+    fn score(&self, product: &Product) -> Score {
+        self.definition.score(&product.nutrition, product.fruits)
+    }
+
+    fn grade(&self, product: &Product, score: &Score) -> String {
+        self.definition
+            .letter(score.total, product.is_water())
+            .to_string()
+    }
+}
+
+impl Config {
+    /// Fills in the nutrition flags that were not given on the command line.
+    pub fn apply(&self, args: &mut NutritionArgs) {
+        if !args.milk {
+            args.category = args.category.or(self.category);
+        }
+        args.energy_unit = args.energy_unit.or(self.energy_unit);
+    }
+}
+
+/// The file `name` in the `nutriscore` directory of `$variable`, or of `~/fallback` if it is unset.
+pub fn user_file(variable: &str, fallback: &str, name: &str) -> Option<PathBuf> {
+    let dir = match env::var_os(variable) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(fallback),
+    };
+    Some(dir.join("nutriscore").join(name))
+}
+
+/// Reads the given config file, or the default one if it exists.
+pub fn load(path: Option<&Path>) -> io::Result<Config> {
+    let path = match path {
+        Some(path) => path.to_owned(),
+        None => match user_file("XDG_CONFIG_HOME", ".config", "config.toml") {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        },
+    };
+    let text = fs::read_to_string(&path)?;
+    let config: Config = toml::from_str(&text).map_err(|e| error::toml(&path, text.clone(), &e))?;
+    if let Some(locale) = config
+        .locale
+        .as_deref()
+        .filter(|l| Lang::from_locale(l).is_none())
+    {
+        eprintln!(
+            "{}",
+            tr_with("unsupported-locale", &[("locale", locale.into())])
+        );
+    }
+    Ok(config)
+}
@@ -0,0 +1,169 @@
+use crate::{Algorithm, Category, CategoryDefinition, Product, Score, ScoringModel};
+use serde::{Deserialize, Serialize};
+
+/// Cutoff tables that replace the built-in ones of a category, see [`CustomCutoffs`].
+///
+/// A table that is `None` keeps the built-in one. `sodium` is the table for salt in the 2023
+/// algorithm, and `fats` the one for the ratio of saturates to fat for oils and fats.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CutoffTables {
+    pub energy: Option<Vec<f32>>,
+    pub sugars: Option<Vec<f32>>,
+    pub fats: Option<Vec<f32>>,
+    #[serde(alias = "salt")]
+    pub sodium: Option<Vec<f32>>,
+    pub fibers: Option<Vec<f32>>,
+    pub protein: Option<Vec<f32>>,
+    pub fruits: Option<Vec<f32>>,
+}
+
+/// The Nutri-Score of one algorithm with custom cutoff tables, to try out changes of the
+/// thresholds.
+///
+/// Like [`CategoryDefinition::with_tables`], this is meant to be created once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomCutoffs {
+    algorithm: Algorithm,
+    definitions: Vec<(Category, CategoryDefinition)>,
+}
+
+impl CustomCutoffs {
+    /// Replaces the tables of the categories with the given ones, see
+    /// [`CategoryDefinition::with_tables`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a message with the category if a table cannot replace the built-in one.
+    pub fn new(
+        algorithm: Algorithm,
+        tables: impl IntoIterator<Item = (Category, CutoffTables)>,
+    ) -> Result<Self, String> {
+        let definitions = tables
+            .into_iter()
+            .map(|(category, tables)| {
+                let definition = category
+                    .definition(algorithm)
+                    .with_tables(tables)
+                    .map_err(|e| format!("{category}: {e}"))?;
+                Ok((category, definition))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self {
+            algorithm,
+            definitions,
+        })
+    }
+
+    /// The algorithm the tables are used with.
+    #[must_use]
+    pub const fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// The definition a product of the category is scored with.
+    fn definition(&self, category: Category) -> CategoryDefinition {
+        self.definitions
+            .iter()
+            .rev()
+            .find(|(c, _)| *c == category)
+            .map_or_else(|| category.definition(self.algorithm), |(_, d)| *d)
+    }
+}
+
+impl ScoringModel for CustomCutoffs {
+    fn name(&self) -> String {
+        format!("Nutri-Score {} with custom cutoffs", self.algorithm)
+    }
+
+    fn score(&self, product: &Product) -> Score {
+        self.definition(product.category)
+            .score(&product.nutrition, product.fruits)
+    }
+
+    fn grade(&self, product: &Product, score: &Score) -> String {
+        self.definition(product.category)
+            .letter(score.total, product.is_water())
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Nutrition;
+
+    fn product(category: Category, energy: f32) -> Product {
+        Product {
+            name: None,
+            category,
+            nutrition: Nutrition {
+                energy,
+                ..Nutrition::default()
+            },
+            fruits: 0.0,
+            water: false,
+            ingredients: None,
+            eco: None,
+            drink: None,
+            alcohol: None,
+        }
+    }
+
+    /// The built-in energy table of 2017 with every cutoff lowered by 300 kJ.
+    fn lower_energy() -> CutoffTables {
+        let energy = Category::Other.definition(Algorithm::V2017).cutoffs[0];
+        CutoffTables {
+            energy: Some(energy.iter().map(|c| c - 300.0).collect()),
+            ..CutoffTables::default()
+        }
+    }
+
+    #[test]
+    fn custom_tables_replace_the_built_in_ones() {
+        let model =
+            CustomCutoffs::new(Algorithm::V2017, [(Category::Other, lower_energy())]).unwrap();
+        let score = model.score(&product(Category::Other, 100.0));
+        assert_eq!(score.negative[0].points, 1);
+        assert_eq!(
+            product(Category::Other, 100.0)
+                .score(Algorithm::V2017)
+                .total,
+            0
+        );
+    }
+
+    #[test]
+    fn other_categories_keep_the_built_in_tables() {
+        let model =
+            CustomCutoffs::new(Algorithm::V2017, [(Category::Other, lower_energy())]).unwrap();
+        let cheese = product(Category::Cheese, 100.0);
+        assert_eq!(model.score(&cheese), cheese.score(Algorithm::V2017));
+    }
+
+    #[test]
+    fn tables_must_keep_the_maximum_points() {
+        let short = CutoffTables {
+            sugars: Some(vec![1.0, 2.0]),
+            ..CutoffTables::default()
+        };
+        let error = CustomCutoffs::new(Algorithm::V2017, [(Category::Other, short)]).unwrap_err();
+        assert!(error.contains("has 2 cutoffs instead of 10"), "{error}");
+    }
+
+    #[test]
+    fn tables_must_be_sorted_numbers() {
+        for table in [
+            vec![2.0, 1.0, 3.0, 4.0, 5.0],
+            vec![1.0, 2.0, f32::NAN, 4.0, 5.0],
+        ] {
+            let fibers = CutoffTables {
+                fibers: Some(table),
+                ..CutoffTables::default()
+            };
+            let error =
+                CustomCutoffs::new(Algorithm::V2017, [(Category::Other, fibers)]).unwrap_err();
+            assert!(error.contains("not a sorted list of numbers"), "{error}");
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::error;
+use nutriscore::{Algorithm, Category, CustomCutoffs, CutoffTables};
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A file with custom cutoff tables for `--cutoffs`, with a table per category, e.g.
+/// `[other]` with `sugars = [...]`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct CutoffsFile {
+    drinks: Option<CutoffTables>,
+    milk_drinks: Option<CutoffTables>,
+    cheese: Option<CutoffTables>,
+    oils_and_fats: Option<CutoffTables>,
+    red_meat: Option<CutoffTables>,
+    other: Option<CutoffTables>,
+}
+
+/// Reads the cutoff tables for an algorithm and checks that they can replace the built-in ones.
+pub fn read(path: &Path, algorithm: Algorithm) -> io::Result<CustomCutoffs> {
+    let invalid = |e: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {e}", path.display()),
+        )
+    };
+    let text = fs::read_to_string(path)?;
+    let file: CutoffsFile =
+        toml::from_str(&text).map_err(|e| error::toml(path, text.clone(), &e))?;
+    let tables = [
+        (Category::Drinks, file.drinks),
+        (Category::MilkDrinks, file.milk_drinks),
+        (Category::Cheese, file.cheese),
+        (Category::OilsAndFats, file.oils_and_fats),
+        (Category::RedMeat, file.red_meat),
+        (Category::Other, file.other),
+    ];
+    let tables = tables
+        .into_iter()
+        .filter_map(|(category, tables)| Some((category, tables?)));
+    CustomCutoffs::new(algorithm, tables).map_err(invalid)
+}
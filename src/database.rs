@@ -0,0 +1,397 @@
+use crate::{
+    config, edit_product, print_text, product_file, read_product, Format, JsonResult,
+    ProductCommand, TextOptions,
+};
+use nutriscore::{Algorithm, Product};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A past result of `product score`.
+#[derive(Debug, Serialize)]
+struct PastResult {
+    algorithm: Algorithm,
+    score: isize,
+    grade: char,
+    scored_at: String,
+}
+
+/// A version of a product from before `product edit`.
+#[derive(Debug, Serialize)]
+struct PastVersion {
+    product: Product,
+    replaced_at: String,
+}
+
+pub struct Database {
+    connection: Connection,
+}
+
+fn sql_error(e: rusqlite::Error) -> io::Error {
+    io::Error::other(e)
+}
+
+fn not_found(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no product named {name:?}"),
+    )
+}
+
+/// The database of `product` without `--database`.
+fn default_path() -> io::Result<PathBuf> {
+    config::user_file("XDG_DATA_HOME", ".local/share", "products.db").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "cannot find the home directory, use --database",
+        )
+    })
+}
+
+/// The products of the default database, without creating it if there is none.
+pub fn saved_products() -> io::Result<Vec<Product>> {
+    match default_path() {
+        Ok(path) if path.exists() => Database::open(Some(&path))?.products(),
+        _ => Ok(Vec::new()),
+    }
+}
+
+impl Database {
+    pub fn open(path: Option<&Path>) -> io::Result<Self> {
+        let path = match path {
+            Some(path) => path.to_owned(),
+            None => default_path()?,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let connection = Connection::open(&path).map_err(sql_error)?;
+        connection
+            .execute_batch(
+                "PRAGMA foreign_keys = ON;
+                 CREATE TABLE IF NOT EXISTS products (
+                     id INTEGER PRIMARY KEY,
+                     name TEXT NOT NULL UNIQUE,
+                     product TEXT NOT NULL
+                 );
+                 CREATE TABLE IF NOT EXISTS results (
+                     product_id INTEGER NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+                     algorithm TEXT NOT NULL,
+                     score INTEGER NOT NULL,
+                     grade TEXT NOT NULL,
+                     scored_at TEXT NOT NULL DEFAULT (datetime('now'))
+                 );
+                 CREATE TABLE IF NOT EXISTS versions (
+                     product_id INTEGER NOT NULL REFERENCES products (id) ON DELETE CASCADE,
+                     product TEXT NOT NULL,
+                     replaced_at TEXT NOT NULL DEFAULT (datetime('now'))
+                 );",
+            )
+            .map_err(sql_error)?;
+        Ok(Self { connection })
+    }
+
+    fn add(&self, name: &str, product: &Product, replace: bool) -> io::Result<()> {
+        let json = serde_json::to_string(product)?;
+        let sql = if replace {
+            "INSERT INTO products (name, product) VALUES (?1, ?2)
+             ON CONFLICT (name) DO UPDATE SET product = excluded.product"
+        } else {
+            "INSERT INTO products (name, product) VALUES (?1, ?2)"
+        };
+        match self.connection.execute(sql, params![name, json]) {
+            Ok(_) => Ok(()),
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.code == ErrorCode::ConstraintViolation =>
+            {
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("a product named {name:?} exists already, use --replace"),
+                ))
+            }
+            Err(e) => Err(sql_error(e)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> io::Result<(i64, Product)> {
+        let (id, json): (i64, String) = self
+            .connection
+            .query_row(
+                "SELECT id, product FROM products WHERE name = ?1",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(sql_error)?
+            .ok_or_else(|| not_found(name))?;
+        Ok((id, serde_json::from_str(&json)?))
+    }
+
+    /// Replaces a product and keeps the old version.
+    fn update(&mut self, id: i64, product: &Product) -> io::Result<()> {
+        let json = serde_json::to_string(product)?;
+        let transaction = self.connection.transaction().map_err(sql_error)?;
+        transaction
+            .execute(
+                "INSERT INTO versions (product_id, product)
+                 SELECT id, product FROM products WHERE id = ?1",
+                params![id],
+            )
+            .map_err(sql_error)?;
+        transaction
+            .execute(
+                "UPDATE products SET product = ?2 WHERE id = ?1",
+                params![id, json],
+            )
+            .map_err(sql_error)?;
+        transaction.commit().map_err(sql_error)
+    }
+
+    fn versions(&self, id: i64) -> io::Result<Vec<PastVersion>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT product, replaced_at FROM versions WHERE product_id = ?1 ORDER BY rowid",
+            )
+            .map_err(sql_error)?;
+        let rows: Vec<(String, String)> = statement
+            .query_map(params![id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(sql_error)?
+            .collect::<Result<_, _>>()
+            .map_err(sql_error)?;
+        rows.into_iter()
+            .map(|(json, replaced_at)| {
+                Ok(PastVersion {
+                    product: serde_json::from_str(&json)?,
+                    replaced_at,
+                })
+            })
+            .collect()
+    }
+
+    fn products(&self) -> io::Result<Vec<Product>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT product FROM products ORDER BY name")
+            .map_err(sql_error)?;
+        let rows: Vec<String> = statement
+            .query_map([], |row| row.get(0))
+            .map_err(sql_error)?
+            .collect::<Result<_, _>>()
+            .map_err(sql_error)?;
+        rows.iter()
+            .map(|json| Ok(serde_json::from_str(json)?))
+            .collect()
+    }
+
+    fn names(&self) -> io::Result<Vec<String>> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT name FROM products ORDER BY name")
+            .map_err(sql_error)?;
+        let names = statement
+            .query_map([], |row| row.get(0))
+            .map_err(sql_error)?
+            .collect::<Result<_, _>>()
+            .map_err(sql_error)?;
+        Ok(names)
+    }
+
+    fn remove(&self, name: &str) -> io::Result<()> {
+        let removed = self
+            .connection
+            .execute("DELETE FROM products WHERE name = ?1", params![name])
+            .map_err(sql_error)?;
+        if removed == 0 {
+            return Err(not_found(name));
+        }
+        Ok(())
+    }
+
+    fn record(&self, id: i64, algorithm: Algorithm, score: isize, grade: char) -> io::Result<()> {
+        self.connection
+            .execute(
+                "INSERT INTO results (product_id, algorithm, score, grade) VALUES (?1, ?2, ?3, ?4)",
+                params![id, algorithm.to_string(), score, grade.to_string()],
+            )
+            .map_err(sql_error)?;
+        Ok(())
+    }
+
+    fn history(&self, id: i64) -> io::Result<Vec<PastResult>> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT algorithm, score, grade, scored_at FROM results
+                 WHERE product_id = ?1 ORDER BY rowid",
+            )
+            .map_err(sql_error)?;
+        let rows = statement
+            .query_map(params![id], |row| {
+                let algorithm: String = row.get(0)?;
+                let grade: String = row.get(2)?;
+                Ok(PastResult {
+                    algorithm: algorithm.parse().unwrap_or_default(),
+                    score: row.get(1)?,
+                    grade: grade.chars().next().unwrap_or('?'),
+                    scored_at: row.get(3)?,
+                })
+            })
+            .map_err(sql_error)?
+            .collect::<Result<_, _>>()
+            .map_err(sql_error)?;
+        Ok(rows)
+    }
+}
+
+/// Scores a saved product and records the result.
+fn score(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    db: &Database,
+    id: i64,
+    product: &Product,
+) -> io::Result<char> {
+    let score = product.score(algorithm);
+    let letter = product.letter(algorithm, &score);
+    db.record(id, algorithm, score.total, letter)?;
+    match format {
+        Format::Text => print_text(algorithm, text, &product.nutrition, &score, letter),
+        Format::Json => {
+            let result = JsonResult {
+                score: score.total,
+                grade: letter,
+                negative: &score.negative,
+                positive: &score.positive,
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(letter)
+}
+
+#[derive(Serialize)]
+struct JsonListed<'a> {
+    name: &'a str,
+    category: String,
+    last_result: Option<&'a PastResult>,
+}
+
+#[derive(Serialize)]
+struct JsonShown<'a> {
+    product: &'a Product,
+    history: &'a [PastResult],
+    versions: &'a [PastVersion],
+}
+
+pub fn run(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    database: Option<&Path>,
+    command: ProductCommand,
+) -> io::Result<Option<char>> {
+    let mut db = Database::open(database)?;
+    match command {
+        ProductCommand::Add {
+            name,
+            file,
+            replace,
+            mut nutrition,
+        } => {
+            // the name is given, so it is not asked and completed
+            nutrition.product_name = Some(name.clone());
+            let mut product = match file {
+                Some(file) => product_file::read(&file)?,
+                None => read_product(algorithm, &nutrition)?,
+            };
+            product.name = Some(name.clone());
+            db.add(&name, &product, replace)?;
+            println!("Saved {name}");
+        }
+        ProductCommand::List => {
+            let mut listed = Vec::new();
+            for name in db.names()? {
+                let (id, product) = db.get(&name)?;
+                listed.push((name, product, db.history(id)?.pop()));
+            }
+            match format {
+                Format::Text => {
+                    for (name, product, last) in &listed {
+                        let last = last.as_ref().map_or_else(
+                            || "not scored yet".to_owned(),
+                            |s| format!("{} ({}, {})", s.grade, s.algorithm, s.scored_at),
+                        );
+                        println!("{name:30} {:15} {last}", product.category.to_string());
+                    }
+                }
+                Format::Json => {
+                    let json: Vec<JsonListed> = listed
+                        .iter()
+                        .map(|(name, product, last)| JsonListed {
+                            name,
+                            category: product.category.to_string(),
+                            last_result: last.as_ref(),
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                }
+            }
+        }
+        ProductCommand::Show { name } => {
+            let (id, product) = db.get(&name)?;
+            let history = db.history(id)?;
+            let versions = db.versions(id)?;
+            match format {
+                Format::Text => {
+                    print!("{}", product_file::to_string(&product)?);
+                    if !history.is_empty() {
+                        println!("\nPast results:");
+                    }
+                    for s in &history {
+                        println!(
+                            "  {} {} (score {}, algorithm {})",
+                            s.scored_at, s.grade, s.score, s.algorithm
+                        );
+                    }
+                    if !versions.is_empty() {
+                        println!("\nEarlier versions, replaced at:");
+                    }
+                    for v in &versions {
+                        println!("  {}", v.replaced_at);
+                    }
+                }
+                Format::Json => {
+                    let json = JsonShown {
+                        product: &product,
+                        history: &history,
+                        versions: &versions,
+                    };
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                }
+            }
+        }
+        ProductCommand::Edit { name } => {
+            let (id, old) = db.get(&name)?;
+            let mut product = edit_product(algorithm, &old)?;
+            product.name = Some(name.clone());
+            db.update(id, &product)?;
+            if format == Format::Text {
+                println!("Saved {name}, the old version is kept in its history\n");
+            }
+            return score(algorithm, format, text, &db, id, &product).map(Some);
+        }
+        ProductCommand::Score { name } => {
+            let (id, product) = db.get(&name)?;
+            return score(algorithm, format, text, &db, id, &product).map(Some);
+        }
+        ProductCommand::Remove { name } => {
+            db.remove(&name)?;
+            println!("Removed {name}");
+        }
+    }
+    Ok(None)
+}
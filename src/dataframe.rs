@@ -0,0 +1,114 @@
+//! Scoring of Arrow record batches, enabled with the `arrow` feature.
+//!
+//! Polars data frames can be converted from and to record batches with their Arrow interface.
+
+use crate::{calculate_nutriscore, Algorithm, Category, Nutrition};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float32Type;
+use arrow_array::{Array, ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use std::sync::Arc;
+
+/// The numeric columns every batch needs, in the same units as [`Nutrition`].
+pub const NUMBER_COLUMNS: [&str; 8] = [
+    "energy",
+    "fat",
+    "saturated_fats",
+    "sugar",
+    "proteins",
+    "salt",
+    "fibers",
+    "fruits",
+];
+
+fn cast(batch: &RecordBatch, name: &str, to: &DataType) -> Result<Option<ArrayRef>, ArrowError> {
+    batch
+        .column_by_name(name)
+        .map(|column| arrow_cast::cast(column, to))
+        .transpose()
+}
+
+fn required(batch: &RecordBatch, name: &str, to: &DataType) -> Result<ArrayRef, ArrowError> {
+    cast(batch, name, to)?.ok_or_else(|| ArrowError::SchemaError(format!("no column {name:?}")))
+}
+
+/// The schema of `input` with the `score` and `grade` columns added by [`score_dataframe`].
+#[must_use]
+pub fn scored_schema(input: &Schema) -> SchemaRef {
+    let mut fields: Vec<Field> = input.fields().iter().map(|f| f.as_ref().clone()).collect();
+    fields.push(Field::new("score", DataType::Int64, true));
+    fields.push(Field::new("grade", DataType::Utf8, true));
+    Arc::new(Schema::new(fields))
+}
+
+/// Scores every row of a record batch and returns it with a `score` and a `grade` column added.
+///
+/// The batch needs the [`NUMBER_COLUMNS`], which can have any numeric type, and a `category`
+/// column with the names of [`Category`]. `carbohydrates`, `water` and `sweeteners` are optional.
+/// Rows with a missing value get a null score and grade.
+///
+/// # Errors
+///
+/// Fails if a column is missing or cannot be converted, or if a category is unknown.
+pub fn score_dataframe(
+    algorithm: Algorithm,
+    batch: &RecordBatch,
+) -> Result<RecordBatch, ArrowError> {
+    let numbers = NUMBER_COLUMNS
+        .iter()
+        .map(|name| required(batch, name, &DataType::Float32))
+        .collect::<Result<Vec<_>, _>>()?;
+    let numbers: Vec<_> = numbers
+        .iter()
+        .map(AsArray::as_primitive::<Float32Type>)
+        .collect();
+    let carbohydrates = cast(batch, "carbohydrates", &DataType::Float32)?;
+    let carbohydrates = carbohydrates
+        .as_ref()
+        .map(AsArray::as_primitive::<Float32Type>);
+    let category = required(batch, "category", &DataType::Utf8)?;
+    let category = category.as_string::<i32>();
+    let water = cast(batch, "water", &DataType::Boolean)?;
+    let water = water.as_ref().map(AsArray::as_boolean);
+    let sweeteners = cast(batch, "sweeteners", &DataType::Boolean)?;
+    let sweeteners = sweeteners.as_ref().map(AsArray::as_boolean);
+
+    let mut scores = Vec::with_capacity(batch.num_rows());
+    let mut grades = Vec::with_capacity(batch.num_rows());
+    for row in 0..batch.num_rows() {
+        if category.is_null(row) || numbers.iter().any(|c| c.is_null(row)) {
+            scores.push(None);
+            grades.push(None);
+            continue;
+        }
+        let [energy, fat, saturated_fats, sugar, proteins, salt, fibers, fruits] =
+            std::array::from_fn(|i| numbers[i].value(row));
+        let flag = |c: Option<&BooleanArray>| c.is_some_and(|c| c.is_valid(row) && c.value(row));
+        let nutrition = Nutrition {
+            energy,
+            fat,
+            saturated_fats,
+            sugar,
+            carbohydrates: carbohydrates
+                .filter(|c| c.is_valid(row))
+                .map(|c| c.value(row)),
+            proteins,
+            salt,
+            fibers,
+            contains_sweeteners: flag(sweeteners),
+        };
+        let name = category.value(row);
+        let category: Category = name
+            .parse()
+            .map_err(|_| ArrowError::InvalidArgumentError(format!("unknown category {name:?}")))?;
+        let score = calculate_nutriscore(algorithm, category, &nutrition, fruits);
+        let letter = category.score_to_letter(algorithm, score.total, flag(water));
+        scores.push(Some(score.total as i64));
+        grades.push(Some(letter.to_string()));
+    }
+
+    let mut columns = batch.columns().to_vec();
+    columns.push(Arc::new(Int64Array::from(scores)));
+    columns.push(Arc::new(StringArray::from(grades)));
+    RecordBatch::try_new(scored_schema(&batch.schema()), columns)
+}
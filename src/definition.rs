@@ -0,0 +1,398 @@
+use crate::Algorithm::{V2017, V2023};
+use crate::Category::{Cheese, Drinks, MilkDrinks, OilsAndFats, Other, RedMeat};
+use crate::{
+    component, is_sorted, round, Algorithm, Category, Component, CutoffTables, Nutrition, Score,
+    DRINKS_ENERGY_CUTOFFS_2023, DRINKS_FRUITS_CUTOFFS_2023, DRINKS_PROTEIN_CUTOFFS_2023,
+    DRINKS_SUGAR_CUTOFFS_2023, ENERGY_CUTOFFS, ENERGY_FROM_SATURATES_CUTOFFS_2023, FIBERS_CUTOFFS,
+    FIBERS_CUTOFFS_2023, FRUITS_CUTOFFS, FRUITS_CUTOFFS_2023, PROTEIN_CUTOFFS,
+    PROTEIN_CUTOFFS_2023, SALT_CUTOFFS_2023, SATURATED_FATS_CUTOFF, SATURATED_FATS_RATIO_CUTOFFS,
+    SODIUM_CUTOFF, SUGAR_CUTOFFS, SUGAR_CUTOFFS_2023,
+};
+
+/// How a category is scored and graded, see [`Category::definition`].
+///
+/// The built-in categories are defined with it, and new ones can be made by changing the
+/// definition of a similar one.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CategoryDefinition {
+    /// The cutoff tables for energy, saturated fats, sugars, protein, sodium (salt in 2023),
+    /// fibers and fruits.
+    pub cutoffs: [&'static [f32]; 7],
+    /// The highest scores of the grades A to D, higher scores are E. `None` if only water gets
+    /// the grade.
+    pub grades: [Option<isize>; 4],
+    /// Whether water always gets an A.
+    pub water: bool,
+    /// Whether it is scored as a beverage, which decides the official document it follows.
+    pub beverage: bool,
+    /// Uses the energy from saturated fats instead of the energy, 37 kJ per g.
+    pub energy_from_saturates: bool,
+    /// Uses the percentage of saturated fats in the fat instead of their amount.
+    pub saturates_ratio: bool,
+    /// Whether the salt instead of the sodium is scored.
+    pub salt: bool,
+    /// The points for non-nutritive sweeteners, if they count.
+    pub sweeteners: Option<usize>,
+    /// Protein is not counted from this many negative points on.
+    pub protein_limit: Option<usize>,
+    /// With the protein limit, fibers are not counted either.
+    pub fibers_limited: bool,
+    /// The protein limit does not apply from this many points for fruits and vegetables on.
+    pub protein_limit_fruits: Option<usize>,
+    /// The highest number of points for protein.
+    pub protein_cap: Option<usize>,
+}
+
+const GRADES: [Option<isize>; 4] = [Some(-1), Some(2), Some(10), Some(18)];
+
+impl Category {
+    /// The definition of the category in an algorithm.
+    #[must_use]
+    pub fn definition(self, algorithm: Algorithm) -> CategoryDefinition {
+        match algorithm {
+            V2017 => self.definition_2017(),
+            V2023 => self.definition_2023(),
+        }
+    }
+
+    fn definition_2017(self) -> CategoryDefinition {
+        let drinks = self == Drinks;
+        let energy = if drinks {
+            &[
+                0.0, 30.0, 60.0, 90.0, 120.0, 150.0, 180.0, 210.0, 240.0, 270.0,
+            ]
+        } else {
+            &ENERGY_CUTOFFS
+        };
+        let fats = if self == OilsAndFats {
+            &SATURATED_FATS_RATIO_CUTOFFS
+        } else {
+            &SATURATED_FATS_CUTOFF
+        };
+        let sugar = if drinks {
+            &[0.0, 1.5, 3.0, 4.5, 6.0, 7.5, 9.0, 10.5, 12.0, 13.5]
+        } else {
+            &SUGAR_CUTOFFS
+        };
+        let fruits = if drinks {
+            &[0.0, 40.0, 40.0, 60.0, 60.0, 80.0, 80.0, 80.0, 80.0, 80.0]
+        } else {
+            &FRUITS_CUTOFFS
+        };
+        CategoryDefinition {
+            cutoffs: [
+                energy,
+                fats,
+                sugar,
+                &PROTEIN_CUTOFFS,
+                &SODIUM_CUTOFF,
+                &FIBERS_CUTOFFS,
+                fruits,
+            ],
+            grades: if drinks {
+                [None, Some(1), Some(5), Some(9)]
+            } else {
+                GRADES
+            },
+            water: drinks,
+            beverage: drinks,
+            energy_from_saturates: false,
+            saturates_ratio: self == OilsAndFats,
+            salt: false,
+            sweeteners: None,
+            protein_limit: (self != Cheese).then_some(11),
+            fibers_limited: true,
+            protein_limit_fruits: Some(5),
+            protein_cap: None,
+        }
+    }
+
+    fn definition_2023(self) -> CategoryDefinition {
+        let beverage = matches!(self, Drinks | MilkDrinks);
+        let cutoffs: [&'static [f32]; 7] = match self {
+            Drinks | MilkDrinks => [
+                &DRINKS_ENERGY_CUTOFFS_2023,
+                &SATURATED_FATS_CUTOFF,
+                &DRINKS_SUGAR_CUTOFFS_2023,
+                &DRINKS_PROTEIN_CUTOFFS_2023,
+                &SALT_CUTOFFS_2023,
+                &FIBERS_CUTOFFS_2023,
+                &DRINKS_FRUITS_CUTOFFS_2023,
+            ],
+            OilsAndFats => [
+                &ENERGY_FROM_SATURATES_CUTOFFS_2023,
+                &SATURATED_FATS_RATIO_CUTOFFS,
+                &SUGAR_CUTOFFS_2023,
+                &PROTEIN_CUTOFFS_2023,
+                &SALT_CUTOFFS_2023,
+                &FIBERS_CUTOFFS_2023,
+                &FRUITS_CUTOFFS_2023,
+            ],
+            Cheese | RedMeat | Other => [
+                &ENERGY_CUTOFFS,
+                &SATURATED_FATS_CUTOFF,
+                &SUGAR_CUTOFFS_2023,
+                &PROTEIN_CUTOFFS_2023,
+                &SALT_CUTOFFS_2023,
+                &FIBERS_CUTOFFS_2023,
+                &FRUITS_CUTOFFS_2023,
+            ],
+        };
+        CategoryDefinition {
+            cutoffs,
+            grades: match self {
+                Drinks | MilkDrinks => [None, Some(2), Some(6), Some(9)],
+                OilsAndFats => [Some(-6), Some(2), Some(10), Some(18)],
+                Cheese | RedMeat | Other => [Some(0), Some(2), Some(10), Some(18)],
+            },
+            water: beverage,
+            beverage,
+            energy_from_saturates: self == OilsAndFats,
+            saturates_ratio: self == OilsAndFats,
+            salt: true,
+            sweeteners: beverage.then_some(SWEETENERS_POINTS_2023),
+            protein_limit: match self {
+                Drinks | MilkDrinks | Cheese => None,
+                OilsAndFats => Some(7),
+                RedMeat | Other => Some(11),
+            },
+            fibers_limited: false,
+            protein_limit_fruits: None,
+            protein_cap: (self == RedMeat).then_some(RED_MEAT_PROTEIN_CAP_2023),
+        }
+    }
+}
+
+const RED_MEAT_PROTEIN_CAP_2023: usize = 2;
+const SWEETENERS_POINTS_2023: usize = 4;
+
+impl CategoryDefinition {
+    /// Replaces the cutoff tables that are given.
+    ///
+    /// The tables are leaked to live as long as the built-in ones, so this is meant to be done
+    /// once, e.g. when a program starts.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if a table is not sorted, contains a value that is not a finite number or
+    /// has another length than the table it replaces, which would change the maximum points.
+    pub fn with_tables(mut self, tables: CutoffTables) -> Result<Self, String> {
+        let [energy, fats, sugars, protein, sodium, fibers, fruits] = &mut self.cutoffs;
+        let replacements = [
+            ("energy", tables.energy, energy),
+            ("fats", tables.fats, fats),
+            ("sugars", tables.sugars, sugars),
+            ("protein", tables.protein, protein),
+            ("sodium", tables.sodium, sodium),
+            ("fibers", tables.fibers, fibers),
+            ("fruits", tables.fruits, fruits),
+        ];
+        for (name, table, built_in) in replacements {
+            let Some(table) = table else { continue };
+            if table.len() != built_in.len() {
+                return Err(format!(
+                    "the {name} table has {} cutoffs instead of {}",
+                    table.len(),
+                    built_in.len()
+                ));
+            }
+            if !table.iter().all(|c| c.is_finite()) || !is_sorted(&table) {
+                return Err(format!("the {name} table is not a sorted list of numbers"));
+            }
+            *built_in = Box::leak(table.into_boxed_slice());
+        }
+        Ok(self)
+    }
+
+    /// Calculates the score, see [`crate::calculate_nutriscore`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the points exceed the range of `isize`, which cannot happen with tables of a
+    /// sensible length.
+    #[must_use]
+    pub fn score(&self, nutrition: &Nutrition, fruits_value: f32) -> Score {
+        let nutrition = &nutrition.rounded();
+        let fruits_value = round(fruits_value, 10.0);
+        let [energy, fats, sugar, protein, sodium, fibers, fruits] = self.cutoffs;
+        let sodium = if self.salt {
+            component("Salt", sodium, nutrition.salt)
+        } else {
+            component("Sodium", sodium, nutrition.sodium())
+        };
+        let energy_value = if self.energy_from_saturates {
+            nutrition.saturated_fats * 37.0 // energy from saturated fats in kJ
+        } else {
+            nutrition.energy
+        };
+        let fats_value = if self.saturates_ratio {
+            nutrition.saturates_ratio()
+        } else {
+            nutrition.saturated_fats
+        };
+        let mut negative = vec![
+            component("Energy", energy, energy_value),
+            component("Sugar", sugar, nutrition.sugar),
+            component("Fats", fats, fats_value),
+            sodium,
+        ];
+        if let Some(points) = self.sweeteners {
+            let sweeteners = nutrition.contains_sweeteners;
+            negative.push(Component {
+                name: "Sweeteners",
+                value: if sweeteners { 1.0 } else { 0.0 },
+                points: if sweeteners { points } else { 0 },
+                max_points: points,
+                lower: None,
+                upper: None,
+                counted: true,
+                cutoffs: &[],
+            });
+        }
+        let mut positive = vec![
+            component("Fruits & Vegs", fruits, fruits_value),
+            component("Fibers", fibers, nutrition.fibers),
+            component("Protein", protein, nutrition.proteins),
+        ];
+        let mut score = Score {
+            negative,
+            positive: Vec::new(),
+            total: 0,
+        };
+        let negative = score.negative_points();
+        let limited = self.protein_limit.is_some_and(|limit| negative >= limit)
+            && self
+                .protein_limit_fruits
+                .is_none_or(|fruits| positive[0].points < fruits);
+        if limited {
+            positive[2].counted = false;
+            if self.fibers_limited {
+                positive[1].counted = false;
+            }
+        }
+        if let Some(cap) = self.protein_cap {
+            let protein = &mut positive[2];
+            protein.points = protein.points.min(cap);
+            protein.max_points = cap;
+            protein.cutoffs = &protein.cutoffs[..cap.min(protein.cutoffs.len())];
+            // the band of the capped points, without an upper cutoff at the cap
+            protein.lower = protein
+                .points
+                .checked_sub(1)
+                .and_then(|i| protein.cutoffs.get(i).copied());
+            protein.upper = protein.cutoffs.get(protein.points).copied();
+        }
+        score.positive = positive;
+        score.total =
+            isize::try_from(negative).unwrap() - isize::try_from(score.positive_points()).unwrap();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            negative,
+            positive = score.positive_points(),
+            limited,
+            total = score.total,
+            "scored"
+        );
+        score
+    }
+
+    /// Converts a score into the letter from A to E.
+    #[must_use]
+    pub fn letter(&self, score: isize, is_water: bool) -> char {
+        if is_water && self.water {
+            return 'A';
+        }
+        self.grades
+            .iter()
+            .zip(['A', 'B', 'C', 'D'])
+            .find_map(|(highest, letter)| highest.filter(|&h| score <= h).map(|_| letter))
+            .unwrap_or('E')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Enough sugar and energy for 11 negative points in 2017, and 10 g of protein.
+    fn limited() -> Nutrition {
+        Nutrition {
+            energy: 3000.0,
+            sugar: 40.0,
+            proteins: 10.0,
+            ..Nutrition::default()
+        }
+    }
+
+    #[test]
+    fn the_grades_are_the_highest_scores_of_a_letter() {
+        let other = Other.definition(V2017);
+        let letters: String = [-1, 0, 2, 3, 10, 11, 18, 19]
+            .into_iter()
+            .map(|score| other.letter(score, false))
+            .collect();
+        assert_eq!(letters, "ABBCCDDE");
+    }
+
+    #[test]
+    fn only_water_is_an_a_for_drinks() {
+        let drinks = Drinks.definition(V2023);
+        assert_eq!(drinks.letter(-10, false), 'B');
+        assert_eq!(drinks.letter(10, true), 'A');
+        // water of other categories is graded by its score
+        assert_eq!(Other.definition(V2023).letter(10, true), 'C');
+    }
+
+    #[test]
+    fn protein_is_limited_by_the_negative_points() {
+        let score = Other.definition(V2017).score(&limited(), 0.0);
+        assert_eq!(
+            (score.positive[1].counted, score.positive[2].counted),
+            (false, false)
+        );
+        // unless there are enough fruits and vegetables, or the category has no limit
+        let fruits = Other.definition(V2017).score(&limited(), 90.0);
+        assert!(fruits.positive[2].counted);
+        assert!(Cheese.definition(V2017).score(&limited(), 0.0).positive[2].counted);
+    }
+
+    #[test]
+    fn new_categories_change_a_definition() {
+        let unlimited = CategoryDefinition {
+            protein_limit: None,
+            ..Other.definition(V2017)
+        };
+        let score = unlimited.score(&limited(), 0.0);
+        assert!(score.positive[2].counted);
+        let built_in = Other.definition(V2017).score(&limited(), 0.0);
+        assert_eq!(score.total, built_in.total - 5);
+    }
+
+    #[test]
+    fn the_definitions_match_the_categories() {
+        let oils = OilsAndFats.definition(V2023);
+        assert!(oils.energy_from_saturates && oils.saturates_ratio && oils.salt);
+        assert_eq!(oils.protein_limit, Some(7));
+        assert_eq!(
+            Drinks.definition(V2023).sweeteners,
+            Some(SWEETENERS_POINTS_2023)
+        );
+        assert_eq!(Other.definition(V2023).sweeteners, None);
+        assert_eq!(
+            RedMeat.definition(V2023).protein_cap,
+            Some(RED_MEAT_PROTEIN_CAP_2023)
+        );
+        assert!(!Other.definition(V2017).salt);
+    }
+
+    #[test]
+    fn tables_keep_their_length() {
+        let tables = CutoffTables {
+            protein: Some(vec![1.0]),
+            ..CutoffTables::default()
+        };
+        let error = Other.definition(V2017).with_tables(tables).unwrap_err();
+        assert_eq!(error, "the protein table has 1 cutoffs instead of 5");
+    }
+}
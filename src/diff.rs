@@ -0,0 +1,151 @@
+use crate::{product_file, report, Format};
+use console::style;
+use nutriscore::{Algorithm, Product, Score};
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+
+/// A section of the diff, with (name, value) lines in the order they are printed.
+struct Section {
+    title: &'static str,
+    lines: Vec<(String, String)>,
+}
+
+fn sections(algorithm: Algorithm, product: &Product, score: &Score) -> [Section; 3] {
+    let mut inputs = vec![("Category".to_owned(), product.category.to_string())];
+    inputs.extend(
+        report::inputs(product)
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value)),
+    );
+    let points = score
+        .negative
+        .iter()
+        .chain(&score.positive)
+        .map(|c| {
+            let points = if c.counted {
+                format!("{}/{}", c.points, c.max_points)
+            } else {
+                format!("{}/{} (not counted)", c.points, c.max_points)
+            };
+            (c.name.to_owned(), points)
+        })
+        .collect();
+    let letter = product.letter(algorithm, score);
+    [
+        Section {
+            title: "nutrition",
+            lines: inputs,
+        },
+        Section {
+            title: "points",
+            lines: points,
+        },
+        Section {
+            title: "grade",
+            lines: vec![
+                ("Score".to_owned(), score.total.to_string()),
+                ("Grade".to_owned(), letter.to_string()),
+            ],
+        },
+    ]
+}
+
+#[derive(Serialize)]
+struct Change<'a> {
+    section: &'static str,
+    name: &'a str,
+    old: Option<&'a str>,
+    new: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonDiff<'a> {
+    old_grade: char,
+    new_grade: char,
+    old_score: isize,
+    new_score: isize,
+    changes: Vec<Change<'a>>,
+}
+
+/// The lines of both sections, matched by name, in the order of the old and then the new one.
+fn changes<'a>(old: &'a Section, new: &'a Section) -> Vec<Change<'a>> {
+    let find = |section: &'a Section, name: &str| {
+        section
+            .lines
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    };
+    let mut names: Vec<&str> = old.lines.iter().map(|(n, _)| n.as_str()).collect();
+    for (name, _) in &new.lines {
+        if !names.contains(&name.as_str()) {
+            names.push(name);
+        }
+    }
+    names
+        .into_iter()
+        .map(|name| Change {
+            section: old.title,
+            name,
+            old: find(old, name),
+            new: find(new, name),
+        })
+        .collect()
+}
+
+/// Prints how the nutritional values, the points and the grade of two product files differ.
+pub fn run(algorithm: Algorithm, format: Format, old: &Path, new: &Path) -> io::Result<()> {
+    let old_product = product_file::read(old)?;
+    let new_product = product_file::read(new)?;
+    let old_score = old_product.score(algorithm);
+    let new_score = new_product.score(algorithm);
+    let old_sections = sections(algorithm, &old_product, &old_score);
+    let new_sections = sections(algorithm, &new_product, &new_score);
+    let changes: Vec<(&Section, Vec<Change>)> = old_sections
+        .iter()
+        .zip(&new_sections)
+        .map(|(o, n)| (o, changes(o, n)))
+        .collect();
+
+    match format {
+        Format::Text => {
+            println!("{}", style(format!("--- {}", old.display())).bold());
+            println!("{}", style(format!("+++ {}", new.display())).bold());
+            for (section, changes) in &changes {
+                println!("{}", style(format!("@@ {} @@", section.title)).cyan());
+                // like in a unified diff, consecutive changes are printed as all removed lines
+                // and then all added lines
+                for run in changes.chunk_by(|a, b| (a.old == a.new) == (b.old == b.new)) {
+                    for c in run {
+                        match c.old {
+                            Some(o) if c.old == c.new => println!(" {}: {o}", c.name),
+                            Some(o) => println!("{}", style(format!("-{}: {o}", c.name)).red()),
+                            None => {}
+                        }
+                    }
+                    for c in run.iter().filter(|c| c.old != c.new) {
+                        if let Some(n) = c.new {
+                            println!("{}", style(format!("+{}: {n}", c.name)).green());
+                        }
+                    }
+                }
+            }
+        }
+        Format::Json => {
+            let result = JsonDiff {
+                old_grade: old_product.letter(algorithm, &old_score),
+                new_grade: new_product.letter(algorithm, &new_score),
+                old_score: old_score.total,
+                new_score: new_score.total,
+                changes: changes
+                    .into_iter()
+                    .flat_map(|(_, changes)| changes)
+                    .filter(|c| c.old != c.new)
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use nutriscore::off::FLAVORED_WATERS;
+use nutriscore::{calculate_nutriscore, Algorithm, Category, Nutrition};
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::Path;
+
+/// How many rows are read before they are scored in parallel.
+const CHUNK: usize = 20_000;
+
+/// The nutrients without which a row is skipped, as they are named in the report.
+const REQUIRED: [&str; 6] = [
+    "energy",
+    "fat",
+    "saturated fat",
+    "sugars",
+    "proteins",
+    "salt",
+];
+
+/// The columns of the Open Food Facts export that are used.
+struct Columns {
+    code: usize,
+    name: Option<usize>,
+    categories: Option<usize>,
+    energy_kj: Option<usize>,
+    energy: Option<usize>,
+    fat: Option<usize>,
+    saturated_fat: Option<usize>,
+    sugars: Option<usize>,
+    carbohydrates: Option<usize>,
+    proteins: Option<usize>,
+    salt: Option<usize>,
+    sodium: Option<usize>,
+    fiber: Option<usize>,
+    fruits: Vec<usize>,
+    off_grade: Option<usize>,
+}
+
+impl Columns {
+    fn new(headers: &csv::StringRecord) -> io::Result<Self> {
+        let find = |name: &str| headers.iter().position(|h| h == name);
+        Ok(Self {
+            code: find("code").ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "no code column, is this the Open Food Facts CSV export?",
+                )
+            })?,
+            name: find("product_name"),
+            categories: find("categories_tags"),
+            energy_kj: find("energy-kj_100g"),
+            energy: find("energy_100g"),
+            fat: find("fat_100g"),
+            saturated_fat: find("saturated-fat_100g"),
+            sugars: find("sugars_100g"),
+            carbohydrates: find("carbohydrates_100g"),
+            proteins: find("proteins_100g"),
+            salt: find("salt_100g"),
+            sodium: find("sodium_100g"),
+            fiber: find("fiber_100g"),
+            fruits: [
+                "fruits-vegetables-nuts_100g",
+                "fruits-vegetables-nuts-estimate_100g",
+                "fruits-vegetables-nuts-estimate-from-ingredients_100g",
+            ]
+            .iter()
+            .filter_map(|name| find(name))
+            .collect(),
+            off_grade: find("nutriscore_grade"),
+        })
+    }
+}
+
+/// The result of a single row, without any output so that rows can be scored on any thread.
+enum Row {
+    Scored {
+        category: Category,
+        score: isize,
+        grade: char,
+    },
+    /// The index into [`REQUIRED`] of the first missing nutrient.
+    Skipped(usize),
+}
+
+fn field(record: &csv::StringRecord, column: Option<usize>) -> &str {
+    column.and_then(|i| record.get(i)).unwrap_or_default()
+}
+
+fn number(record: &csv::StringRecord, column: Option<usize>) -> Option<f32> {
+    field(record, column)
+        .trim()
+        .parse()
+        .ok()
+        .filter(|v: &f32| v.is_finite())
+}
+
+fn score(algorithm: Algorithm, columns: &Columns, record: &csv::StringRecord) -> Row {
+    let values = [
+        number(record, columns.energy_kj).or_else(|| number(record, columns.energy)),
+        number(record, columns.fat),
+        number(record, columns.saturated_fat),
+        number(record, columns.sugars),
+        number(record, columns.proteins),
+        number(record, columns.salt).or_else(|| number(record, columns.sodium).map(|s| s * 2.5)),
+    ];
+    let tags: Vec<&str> = field(record, columns.categories).split(',').collect();
+    let category = Category::from_off_tags(&tags).unwrap_or(Category::Other);
+    // the fats are only needed for the ratio of oils and fats
+    let missing = (0..values.len())
+        .filter(|&i| i != 1 || category == Category::OilsAndFats)
+        .find(|&i| values[i].is_none());
+    if let Some(missing) = missing {
+        return Row::Skipped(missing);
+    }
+    let [energy, fat, saturated_fats, sugar, proteins, salt] =
+        values.map(Option::unwrap_or_default);
+    let nutrition = Nutrition {
+        energy,
+        fat,
+        saturated_fats,
+        sugar,
+        carbohydrates: number(record, columns.carbohydrates),
+        proteins,
+        salt,
+        fibers: number(record, columns.fiber).unwrap_or_default(),
+        contains_sweeteners: false,
+    };
+    let fruits = columns
+        .fruits
+        .iter()
+        .find_map(|&i| number(record, Some(i)))
+        .unwrap_or_default();
+    let water = tags.contains(&"en:waters") && !FLAVORED_WATERS.iter().any(|t| tags.contains(t));
+    let score = calculate_nutriscore(algorithm, category, &nutrition, fruits);
+    Row::Scored {
+        category,
+        score: score.total,
+        grade: category.score_to_letter(algorithm, score.total, water),
+    }
+}
+
+/// Scores every product of the Open Food Facts CSV export and writes the code, name, category,
+/// score and grade of every product that has all required nutrients.
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
+pub fn run(algorithm: Algorithm, input: &Path, output: Option<&Path>) -> io::Result<()> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b'\t')
+        .quoting(false)
+        .flexible(true)
+        .from_reader(BufReader::new(File::open(input)?));
+    let columns = Columns::new(reader.headers()?)?;
+    let output: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut writer = csv::Writer::from_writer(output);
+    writer.write_record([
+        "code",
+        "product_name",
+        "category",
+        "score",
+        "grade",
+        "off_grade",
+    ])?;
+
+    let progress = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+    progress.set_style(
+        ProgressStyle::with_template("{spinner} {pos} rows read, {per_sec}")
+            .expect("the template is valid"),
+    );
+    let mut scored = 0_usize;
+    let mut skipped = [0_usize; REQUIRED.len()];
+    let mut unreadable = 0_usize;
+    let mut records = reader.records();
+    loop {
+        let mut chunk = Vec::with_capacity(CHUNK);
+        let mut read = 0;
+        for record in records.by_ref().take(CHUNK) {
+            read += 1;
+            match record {
+                Ok(record) => chunk.push(record),
+                Err(e) if e.is_io_error() => return Err(e.into()),
+                // e.g. a row with invalid UTF-8
+                Err(_) => unreadable += 1,
+            }
+        }
+        if read == 0 {
+            break;
+        }
+        let rows: Vec<Row> = chunk
+            .par_iter()
+            .map(|record| score(algorithm, &columns, record))
+            .collect();
+        for (record, row) in chunk.iter().zip(rows) {
+            match row {
+                Row::Scored {
+                    category,
+                    score,
+                    grade,
+                } => {
+                    scored += 1;
+                    writer.write_record([
+                        field(record, Some(columns.code)),
+                        field(record, columns.name),
+                        &category.to_string(),
+                        &score.to_string(),
+                        &grade.to_string(),
+                        &field(record, columns.off_grade).to_uppercase(),
+                    ])?;
+                }
+                Row::Skipped(missing) => skipped[missing] += 1,
+            }
+        }
+        progress.inc(read);
+        tracing::debug!(read, scored, unreadable, "chunk scored");
+    }
+    writer.flush()?;
+    progress.finish_and_clear();
+
+    let total: usize = skipped.iter().sum();
+    tracing::info!(scored, skipped = total, unreadable, "dump scored");
+    eprintln!("Scored {scored} products, skipped {total} with missing nutrients");
+    for (name, count) in REQUIRED.iter().zip(skipped) {
+        if count > 0 {
+            eprintln!("  no {name}: {count}");
+        }
+    }
+    if unreadable > 0 {
+        eprintln!("Skipped {unreadable} rows that could not be read");
+    }
+    Ok(())
+}
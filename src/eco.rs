@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+/// The kind of food for the baseline of the Eco-Score, see [`EcoProduct`].
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumIter, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+#[serde(rename_all = "kebab-case")]
+pub enum EcoCategory {
+    Beef,
+    Lamb,
+    Pork,
+    Poultry,
+    Fish,
+    Cheese,
+    Milk,
+    Yoghurt,
+    Eggs,
+    Butter,
+    Oils,
+    Cereals,
+    Legumes,
+    Vegetables,
+    Fruits,
+    Nuts,
+    Chocolate,
+    Coffee,
+    SoftDrinks,
+    Water,
+    Other,
+}
+
+impl EcoCategory {
+    /// The score from 0 to 100 of a typical product of the category, before bonuses and maluses,
+    /// derived from the life cycle analyses of Agribalyse.
+    #[must_use]
+    pub const fn baseline(self) -> isize {
+        match self {
+            Self::Beef => 10,
+            Self::Lamb => 15,
+            Self::Coffee => 20,
+            Self::Butter | Self::Chocolate => 25,
+            Self::Cheese => 35,
+            Self::Fish => 40,
+            Self::Pork => 45,
+            Self::Poultry | Self::Oils => 50,
+            Self::Other => 55,
+            Self::Milk | Self::Eggs => 60,
+            Self::Yoghurt | Self::Nuts => 65,
+            Self::SoftDrinks => 70,
+            Self::Cereals => 75,
+            Self::Fruits => 80,
+            Self::Legumes | Self::Vegetables => 85,
+            Self::Water => 90,
+        }
+    }
+}
+
+/// What a product is packed in, which gives a malus.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumIter, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+#[serde(rename_all = "kebab-case")]
+pub enum Packaging {
+    /// Sold loose, without packaging.
+    Bulk,
+    Paper,
+    Glass,
+    Metal,
+    Plastic,
+    /// Mixed materials that cannot be recycled, like coated cartons or multilayer films.
+    NonRecyclable,
+}
+
+impl Packaging {
+    #[must_use]
+    pub const fn malus(self) -> isize {
+        match self {
+            Self::Bulk => 0,
+            Self::Paper => 3,
+            Self::Glass => 4,
+            Self::Metal => 6,
+            Self::Plastic => 10,
+            Self::NonRecyclable => 15,
+        }
+    }
+}
+
+/// Where the ingredients come from, with a bonus for short transports.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumIter, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+#[serde(rename_all = "kebab-case")]
+pub enum Origin {
+    /// The country where the product is sold.
+    Local,
+    Europe,
+    World,
+    Unknown,
+}
+
+impl Origin {
+    #[must_use]
+    pub const fn bonus(self) -> isize {
+        match self {
+            Self::Local => 15,
+            Self::Europe => 8,
+            Self::World | Self::Unknown => 0,
+        }
+    }
+}
+
+/// A label of the production system, with a bonus.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumIter, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+#[serde(rename_all = "kebab-case")]
+pub enum EcoLabel {
+    Organic,
+    Demeter,
+    LabelRouge,
+    /// Marine Stewardship Council, for sustainable fishing.
+    Msc,
+    /// Aquaculture Stewardship Council.
+    Asc,
+    RainforestAlliance,
+    FairTrade,
+}
+
+impl EcoLabel {
+    #[must_use]
+    pub const fn bonus(self) -> isize {
+        match self {
+            Self::Demeter => 20,
+            Self::Organic => 15,
+            Self::LabelRouge | Self::Msc | Self::Asc => 10,
+            Self::RainforestAlliance | Self::FairTrade => 5,
+        }
+    }
+}
+
+/// The bonus for labels is at most this high, even with several labels.
+const MAX_LABELS_BONUS: isize = 20;
+/// The malus for palm oil that is not certified as sustainable, which threatens species.
+const PALM_OIL_MALUS: isize = 10;
+
+/// Everything needed for the Eco-Score of a product.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EcoProduct {
+    pub category: EcoCategory,
+    pub packaging: Packaging,
+    pub origin: Origin,
+    #[serde(default)]
+    pub labels: Vec<EcoLabel>,
+    /// Whether the product contains palm oil that is not certified as sustainable.
+    #[serde(default)]
+    pub palm_oil: bool,
+}
+
+/// The result of [`EcoProduct::score`], with the bonuses and maluses that led to it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EcoScore {
+    /// The category the baseline is taken from.
+    pub category: EcoCategory,
+    pub baseline: isize,
+    pub labels: isize,
+    pub origin: isize,
+    /// Subtracted from the score, like `palm_oil`.
+    pub packaging: isize,
+    pub palm_oil: isize,
+    /// The score from 0 to 100, higher is better.
+    pub total: isize,
+    /// The grade from A to E.
+    pub grade: char,
+}
+
+impl EcoProduct {
+    /// Calculates the simplified Eco-Score: the baseline of the category plus the bonuses for
+    /// labels and origin minus the maluses for packaging and palm oil, limited to 0 to 100.
+    #[must_use]
+    pub fn score(&self) -> EcoScore {
+        let baseline = self.category.baseline();
+        let labels = self
+            .labels
+            .iter()
+            .map(|label| label.bonus())
+            .sum::<isize>()
+            .min(MAX_LABELS_BONUS);
+        let origin = self.origin.bonus();
+        let packaging = self.packaging.malus();
+        let palm_oil = if self.palm_oil { PALM_OIL_MALUS } else { 0 };
+        let total = (baseline + labels + origin - packaging - palm_oil).clamp(0, 100);
+        let grade = match total {
+            80.. => 'A',
+            60..=79 => 'B',
+            40..=59 => 'C',
+            20..=39 => 'D',
+            _ => 'E',
+        };
+        EcoScore {
+            category: self.category,
+            baseline,
+            labels,
+            origin,
+            packaging,
+            palm_oil,
+            total,
+            grade,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn product(category: EcoCategory, packaging: Packaging, origin: Origin) -> EcoProduct {
+        EcoProduct {
+            category,
+            packaging,
+            origin,
+            labels: Vec::new(),
+            palm_oil: false,
+        }
+    }
+
+    #[test]
+    fn the_baseline_gets_the_bonuses_and_maluses() {
+        let score = product(EcoCategory::Cereals, Packaging::Plastic, Origin::Europe).score();
+        assert_eq!(
+            (score.baseline, score.origin, score.packaging, score.total),
+            (75, 8, 10, 73)
+        );
+        assert_eq!(score.grade, 'B');
+    }
+
+    #[test]
+    fn the_labels_bonus_is_limited() {
+        let organic = EcoProduct {
+            labels: vec![EcoLabel::Organic, EcoLabel::FairTrade, EcoLabel::Msc],
+            ..product(EcoCategory::Fish, Packaging::Bulk, Origin::World)
+        };
+        let score = organic.score();
+        assert_eq!((score.labels, score.total), (MAX_LABELS_BONUS, 60));
+    }
+
+    #[test]
+    fn palm_oil_gives_a_malus() {
+        let spread = EcoProduct {
+            palm_oil: true,
+            ..product(EcoCategory::Chocolate, Packaging::Glass, Origin::World)
+        };
+        let score = spread.score();
+        assert_eq!((score.palm_oil, score.total, score.grade), (10, 11, 'E'));
+    }
+
+    #[test]
+    fn the_score_stays_between_0_and_100() {
+        let beef = EcoProduct {
+            palm_oil: true,
+            ..product(EcoCategory::Beef, Packaging::NonRecyclable, Origin::Unknown)
+        };
+        assert_eq!(beef.score().total, 0);
+        let water = EcoProduct {
+            labels: vec![EcoLabel::Demeter],
+            ..product(EcoCategory::Water, Packaging::Bulk, Origin::Local)
+        };
+        assert_eq!((water.score().total, water.score().grade), (100, 'A'));
+    }
+}
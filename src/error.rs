@@ -0,0 +1,151 @@
+use crate::i18n::{tr, tr_with};
+use miette::{Diagnostic, NamedSource, SourceSpan};
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use thiserror::Error;
+
+/// The errors of the command line tool, which `main` renders as diagnostics.
+///
+/// The functions of the tool return `io::Result`, so these travel inside an [`io::Error`] and
+/// are unpacked again by [`NutriscoreError::from`].
+#[derive(Debug, Error, Diagnostic)]
+pub enum NutriscoreError {
+    /// A value on the command line that cannot be parsed, like `12 kg` for a nutrient.
+    #[error("{name}: {message}")]
+    #[diagnostic(code(nutriscore::invalid_value))]
+    InvalidValue {
+        name: String,
+        message: String,
+        #[source_code]
+        command_line: NamedSource<String>,
+        #[label]
+        span: Option<SourceSpan>,
+    },
+    /// A value that is missing or does not fit the other ones.
+    #[error("{message}")]
+    #[diagnostic(code(nutriscore::invalid_input))]
+    InvalidInput {
+        message: String,
+        #[help]
+        help: Option<String>,
+    },
+    /// A file that cannot be parsed, with the location of the problem.
+    #[error("{path}: {message}")]
+    #[diagnostic(code(nutriscore::invalid_file))]
+    InvalidFile {
+        path: String,
+        message: String,
+        #[source_code]
+        text: NamedSource<String>,
+        #[label]
+        span: Option<SourceSpan>,
+    },
+    /// A prompt that cannot be shown or answered, usually because there is no terminal.
+    #[error("{message}")]
+    #[diagnostic(code(nutriscore::terminal))]
+    Terminal {
+        message: String,
+        #[help]
+        help: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    #[diagnostic(code(nutriscore::io))]
+    Io(io::Error),
+}
+
+impl From<io::Error> for NutriscoreError {
+    fn from(error: io::Error) -> Self {
+        error.downcast::<Self>().unwrap_or_else(Self::Io)
+    }
+}
+
+impl From<NutriscoreError> for io::Error {
+    fn from(error: NutriscoreError) -> Self {
+        let kind = match &error {
+            NutriscoreError::InvalidValue { .. } | NutriscoreError::InvalidInput { .. } => {
+                io::ErrorKind::InvalidInput
+            }
+            NutriscoreError::InvalidFile { .. } => io::ErrorKind::InvalidData,
+            NutriscoreError::Terminal { source, .. } => source.kind(),
+            NutriscoreError::Io(error) => error.kind(),
+        };
+        match error {
+            NutriscoreError::Io(error) => error,
+            error => Self::new(kind, error),
+        }
+    }
+}
+
+/// A value of the argument `name` that cannot be parsed, pointing at it on the command line.
+pub fn invalid_value(name: &str, value: &str, message: String) -> io::Error {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut offset = 0;
+    let mut span = None;
+    for arg in &args {
+        if arg == value || arg.ends_with(&format!("={value}")) {
+            let start = offset + arg.len() - value.len();
+            span = Some((start..start + value.len()).into());
+            break;
+        }
+        offset += arg.len() + 1;
+    }
+    NutriscoreError::InvalidValue {
+        name: name.to_owned(),
+        message,
+        command_line: NamedSource::new("command line", args.join(" ")),
+        span,
+    }
+    .into()
+}
+
+/// A value that has to be given with `--non-interactive`.
+pub fn missing(message: String) -> io::Error {
+    NutriscoreError::InvalidInput {
+        message,
+        help: Some(tr("missing-value-help")),
+    }
+    .into()
+}
+
+/// A file that cannot be parsed, with the byte range of the problem in `text` if it is known.
+pub fn invalid_file(
+    path: &Path,
+    text: String,
+    message: String,
+    span: Option<Range<usize>>,
+) -> io::Error {
+    let name = path.display().to_string();
+    NutriscoreError::InvalidFile {
+        path: name.clone(),
+        message,
+        text: NamedSource::new(name, text),
+        span: span.map(Into::into),
+    }
+    .into()
+}
+
+/// A TOML file that cannot be parsed.
+pub fn toml(path: &Path, text: String, error: &toml::de::Error) -> io::Error {
+    invalid_file(path, text, error.message().to_owned(), error.span())
+}
+
+/// A YAML file that cannot be parsed.
+pub fn yaml(path: &Path, text: String, error: &serde_yaml::Error) -> io::Error {
+    let span = error.location().map(|l| l.index()..l.index() + 1);
+    invalid_file(path, text, error.to_string(), span)
+}
+
+/// Turns the error of a prompt into a [`NutriscoreError::Terminal`].
+pub fn terminal(prompt: &str) -> impl FnOnce(io::Error) -> io::Error + '_ {
+    move |source| {
+        NutriscoreError::Terminal {
+            message: tr_with("terminal", &[("prompt", prompt.into())]),
+            help: tr("terminal-help"),
+            source,
+        }
+        .into()
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{band, report};
+use nutriscore::{Algorithm, Category, Component, Product, Score};
+
+/// The official document an algorithm is specified in.
+const fn document(algorithm: Algorithm, beverage: bool) -> &'static str {
+    match (algorithm, beverage) {
+        (Algorithm::V2017, _) => {
+            "Santé publique France, conditions of use of the Nutri-Score logo, annex on the \
+             calculation of the score (2017)"
+        }
+        (Algorithm::V2023, false) => {
+            "Update report from the Scientific Committee of the Nutri-Score (2022)"
+        }
+        (Algorithm::V2023, true) => {
+            "Update report from the Scientific Committee of the Nutri-Score on beverages (2023)"
+        }
+    }
+}
+
+/// The tables of cutoffs and grades that a category is scored with.
+fn table(algorithm: Algorithm, category: Category) -> &'static str {
+    match (algorithm, category) {
+        (_, c) if c.is_beverage(algorithm) => "beverages",
+        (Algorithm::V2023, Category::OilsAndFats) => "fats, oils, nuts and seeds",
+        _ => "general foods",
+    }
+}
+
+/// Where in the tables a component is found.
+fn section(c: &Component, negative: bool) -> String {
+    let points = if negative { "negative" } else { "positive" };
+    match band(c) {
+        band if band.is_empty() => format!("{points} points"),
+        band => format!("{points} points, row {band}"),
+    }
+}
+
+/// The scores of a grade, probed from [`Category::score_to_letter`].
+fn grade_range(algorithm: Algorithm, product: &Product, letter: char) -> String {
+    const LOWEST: isize = -20;
+    const HIGHEST: isize = 60;
+    let scores: Vec<isize> = (LOWEST..=HIGHEST)
+        .filter(|&s| {
+            product
+                .category
+                .score_to_letter(algorithm, s, product.is_water())
+                == letter
+        })
+        .collect();
+    match (scores.first(), scores.last()) {
+        _ if product.is_water() => "water".to_owned(),
+        (Some(&LOWEST), Some(&max)) => format!("at most {max}"),
+        (Some(&min), Some(&HIGHEST)) => format!("at least {min}"),
+        (Some(min), Some(max)) if min == max => format!("exactly {min}"),
+        (Some(min), Some(max)) => format!("{min} to {max}"),
+        _ => String::new(),
+    }
+}
+
+/// Prints every step of the scoring with the rule of the official algorithm that was applied.
+pub fn print(algorithm: Algorithm, product: &Product, score: &Score, letter: char) {
+    let category = product.category;
+    let table = table(algorithm, category);
+    println!("\nExplanation");
+    println!(
+        "Algorithm {algorithm}, as specified in: {}",
+        document(algorithm, category.is_beverage(algorithm))
+    );
+    println!("Category {category}, so the tables for {table} apply.\n");
+    let components = score
+        .negative
+        .iter()
+        .map(|c| (c, true))
+        .chain(score.positive.iter().map(|c| (c, false)));
+    for (c, negative) in components {
+        let counted = if c.counted { "" } else { ", not counted" };
+        println!(
+            "{}: {:.2} gives {} of {} points ({}){counted}",
+            c.name,
+            c.value,
+            c.points,
+            c.max_points,
+            section(c, negative)
+        );
+    }
+    println!();
+    for rule in report::rules(algorithm, product, score) {
+        println!("{rule}");
+    }
+    println!(
+        "Total: {} negative minus {} positive points is {}, which is grade {letter} ({} in the \
+         grades for {table}).",
+        score.negative_points(),
+        score.positive_points(),
+        score.total,
+        grade_range(algorithm, product, letter),
+    );
+}
@@ -0,0 +1,103 @@
+//! C bindings, enabled with the `ffi` feature.
+//!
+//! The header `include/nutriscore.h` is generated from this module when building with the feature.
+
+use crate::{calculate_nutriscore, Algorithm, Category, Nutrition};
+use std::ffi::c_char;
+
+pub const NUTRISCORE_ALGORITHM_2017: u32 = 2017;
+pub const NUTRISCORE_ALGORITHM_2023: u32 = 2023;
+
+pub const NUTRISCORE_CATEGORY_DRINKS: u32 = 0;
+pub const NUTRISCORE_CATEGORY_MILK_DRINKS: u32 = 1;
+pub const NUTRISCORE_CATEGORY_CHEESE: u32 = 2;
+pub const NUTRISCORE_CATEGORY_OILS_AND_FATS: u32 = 3;
+pub const NUTRISCORE_CATEGORY_RED_MEAT: u32 = 4;
+pub const NUTRISCORE_CATEGORY_OTHER: u32 = 5;
+
+/// The nutritional values per 100g or 100ml, see `Nutrition`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct NutriscoreNutrition {
+    pub energy: f32,
+    pub fat: f32,
+    pub saturated_fats: f32,
+    pub sugar: f32,
+    pub proteins: f32,
+    pub salt: f32,
+    pub fibers: f32,
+    pub contains_sweeteners: bool,
+}
+
+/// The total score and the letter from `'A'` to `'E'`.
+///
+/// The letter is `'\0'` if the algorithm or the category was invalid.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct NutriscoreResult {
+    pub score: isize,
+    pub letter: c_char,
+}
+
+impl From<NutriscoreNutrition> for Nutrition {
+    fn from(n: NutriscoreNutrition) -> Self {
+        Self {
+            energy: n.energy,
+            fat: n.fat,
+            saturated_fats: n.saturated_fats,
+            sugar: n.sugar,
+            carbohydrates: None,
+            proteins: n.proteins,
+            salt: n.salt,
+            fibers: n.fibers,
+            contains_sweeteners: n.contains_sweeteners,
+        }
+    }
+}
+
+const fn algorithm(value: u32) -> Option<Algorithm> {
+    match value {
+        NUTRISCORE_ALGORITHM_2017 => Some(Algorithm::V2017),
+        NUTRISCORE_ALGORITHM_2023 => Some(Algorithm::V2023),
+        _ => None,
+    }
+}
+
+const fn category(value: u32) -> Option<Category> {
+    match value {
+        NUTRISCORE_CATEGORY_DRINKS => Some(Category::Drinks),
+        NUTRISCORE_CATEGORY_MILK_DRINKS => Some(Category::MilkDrinks),
+        NUTRISCORE_CATEGORY_CHEESE => Some(Category::Cheese),
+        NUTRISCORE_CATEGORY_OILS_AND_FATS => Some(Category::OilsAndFats),
+        NUTRISCORE_CATEGORY_RED_MEAT => Some(Category::RedMeat),
+        NUTRISCORE_CATEGORY_OTHER => Some(Category::Other),
+        _ => None,
+    }
+}
+
+/// Calculates the Nutri-Score of a product.
+///
+/// `algorithm` is one of the `NUTRISCORE_ALGORITHM_*` constants and `category` one of the
+/// `NUTRISCORE_CATEGORY_*` constants. `fruits` is the percentage of fruits, vegetables and nuts.
+#[no_mangle]
+pub extern "C" fn nutriscore_calculate(
+    algorithm: u32,
+    category: u32,
+    nutrition: NutriscoreNutrition,
+    fruits: f32,
+    is_water: bool,
+) -> NutriscoreResult {
+    let (Some(algorithm), Some(category)) = (self::algorithm(algorithm), self::category(category))
+    else {
+        return NutriscoreResult {
+            score: 0,
+            letter: 0,
+        };
+    };
+    let score = calculate_nutriscore(algorithm, category, &nutrition.into(), fruits).total;
+    let letter = category.score_to_letter(algorithm, score, is_water);
+    NutriscoreResult {
+        score,
+        letter: letter as c_char,
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount, EnumIter, EnumVariantNames};
+
+/// How an ingredient counts towards the percentage of fruits, vegetables, pulses and nuts.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Default,
+    Display,
+    EnumVariantNames,
+    EnumIter,
+    EnumCount,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum IngredientKind {
+    /// Fresh, frozen or cooked fruits, vegetables, pulses and nuts, counted with their mass.
+    #[strum(serialize = "Fruits, vegetables, pulses or nuts")]
+    FruitsAndVegetables,
+    /// Dried fruits, vegetables and pulses, which count twice.
+    #[strum(serialize = "Dried fruits, vegetables or pulses")]
+    Dried,
+    /// Concentrates like tomato paste, counted with the mass after reconstitution.
+    #[strum(serialize = "Concentrated fruits or vegetables")]
+    Concentrated,
+    /// Everything else, including potatoes and cereals.
+    #[strum(serialize = "Other ingredient")]
+    #[default]
+    Other,
+}
+
+/// An ingredient of a recipe, see [`fruits_percentage`].
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ingredient {
+    /// How the ingredient counts, [`IngredientKind::Other`] if not given.
+    #[serde(default)]
+    pub kind: IngredientKind,
+    /// Mass in g as used in the recipe.
+    pub mass: f32,
+    /// How many times a concentrated ingredient is concentrated, ignored for other kinds.
+    #[serde(default = "one")]
+    pub concentration: f32,
+}
+
+const fn one() -> f32 {
+    1.0
+}
+
+impl Ingredient {
+    /// The factor the mass is multiplied with, both for the fruits and for the total.
+    const fn factor(&self) -> f32 {
+        match self.kind {
+            IngredientKind::Dried => 2.0,
+            IngredientKind::Concentrated => self.concentration.max(1.0),
+            IngredientKind::FruitsAndVegetables | IngredientKind::Other => 1.0,
+        }
+    }
+}
+
+/// Calculates the percentage of fruits, vegetables, pulses and nuts of a recipe.
+///
+/// Dried ingredients count twice and concentrates with their reconstituted mass. The added mass
+/// is added to the total as well, e.g. 50g of dried apricots and 50g of flour give
+/// 100 * (2 * 50) / (100 + 50) = 66.7%.
+#[must_use]
+pub fn fruits_percentage(ingredients: &[Ingredient]) -> f32 {
+    let mut fruits = 0.0;
+    let mut total = 0.0;
+    for ingredient in ingredients {
+        let mass = ingredient.mass * ingredient.factor();
+        if ingredient.kind != IngredientKind::Other {
+            fruits += mass;
+        }
+        total += mass;
+    }
+    if total > 0.0 {
+        (fruits / total * 100.0).min(100.0)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use IngredientKind::{Concentrated, Dried, FruitsAndVegetables, Other};
+
+    const fn ingredient(kind: IngredientKind, mass: f32) -> Ingredient {
+        Ingredient {
+            kind,
+            mass,
+            concentration: 1.0,
+        }
+    }
+
+    fn assert_percentage(ingredients: &[Ingredient], expected: f32) {
+        let percentage = fruits_percentage(ingredients);
+        assert!((percentage - expected).abs() < 0.01, "{percentage}");
+    }
+
+    #[test]
+    fn fruits_count_with_their_mass() {
+        assert_percentage(
+            &[
+                ingredient(FruitsAndVegetables, 30.0),
+                ingredient(Other, 70.0),
+            ],
+            30.0,
+        );
+        assert_percentage(&[ingredient(FruitsAndVegetables, 50.0)], 100.0);
+    }
+
+    #[test]
+    fn dried_fruits_count_twice() {
+        assert_percentage(&[ingredient(Dried, 50.0), ingredient(Other, 50.0)], 66.67);
+    }
+
+    #[test]
+    fn concentrates_count_with_their_reconstituted_mass() {
+        let paste = Ingredient {
+            concentration: 3.0,
+            ..ingredient(Concentrated, 10.0)
+        };
+        assert_percentage(&[paste, ingredient(Other, 90.0)], 25.0);
+        // less than once concentrated counts like fresh
+        let juice = Ingredient {
+            concentration: 0.5,
+            ..ingredient(Concentrated, 10.0)
+        };
+        assert_percentage(&[juice, ingredient(Other, 90.0)], 10.0);
+    }
+
+    #[test]
+    fn no_ingredients_have_no_fruits() {
+        assert_percentage(&[], 0.0);
+        assert_percentage(&[ingredient(Other, 0.0)], 0.0);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::cache::Cache;
+use crate::lookup;
+use juniper::http::GraphQLRequest;
+use juniper::{
+    graphql_object, EmptyMutation, EmptySubscription, FieldResult, GraphQLEnum, GraphQLInputObject,
+    GraphQLObject, RootNode,
+};
+use nutriscore::off::Client;
+use nutriscore::{Nutrition, Product};
+use std::mem;
+use std::sync::{Mutex, PoisonError};
+
+/// What the resolvers need: the default algorithm and the client for `lookup`.
+pub struct Context {
+    pub algorithm: nutriscore::Algorithm,
+    pub client: Client,
+    pub cache: Cache,
+    /// The grades of the request that is executed, see [`execute`].
+    pub grades: Mutex<Vec<(nutriscore::Algorithm, char)>>,
+}
+
+impl juniper::Context for Context {}
+
+#[derive(Copy, Clone, GraphQLEnum)]
+enum Algorithm {
+    V2017,
+    V2023,
+}
+
+impl From<Algorithm> for nutriscore::Algorithm {
+    fn from(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::V2017 => Self::V2017,
+            Algorithm::V2023 => Self::V2023,
+        }
+    }
+}
+
+#[derive(Copy, Clone, GraphQLEnum)]
+enum Category {
+    Drinks,
+    MilkDrinks,
+    Cheese,
+    OilsAndFats,
+    RedMeat,
+    Other,
+}
+
+impl From<Category> for nutriscore::Category {
+    fn from(category: Category) -> Self {
+        match category {
+            Category::Drinks => Self::Drinks,
+            Category::MilkDrinks => Self::MilkDrinks,
+            Category::Cheese => Self::Cheese,
+            Category::OilsAndFats => Self::OilsAndFats,
+            Category::RedMeat => Self::RedMeat,
+            Category::Other => Self::Other,
+        }
+    }
+}
+
+impl From<nutriscore::Category> for Category {
+    fn from(category: nutriscore::Category) -> Self {
+        match category {
+            nutriscore::Category::Drinks => Self::Drinks,
+            nutriscore::Category::MilkDrinks => Self::MilkDrinks,
+            nutriscore::Category::Cheese => Self::Cheese,
+            nutriscore::Category::OilsAndFats => Self::OilsAndFats,
+            nutriscore::Category::RedMeat => Self::RedMeat,
+            nutriscore::Category::Other => Self::Other,
+        }
+    }
+}
+
+/// Nutritional values per 100g or 100ml, energy in kJ and the rest in g.
+#[derive(GraphQLInputObject)]
+struct NutritionInput {
+    energy: f64,
+    fat: f64,
+    saturated_fats: f64,
+    sugar: f64,
+    carbohydrates: Option<f64>,
+    proteins: f64,
+    salt: f64,
+    fibers: f64,
+    #[graphql(default = false)]
+    contains_sweeteners: bool,
+}
+
+impl From<NutritionInput> for Nutrition {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(n: NutritionInput) -> Self {
+        Self {
+            energy: n.energy as f32,
+            fat: n.fat as f32,
+            saturated_fats: n.saturated_fats as f32,
+            sugar: n.sugar as f32,
+            carbohydrates: n.carbohydrates.map(|v| v as f32),
+            proteins: n.proteins as f32,
+            salt: n.salt as f32,
+            fibers: n.fibers as f32,
+            contains_sweeteners: n.contains_sweeteners,
+        }
+    }
+}
+
+/// The points of one nutrient.
+#[derive(GraphQLObject)]
+struct Component {
+    name: String,
+    value: f64,
+    points: i32,
+    max_points: i32,
+    counted: bool,
+}
+
+impl From<&nutriscore::Component> for Component {
+    fn from(c: &nutriscore::Component) -> Self {
+        let points = |p: usize| i32::try_from(p).unwrap_or(i32::MAX);
+        Self {
+            name: c.name.to_owned(),
+            value: f64::from(c.value),
+            points: points(c.points),
+            max_points: points(c.max_points),
+            counted: c.counted,
+        }
+    }
+}
+
+/// The Nutri-Score of a product, without score and grade for drinks with alcohol.
+#[derive(GraphQLObject)]
+struct ScoreResult {
+    applicable: bool,
+    algorithm: Algorithm,
+    score: Option<i32>,
+    grade: Option<String>,
+    negative: Vec<Component>,
+    positive: Vec<Component>,
+}
+
+impl ScoreResult {
+    /// The result of a product, whose grade is added to the ones of the context.
+    fn new(context: &Context, product: &Product, algorithm: nutriscore::Algorithm) -> Self {
+        let graphql_algorithm = match algorithm {
+            nutriscore::Algorithm::V2017 => Algorithm::V2017,
+            nutriscore::Algorithm::V2023 => Algorithm::V2023,
+        };
+        if !product.is_applicable() {
+            return Self {
+                applicable: false,
+                algorithm: graphql_algorithm,
+                score: None,
+                grade: None,
+                negative: Vec::new(),
+                positive: Vec::new(),
+            };
+        }
+        let score = product.score(algorithm);
+        let grade = product.letter(algorithm, &score);
+        context
+            .grades
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((algorithm, grade));
+        Self {
+            applicable: true,
+            algorithm: graphql_algorithm,
+            score: i32::try_from(score.total).ok(),
+            grade: Some(grade.to_string()),
+            negative: score.negative.iter().map(Into::into).collect(),
+            positive: score.positive.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A product from Open Food Facts with its score.
+#[derive(GraphQLObject)]
+struct Lookup {
+    /// The GTIN in the form Open Food Facts uses.
+    barcode: String,
+    name: Option<String>,
+    category: Category,
+    /// The grade Open Food Facts computed, in lower case.
+    off_grade: Option<String>,
+    result: ScoreResult,
+}
+
+pub struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    /// The Nutri-Score of a product.
+    #[allow(clippy::too_many_arguments)]
+    fn score(
+        context: &Context,
+        nutrition: NutritionInput,
+        category: Category,
+        #[graphql(default = 0.0)] fruits: f64,
+        #[graphql(default = false)] water: bool,
+        alcohol: Option<f64>,
+        algorithm: Option<Algorithm>,
+    ) -> ScoreResult {
+        #[allow(clippy::cast_possible_truncation)]
+        let product = Product {
+            name: None,
+            category: category.into(),
+            nutrition: nutrition.into(),
+            fruits: fruits as f32,
+            water,
+            drink: None,
+            alcohol: alcohol.map(|v| v as f32),
+            ingredients: None,
+            eco: None,
+        };
+        let algorithm = algorithm.map_or(context.algorithm, Into::into);
+        ScoreResult::new(context, &product, algorithm)
+    }
+
+    /// The Nutri-Score of a product from Open Food Facts, by default in its suggested category.
+    /// The barcode can be any GTIN or a GS1 Digital Link URL.
+    #[allow(clippy::needless_pass_by_value)]
+    fn lookup(
+        context: &Context,
+        barcode: String,
+        category: Option<Category>,
+        algorithm: Option<Algorithm>,
+    ) -> FieldResult<Lookup> {
+        let barcode = lookup::barcode(&barcode)?;
+        let off = lookup::fetch(&context.client, &context.cache, &barcode)?;
+        let category = category.map_or_else(
+            || off.category().unwrap_or(nutriscore::Category::Other),
+            Into::into,
+        );
+        let product = Product {
+            name: off.name.clone(),
+            category,
+            nutrition: off.nutrition(),
+            fruits: off.fruits(),
+            water: off.is_water(),
+            drink: None,
+            alcohol: None,
+            ingredients: None,
+            eco: None,
+        };
+        Ok(Lookup {
+            result: ScoreResult::new(
+                context,
+                &product,
+                algorithm.map_or(context.algorithm, Into::into),
+            ),
+            barcode,
+            name: off.name,
+            category: category.into(),
+            off_grade: off.nutriscore_grade,
+        })
+    }
+}
+
+pub type Schema = RootNode<Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+pub fn schema() -> Schema {
+    Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+/// Answers a GraphQL request in JSON, with whether it succeeded and the grades it returned.
+pub fn execute(
+    schema: &Schema,
+    context: &Context,
+    body: &str,
+) -> (bool, String, Vec<(nutriscore::Algorithm, char)>) {
+    let request: GraphQLRequest = match serde_json::from_str(body) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = serde_json::json!({ "errors": [{ "message": e.to_string() }] });
+            return (false, error.to_string(), Vec::new());
+        }
+    };
+    let response = request.execute_sync(schema, context);
+    let grades = mem::take(
+        &mut *context
+            .grades
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner),
+    );
+    (
+        response.is_ok(),
+        serde_json::to_string(&response).expect("the responses have string keys"),
+        grades,
+    )
+}
@@ -0,0 +1,236 @@
+use crate::auth::{self, Denied, Keys};
+use crate::cache::Cache;
+use crate::{graphql, lookup};
+use nutriscore::off::Client;
+use nutriscore::{Nutrition, Product};
+use proto::scoring_server::{Scoring, ScoringServer};
+use std::io;
+use std::net::ToSocketAddrs;
+use std::sync::{Arc, Mutex, PoisonError};
+use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tonic::{Request, Response, Status};
+
+/// The messages and the service of `proto/nutriscore.proto`.
+#[allow(clippy::all, clippy::pedantic, clippy::nursery)]
+mod proto {
+    tonic::include_proto!("nutriscore.v1");
+}
+
+const fn algorithm(
+    algorithm: proto::Algorithm,
+    default: nutriscore::Algorithm,
+) -> nutriscore::Algorithm {
+    match algorithm {
+        proto::Algorithm::Unspecified => default,
+        proto::Algorithm::V2017 => nutriscore::Algorithm::V2017,
+        proto::Algorithm::V2023 => nutriscore::Algorithm::V2023,
+    }
+}
+
+/// The category of a request, `None` if it is unspecified.
+const fn category(category: proto::Category) -> Option<nutriscore::Category> {
+    match category {
+        proto::Category::Unspecified => None,
+        proto::Category::Other => Some(nutriscore::Category::Other),
+        proto::Category::Drinks => Some(nutriscore::Category::Drinks),
+        proto::Category::MilkDrinks => Some(nutriscore::Category::MilkDrinks),
+        proto::Category::Cheese => Some(nutriscore::Category::Cheese),
+        proto::Category::OilsAndFats => Some(nutriscore::Category::OilsAndFats),
+        proto::Category::RedMeat => Some(nutriscore::Category::RedMeat),
+    }
+}
+
+const fn proto_category(category: nutriscore::Category) -> proto::Category {
+    match category {
+        nutriscore::Category::Other => proto::Category::Other,
+        nutriscore::Category::Drinks => proto::Category::Drinks,
+        nutriscore::Category::MilkDrinks => proto::Category::MilkDrinks,
+        nutriscore::Category::Cheese => proto::Category::Cheese,
+        nutriscore::Category::OilsAndFats => proto::Category::OilsAndFats,
+        nutriscore::Category::RedMeat => proto::Category::RedMeat,
+    }
+}
+
+impl From<proto::Nutrition> for Nutrition {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(n: proto::Nutrition) -> Self {
+        Self {
+            energy: n.energy as f32,
+            fat: n.fat as f32,
+            saturated_fats: n.saturated_fats as f32,
+            sugar: n.sugar as f32,
+            carbohydrates: n.carbohydrates.map(|v| v as f32),
+            proteins: n.proteins as f32,
+            salt: n.salt as f32,
+            fibers: n.fibers as f32,
+            contains_sweeteners: n.contains_sweeteners,
+        }
+    }
+}
+
+impl From<&nutriscore::Component> for proto::Component {
+    fn from(c: &nutriscore::Component) -> Self {
+        let points = |p: usize| u32::try_from(p).unwrap_or(u32::MAX);
+        Self {
+            name: c.name.to_owned(),
+            value: f64::from(c.value),
+            points: points(c.points),
+            max_points: points(c.max_points),
+            counted: c.counted,
+        }
+    }
+}
+
+/// The Nutri-Score of a product, without score and grade for drinks with alcohol.
+fn reply(product: &Product, algorithm: nutriscore::Algorithm) -> proto::ScoreReply {
+    let proto_algorithm = match algorithm {
+        nutriscore::Algorithm::V2017 => proto::Algorithm::V2017,
+        nutriscore::Algorithm::V2023 => proto::Algorithm::V2023,
+    };
+    let mut reply = proto::ScoreReply {
+        applicable: product.is_applicable(),
+        algorithm: proto_algorithm.into(),
+        ..proto::ScoreReply::default()
+    };
+    if reply.applicable {
+        let score = product.score(algorithm);
+        reply.score = i32::try_from(score.total).ok();
+        reply.grade = Some(product.letter(algorithm, &score).to_string());
+        reply.negative = score.negative.iter().map(Into::into).collect();
+        reply.positive = score.positive.iter().map(Into::into).collect();
+    }
+    reply
+}
+
+/// The service with the default algorithm and the client for `Lookup`.
+struct Service {
+    algorithm: nutriscore::Algorithm,
+    /// Shared with the blocking threads the products are fetched on.
+    lookups: Arc<(Client, Cache)>,
+}
+
+#[tonic::async_trait]
+impl Scoring for Service {
+    async fn score(
+        &self,
+        request: Request<proto::ScoreRequest>,
+    ) -> Result<Response<proto::ScoreReply>, Status> {
+        let request = request.into_inner();
+        let nutrition = request
+            .nutrition
+            .ok_or_else(|| Status::invalid_argument("the nutrition is missing"))?;
+        #[allow(clippy::cast_possible_truncation)]
+        let product = Product {
+            name: None,
+            category: category(request.category()).unwrap_or(nutriscore::Category::Other),
+            nutrition: nutrition.into(),
+            fruits: request.fruits as f32,
+            water: request.water,
+            drink: None,
+            alcohol: request.alcohol.map(|v| v as f32),
+            ingredients: None,
+            eco: None,
+        };
+        let algorithm = algorithm(request.algorithm(), self.algorithm);
+        Ok(Response::new(reply(&product, algorithm)))
+    }
+
+    async fn lookup(
+        &self,
+        request: Request<proto::LookupRequest>,
+    ) -> Result<Response<proto::LookupReply>, Status> {
+        let request = request.into_inner();
+        let lookups = Arc::clone(&self.lookups);
+        let barcode = lookup::barcode(&request.barcode)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let normalized = barcode.clone();
+        let off = tokio::task::spawn_blocking(move || {
+            let (client, cache) = &*lookups;
+            lookup::fetch(client, cache, &barcode)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => Status::not_found(e.to_string()),
+            _ => Status::unavailable(e.to_string()),
+        })?;
+        let category = category(request.category())
+            .unwrap_or_else(|| off.category().unwrap_or(nutriscore::Category::Other));
+        let product = Product {
+            name: off.name.clone(),
+            category,
+            nutrition: off.nutrition(),
+            fruits: off.fruits(),
+            water: off.is_water(),
+            drink: None,
+            alcohol: None,
+            ingredients: None,
+            eco: None,
+        };
+        let algorithm = algorithm(request.algorithm(), self.algorithm);
+        Ok(Response::new(proto::LookupReply {
+            result: Some(reply(&product, algorithm)),
+            barcode: normalized,
+            name: off.name,
+            category: proto_category(category).into(),
+            off_grade: off.nutriscore_grade,
+        }))
+    }
+}
+
+/// Answers gRPC requests until the process is stopped, with TLS if a certificate and key in
+/// PEM are given.
+pub fn run(
+    context: graphql::Context,
+    keys: Option<Keys>,
+    (host, port): (&str, u16),
+    tls: Option<(Vec<u8>, Vec<u8>)>,
+) -> io::Result<()> {
+    let algorithm = context.algorithm;
+    let address = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the host has no address"))?;
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    let mut server = Server::builder();
+    if let Some((certificate, private_key)) = tls {
+        let identity = Identity::from_pem(certificate, private_key);
+        server = server
+            .tls_config(ServerTlsConfig::new().identity(identity))
+            .map_err(io::Error::other)?;
+    }
+    let service = Service {
+        algorithm,
+        lookups: Arc::new((context.client, context.cache)),
+    };
+    let keys = keys.map(|keys| Arc::new(Mutex::new(keys)));
+    let authorize = move |request: Request<()>| {
+        if let Some(keys) = &keys {
+            let metadata = request.metadata();
+            let value = |name| metadata.get(name).and_then(|value| value.to_str().ok());
+            let secret = auth::secret(value("authorization"), value("x-api-key"));
+            let mut keys = keys.lock().unwrap_or_else(PoisonError::into_inner);
+            match keys.check(secret) {
+                Ok(name) => tracing::debug!(key = name, "authorized"),
+                Err(denied @ Denied::Unauthorized) => {
+                    return Err(Status::unauthenticated(denied.message()))
+                }
+                Err(denied @ Denied::RateLimited(_)) => {
+                    return Err(Status::resource_exhausted(denied.message()))
+                }
+            }
+        }
+        Ok(request)
+    };
+    eprintln!("Listening for gRPC on {scheme}://{host}:{port}");
+    tracing::info!(host, port, %algorithm, "listening for gRPC");
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(
+            server
+                .add_service(ScoringServer::with_interceptor(service, authorize))
+                .serve(address),
+        )
+        .map_err(io::Error::other)
+}
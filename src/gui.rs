@@ -0,0 +1,199 @@
+use crate::i18n::{self, tr};
+use crate::{badge_file, Badge, BadgeFormat, BadgeStyle};
+use eframe::egui::{self, Color32, ComboBox, DragValue, Grid, ProgressBar, RichText};
+use nutriscore::{
+    Algorithm, Category, Component, DrinkType, Nutrition, Product, Severity, GRADE_COLORS,
+};
+use std::io;
+use std::path::PathBuf;
+use strum::IntoEnumIterator;
+
+fn grade_color(letter: char) -> Color32 {
+    GRADE_COLORS
+        .iter()
+        .find(|(l, _)| *l == letter)
+        .and_then(|(_, color)| Color32::from_hex(color).ok())
+        .unwrap_or(Color32::GRAY)
+}
+
+struct App {
+    algorithm: Algorithm,
+    product: Product,
+    /// Where the badge is exported to, a .png or .svg file.
+    badge: String,
+    /// The result of the last export.
+    message: String,
+}
+
+impl App {
+    fn values(&mut self, ui: &mut egui::Ui) {
+        let algorithm = self.algorithm;
+        let product = &mut self.product;
+        Grid::new("values").num_columns(2).show(ui, |ui| {
+            let n = &mut product.nutrition;
+            for (prompt, value, unit) in [
+                ("prompt-energy-kj", &mut n.energy, ""),
+                ("prompt-fat", &mut n.fat, " g"),
+                ("prompt-saturated-fats", &mut n.saturated_fats, " g"),
+                ("prompt-sugar", &mut n.sugar, " g"),
+                ("prompt-proteins", &mut n.proteins, " g"),
+                ("prompt-salt", &mut n.salt, " g"),
+                ("prompt-fibers", &mut n.fibers, " g"),
+            ] {
+                ui.label(tr(prompt));
+                ui.add(
+                    DragValue::new(value)
+                        .speed(0.1)
+                        .range(0.0..=f32::MAX)
+                        .suffix(unit),
+                );
+                ui.end_row();
+            }
+            ui.label(tr("prompt-fruits"));
+            ui.add(egui::Slider::new(&mut product.fruits, 0.0..=100.0).suffix(" %"));
+            ui.end_row();
+            ui.label(tr("prompt-category"));
+            ComboBox::from_id_salt("category")
+                .selected_text(i18n::category(product.category))
+                .show_ui(ui, |ui| {
+                    for category in Category::iter() {
+                        ui.selectable_value(
+                            &mut product.category,
+                            category,
+                            i18n::category(category),
+                        );
+                    }
+                });
+            ui.end_row();
+            if product.category == Category::Drinks {
+                let drink = product.drink.get_or_insert(DrinkType::Other);
+                ui.label(tr("prompt-drink"));
+                ComboBox::from_id_salt("drink")
+                    .selected_text(tr(&format!("drink-{drink}")))
+                    .show_ui(ui, |ui| {
+                        for choice in DrinkType::iter() {
+                            ui.selectable_value(drink, choice, tr(&format!("drink-{choice}")));
+                        }
+                    });
+                ui.end_row();
+                product.water = drink.is_water();
+            } else {
+                product.water = false;
+                product.drink = None;
+            }
+            if algorithm == Algorithm::V2023
+                && product.category.is_beverage(algorithm)
+                && !product.water
+            {
+                ui.label(tr("prompt-sweeteners"));
+                ui.checkbox(&mut product.nutrition.contains_sweeteners, "");
+                ui.end_row();
+            }
+        });
+    }
+
+    fn export(&mut self, ui: &mut egui::Ui, letter: char) {
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.badge);
+            if ui.button("Export badge").clicked() {
+                let out = PathBuf::from(&self.badge);
+                let format = if out
+                    .extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("png"))
+                {
+                    BadgeFormat::Png
+                } else {
+                    BadgeFormat::Svg
+                };
+                let badge = Badge {
+                    format,
+                    style: BadgeStyle::Logo,
+                    width: None,
+                    out,
+                };
+                self.message = match badge_file::write(&badge, letter) {
+                    Ok(()) => format!("Saved {}", self.badge),
+                    Err(e) => e.to_string(),
+                };
+            }
+        });
+        ui.label(&self.message);
+    }
+}
+
+fn gauge(ui: &mut egui::Ui, c: &Component) {
+    #[allow(clippy::cast_precision_loss)]
+    let fraction = c.points as f32 / c.max_points.max(1) as f32;
+    let mut text = format!("{} {}/{}", i18n::component(c.name), c.points, c.max_points);
+    if !c.counted {
+        text = format!("{text} {}", tr("breakdown-not-counted"));
+    }
+    ui.add(ProgressBar::new(fraction).text(text));
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.values(ui);
+            ui.separator();
+            let errors: Vec<String> = self
+                .product
+                .validate()
+                .into_iter()
+                .filter(|issue| issue.severity == Severity::Error)
+                .map(|issue| i18n::issue(&issue))
+                .collect();
+            if !errors.is_empty() {
+                ui.colored_label(Color32::RED, errors.join("\n"));
+                return;
+            }
+            let score = self.product.score(self.algorithm);
+            let letter = self.product.letter(self.algorithm, &score);
+            ui.label(tr("breakdown-negative"));
+            for c in &score.negative {
+                gauge(ui, c);
+            }
+            ui.label(tr("breakdown-positive"));
+            for c in &score.positive {
+                gauge(ui, c);
+            }
+            ui.separator();
+            ui.label(
+                RichText::new(format!("Nutri-Score {letter}"))
+                    .size(40.0)
+                    .strong()
+                    .color(grade_color(letter)),
+            );
+            ui.label(format!("{} {}", tr("total-score"), score.total));
+            ui.separator();
+            self.export(ui, letter);
+        });
+    }
+}
+
+/// Opens a window with fields for all values, the breakdown as gauges and the grade, which are
+/// updated on every change, and a button to export the badge.
+pub fn run(algorithm: Algorithm) -> io::Result<()> {
+    let app = App {
+        algorithm,
+        product: Product {
+            name: None,
+            category: Category::Other,
+            nutrition: Nutrition::default(),
+            fruits: 0.0,
+            water: false,
+            drink: None,
+            alcohol: None,
+            ingredients: None,
+            eco: None,
+        },
+        badge: "label.png".to_owned(),
+        message: String::new(),
+    };
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([420.0, 720.0]),
+        ..eframe::NativeOptions::default()
+    };
+    eframe::run_native("Nutri-Score", options, Box::new(|_| Ok(Box::new(app))))
+        .map_err(|e| io::Error::other(e.to_string()))
+}
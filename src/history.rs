@@ -0,0 +1,153 @@
+use crate::{config, print_quiet, print_text, Format, JsonResult, TextOptions};
+use nutriscore::{Algorithm, Product, Score};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// One scoring run, stored as one line of JSON.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    timestamp: String,
+    algorithm: Algorithm,
+    product: Product,
+    score: isize,
+    grade: char,
+}
+
+fn path() -> io::Result<PathBuf> {
+    config::user_file("XDG_STATE_HOME", ".local/state", "history.ndjson").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "cannot find the home directory for the history",
+        )
+    })
+}
+
+/// Appends a result to the history file.
+pub fn record(
+    algorithm: Algorithm,
+    product: &Product,
+    score: &Score,
+    grade: char,
+) -> io::Result<()> {
+    let path = path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let entry = Entry {
+        timestamp: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+        algorithm,
+        product: product.clone(),
+        score: score.total,
+        grade,
+    };
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)
+}
+
+fn read() -> io::Result<Vec<Entry>> {
+    let path = path()?;
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut entries = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: {e}", path.display(), i + 1),
+            )
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[derive(Serialize)]
+struct JsonShown<'a> {
+    timestamp: &'a str,
+    algorithm: Algorithm,
+    product: &'a Product,
+    #[serde(flatten)]
+    result: JsonResult<'a>,
+}
+
+/// Lists the history, or prints entry `show` again, counted from 1.
+pub fn run(format: Format, text: TextOptions, show: Option<usize>) -> io::Result<()> {
+    let entries = read()?;
+    let Some(show) = show else {
+        match format {
+            Format::Text => {
+                for (i, e) in entries.iter().enumerate() {
+                    println!(
+                        "{:>4}  {}  {} (score {}, algorithm {}){}",
+                        i + 1,
+                        e.timestamp,
+                        e.grade,
+                        e.score,
+                        e.algorithm,
+                        e.product
+                            .name
+                            .as_ref()
+                            .map_or_else(String::new, |name| format!("  {name}"))
+                    );
+                }
+            }
+            Format::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        }
+        return Ok(());
+    };
+    let entry = show
+        .checked_sub(1)
+        .and_then(|i| entries.get(i))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("there is no history entry {show}"),
+            )
+        })?;
+    let score = entry.product.score(entry.algorithm);
+    let letter = entry.product.letter(entry.algorithm, &score);
+    match format {
+        Format::Text if text.quiet => print_quiet(text, score.total, letter),
+        Format::Text => {
+            println!("Scored at {}", entry.timestamp);
+            print_text(
+                entry.algorithm,
+                text,
+                &entry.product.nutrition,
+                &score,
+                letter,
+            );
+            if score.total != entry.score {
+                println!(
+                    "At the time the score was {} with grade {}.",
+                    entry.score, entry.grade
+                );
+            }
+        }
+        Format::Json => {
+            let json = JsonShown {
+                timestamp: &entry.timestamp,
+                algorithm: entry.algorithm,
+                product: &entry.product,
+                result: JsonResult {
+                    score: score.total,
+                    grade: letter,
+                    negative: &score.negative,
+                    positive: &score.positive,
+                },
+            };
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use crate::Category::{Cheese, Drinks, MilkDrinks, OilsAndFats, Other, RedMeat};
+use crate::{
+    component, Product, Score, ScoringModel, ENERGY_CUTOFFS, FIBERS_CUTOFFS, FRUITS_CUTOFFS,
+    PROTEIN_CUTOFFS, SATURATED_FATS_CUTOFF, SODIUM_CUTOFF, SUGAR_CUTOFFS,
+};
+
+// category 3 and 3D, oils, spreads and cheese, can get up to 30 points
+static ENERGY_CUTOFFS_HIGH: [f32; 30] = [
+    335.0, 670.0, 1005.0, 1340.0, 1675.0, 2010.0, 2345.0, 2680.0, 3015.0, 3350.0, 3685.0, 4020.0,
+    4355.0, 4690.0, 5025.0, 5360.0, 5695.0, 6030.0, 6365.0, 6700.0, 7035.0, 7370.0, 7705.0, 8040.0,
+    8375.0, 8710.0, 9045.0, 9380.0, 9715.0, 10050.0,
+];
+static SATURATED_FATS_CUTOFFS_HIGH: [f32; 30] = [
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0,
+    18.0, 19.0, 20.0, 21.0, 22.0, 23.0, 24.0, 25.0, 26.0, 27.0, 28.0, 29.0, 30.0,
+];
+static SODIUM_CUTOFFS_HIGH: [f32; 30] = [
+    90.0, 180.0, 270.0, 360.0, 450.0, 540.0, 630.0, 720.0, 810.0, 900.0, 990.0, 1080.0, 1170.0,
+    1260.0, 1350.0, 1440.0, 1530.0, 1620.0, 1710.0, 1800.0, 1890.0, 1980.0, 2070.0, 2160.0, 2250.0,
+    2340.0, 2430.0, 2520.0, 2610.0, 2700.0,
+];
+
+/// Protein is not counted from this many baseline points on, unless the product has at least
+/// [`FRUITS_FOR_PROTEIN`] points for fruits and vegetables.
+const BASELINE_WITHOUT_PROTEIN: usize = 13;
+const FRUITS_FOR_PROTEIN: usize = 5;
+
+/// The Health Star Rating of Australia and New Zealand, from half a star to five stars.
+///
+/// The baseline points for energy, saturated fats, sugars and sodium minus the modifying points
+/// for fruits and vegetables, protein and fibers give the score, and the stars are looked up in
+/// the table of the HSR category. Drinks are category 1, milk drinks 1D, oils and fats 3, cheese
+/// 3D and everything else 2.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct HealthStarRating;
+
+impl HealthStarRating {
+    /// The stars for a score calculated with [`ScoringModel::score`].
+    #[must_use]
+    pub fn stars(self, product: &Product, score: &Score) -> f32 {
+        // the half stars of the first row and the highest score for each row, the last row with
+        // half a star has no limit
+        let (best, rows): (u8, &[isize]) = match product.category {
+            Drinks if product.is_water() => return 5.0,
+            Drinks => (9, &[0, 1, 2, 3, 4, 5, 6, 7]),
+            MilkDrinks => (10, &[-2, 0, 2, 4, 6, 8, 10, 12, 14]),
+            OilsAndFats => (10, &[13, 16, 20, 23, 27, 30, 34, 37, 41]),
+            Cheese => (10, &[15, 18, 21, 24, 27, 30, 33, 36, 39]),
+            RedMeat | Other => (10, &[-11, -7, -2, 2, 6, 11, 15, 20, 24]),
+        };
+        let row = rows
+            .iter()
+            .position(|&highest| score.total <= highest)
+            .unwrap_or(rows.len());
+        let half_stars = best.saturating_sub(u8::try_from(row).unwrap_or(u8::MAX));
+        f32::from(half_stars.max(1)) / 2.0
+    }
+}
+
+impl ScoringModel for HealthStarRating {
+    fn name(&self) -> String {
+        "Health Star Rating".to_owned()
+    }
+
+    fn score(&self, product: &Product) -> Score {
+        let n = &product.nutrition.rounded();
+        let high = matches!(product.category, OilsAndFats | Cheese);
+        let (energy, fats, sodium): (&'static [f32], &'static [f32], &'static [f32]) = if high {
+            (
+                &ENERGY_CUTOFFS_HIGH,
+                &SATURATED_FATS_CUTOFFS_HIGH,
+                &SODIUM_CUTOFFS_HIGH,
+            )
+        } else {
+            (&ENERGY_CUTOFFS, &SATURATED_FATS_CUTOFF, &SODIUM_CUTOFF)
+        };
+        let negative = vec![
+            component("Energy", energy, n.energy),
+            component("Saturates", fats, n.saturated_fats),
+            component("Sugars", &SUGAR_CUTOFFS, n.sugar),
+            // in mg
+            component("Sodium", sodium, n.salt * 400.0),
+        ];
+        let mut positive = vec![
+            component("Fruits & Vegs", &FRUITS_CUTOFFS[..5], product.fruits),
+            component("Protein", &PROTEIN_CUTOFFS, n.proteins),
+            component("Fibers", &FIBERS_CUTOFFS, n.fibers),
+        ];
+        let baseline: usize = negative.iter().map(|c| c.points).sum();
+        if product.category != Cheese
+            && baseline >= BASELINE_WITHOUT_PROTEIN
+            && positive[0].points < FRUITS_FOR_PROTEIN
+        {
+            positive[1].counted = false;
+        }
+        let mut score = Score {
+            negative,
+            positive,
+            total: 0,
+        };
+        // at most 120 baseline and 25 modifying points
+        score.total = isize::try_from(baseline).unwrap_or(isize::MAX)
+            - isize::try_from(score.positive_points()).unwrap_or(isize::MAX);
+        score
+    }
+
+    fn grade(&self, product: &Product, score: &Score) -> String {
+        self.stars(product, score).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Nutrition;
+
+    fn product(category: crate::Category, nutrition: Nutrition, fruits: f32) -> Product {
+        Product {
+            name: None,
+            category,
+            nutrition,
+            fruits,
+            water: false,
+            ingredients: None,
+            eco: None,
+            drink: None,
+            alcohol: None,
+        }
+    }
+
+    fn stars(product: &Product) -> String {
+        HealthStarRating.grade(product, &HealthStarRating.score(product))
+    }
+
+    #[test]
+    fn water_gets_five_stars() {
+        let water = Product {
+            water: true,
+            ..product(Drinks, Nutrition::default(), 0.0)
+        };
+        assert_eq!(stars(&water), "5");
+    }
+
+    #[test]
+    fn stars_are_looked_up_in_the_table_of_the_category() {
+        let nothing = Nutrition::default();
+        // a score of 0 is in the fourth row of category 2 and the first of category 3D
+        assert_eq!(stars(&product(Other, nothing, 0.0)), "3.5");
+        assert_eq!(stars(&product(Cheese, nothing, 0.0)), "5");
+        let healthy = Nutrition {
+            proteins: 10.0,
+            fibers: 10.0,
+            ..Nutrition::default()
+        };
+        assert_eq!(stars(&product(Other, healthy, 100.0)), "5");
+        let unhealthy = Nutrition {
+            energy: 3400.0,
+            saturated_fats: 10.0,
+            sugar: 45.0,
+            salt: 2.5,
+            ..Nutrition::default()
+        };
+        assert_eq!(stars(&product(Other, unhealthy, 0.0)), "0.5");
+    }
+
+    #[test]
+    fn sodium_is_scored_in_mg() {
+        // 1 g of salt is 400 mg of sodium, above 360 mg
+        let salty = Nutrition {
+            salt: 1.0,
+            ..Nutrition::default()
+        };
+        let score = HealthStarRating.score(&product(Other, salty, 0.0));
+        assert_eq!(score.negative[3].points, 4);
+    }
+
+    #[test]
+    fn oils_and_cheese_get_up_to_30_points() {
+        let energy = Nutrition {
+            energy: 4000.0,
+            ..Nutrition::default()
+        };
+        let points = |category| {
+            HealthStarRating
+                .score(&product(category, energy, 0.0))
+                .negative[0]
+                .points
+        };
+        assert_eq!(points(Other), 10);
+        assert_eq!(points(Cheese), 11);
+        assert_eq!(points(OilsAndFats), 11);
+    }
+
+    #[test]
+    fn protein_is_not_counted_with_13_baseline_points() {
+        let nutrition = Nutrition {
+            energy: 3400.0,
+            sugar: 20.0,
+            proteins: 10.0,
+            ..Nutrition::default()
+        };
+        let counted = |category, fruits| {
+            HealthStarRating
+                .score(&product(category, nutrition, fruits))
+                .positive[1]
+                .counted
+        };
+        assert!(!counted(Other, 0.0));
+        // unless the product has enough fruits and vegetables or is cheese
+        assert!(counted(Other, 100.0));
+        assert!(counted(Cheese, 0.0));
+    }
+}
@@ -0,0 +1,185 @@
+use clap::ValueEnum;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use nutriscore::{Field, Issue};
+use std::env;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+/// The languages of the prompts and messages.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Lang {
+    #[default]
+    En,
+    De,
+    Fr,
+    Nl,
+}
+
+impl Lang {
+    /// The language of a locale like `de_DE.UTF-8` or `fr`, if it is supported.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let language = locale.split(['_', '-', '.', '@']).next()?;
+        Self::from_str(language, true).ok()
+    }
+
+    /// The language of the `LC_ALL`, `LC_MESSAGES` or `LANG` environment variable.
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+    }
+
+    /// Whether numbers are written with a decimal comma, like 3,5 in German.
+    pub const fn decimal_comma(self) -> bool {
+        !matches!(self, Self::En)
+    }
+
+    const fn messages(self) -> (&'static str, &'static str) {
+        match self {
+            Self::En => ("en", include_str!("../locales/en.ftl")),
+            Self::De => ("de", include_str!("../locales/de.ftl")),
+            Self::Fr => ("fr", include_str!("../locales/fr.ftl")),
+            Self::Nl => ("nl", include_str!("../locales/nl.ftl")),
+        }
+    }
+
+    fn bundle(self) -> FluentBundle<FluentResource> {
+        let (id, source) = self.messages();
+        let id: LanguageIdentifier = id.parse().expect("the language ids are valid");
+        let mut bundle = FluentBundle::new_concurrent(vec![id]);
+        // the isolation marks around arguments would end up in the terminal
+        bundle.set_use_isolating(false);
+        let resource = FluentResource::try_new(source.to_owned()).expect("the messages are valid");
+        bundle
+            .add_resource(resource)
+            .expect("the messages have no duplicates");
+        bundle
+    }
+}
+
+struct Messages {
+    decimal_comma: bool,
+    bundle: FluentBundle<FluentResource>,
+    /// English, for messages that are not translated yet.
+    fallback: FluentBundle<FluentResource>,
+}
+
+static MESSAGES: OnceLock<Messages> = OnceLock::new();
+
+/// Sets the language of all following messages and whether numbers can be entered with a decimal
+/// comma. Without this, messages are English and numbers need a decimal point.
+pub fn init(lang: Lang, decimal_comma: bool) {
+    let _ = MESSAGES.set(Messages {
+        decimal_comma,
+        bundle: lang.bundle(),
+        fallback: Lang::En.bundle(),
+    });
+}
+
+fn messages() -> &'static Messages {
+    MESSAGES.get_or_init(|| Messages {
+        decimal_comma: false,
+        bundle: Lang::En.bundle(),
+        fallback: Lang::En.bundle(),
+    })
+}
+
+/// Parses a number entered by the user, which can be `3,5` instead of `3.5` with a decimal comma.
+pub fn number(text: &str) -> Result<f32, String> {
+    let text = text.trim();
+    let parsed = if messages().decimal_comma {
+        text.replace(',', ".").parse()
+    } else {
+        text.parse()
+    };
+    parsed.map_err(|_| tr_with("invalid-number", &[("text", text.into())]))
+}
+
+/// The message with the id in the current language, with arguments like `{ $name }` replaced.
+pub fn tr_with(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let messages = messages();
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    [&messages.bundle, &messages.fallback]
+        .into_iter()
+        .find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(
+                bundle
+                    .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                    .into_owned(),
+            )
+        })
+        .unwrap_or_else(|| id.to_owned())
+}
+
+/// The message with the id in the current language.
+pub fn tr(id: &str) -> String {
+    tr_with(id, &[])
+}
+
+/// The id of a name like `Fruits & Vegs` in the messages, e.g. `fruits-vegs`.
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// The translated name of a component of the score, for tables.
+pub fn component(name: &str) -> String {
+    let id = format!("component-{}", slug(name));
+    let text = tr(&id);
+    if text == id {
+        name.to_owned()
+    } else {
+        text
+    }
+}
+
+/// The translated name of a nutrient, within a sentence.
+pub fn nutrient(name: &str) -> String {
+    let id = format!("nutrient-{}", slug(name));
+    let text = tr(&id);
+    if text == id {
+        name.to_lowercase()
+    } else {
+        text
+    }
+}
+
+/// The translated name of a category, like the id `category-milk-drinks`.
+pub fn category(category: nutriscore::Category) -> String {
+    tr(&format!("category-{}", slug(&category.to_string())))
+}
+
+/// The translated message of a problem with the values, with the first field as `{ $name }`.
+pub fn issue(issue: &Issue) -> String {
+    let mut args: Vec<(&str, FluentValue)> = issue
+        .values
+        .iter()
+        .map(|&(name, value)| (name, value.to_string().into()))
+        .collect();
+    if let Some(&field) = issue.fields.first() {
+        let id = match field {
+            Field::Energy => "component-energy",
+            Field::Fat => "prompt-fat",
+            Field::SaturatedFats => "prompt-saturated-fats",
+            Field::Carbohydrates => "prompt-carbohydrates",
+            Field::Sugar => "prompt-sugar",
+            Field::Proteins => "prompt-proteins",
+            Field::Salt => "prompt-salt",
+            Field::Fibers => "prompt-fibers",
+            Field::Fruits => "prompt-fruits",
+        };
+        args.push(("name", tr(id).into()));
+    }
+    tr_with(issue.id, &args)
+}
@@ -0,0 +1,168 @@
+use crate::search::{self, Export, Hit};
+use crate::{config, Format, TextOptions};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use nutriscore::off::Product;
+use nutriscore::{Algorithm, Category};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tantivy::collector::TopDocs;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+use tantivy::{Index, IndexWriter, TantivyDocument};
+
+/// The memory the index is built in, more makes fewer segments to merge.
+const WRITER_MEMORY: usize = 256 * 1024 * 1024;
+
+fn tantivy_error(e: tantivy::TantivyError) -> io::Error {
+    io::Error::other(e)
+}
+
+/// The directory of `--index`, or else the one in `~/.local/share/nutriscore`.
+pub fn dir(dir: Option<&Path>) -> io::Result<PathBuf> {
+    dir.map_or_else(
+        || {
+            config::user_file("XDG_DATA_HOME", ".local/share", "index").ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "cannot find the home directory, use --index",
+                )
+            })
+        },
+        |dir| Ok(dir.to_owned()),
+    )
+}
+
+/// The fields of a product in the index.
+struct Fields {
+    code: Field,
+    name: Field,
+    brands: Field,
+    categories: Field,
+    /// The whole product as JSON, to score it without the export.
+    product: Field,
+}
+
+impl Fields {
+    fn schema() -> Schema {
+        let mut schema = Schema::builder();
+        schema.add_text_field("code", STRING | STORED);
+        schema.add_text_field("name", TEXT);
+        schema.add_text_field("brands", TEXT);
+        schema.add_text_field("categories", TEXT);
+        schema.add_text_field("product", STORED);
+        schema.build()
+    }
+
+    fn new(schema: &Schema) -> io::Result<Self> {
+        let field = |name| schema.get_field(name).map_err(tantivy_error);
+        Ok(Self {
+            code: field("code")?,
+            name: field("name")?,
+            brands: field("brands")?,
+            categories: field("categories")?,
+            product: field("product")?,
+        })
+    }
+}
+
+/// Indexes every product of the export in `dir`, replacing the index that is there.
+pub fn build(dir: &Path, input: &Path) -> io::Result<()> {
+    if dir.join("meta.json").exists() {
+        fs::remove_dir_all(dir)?;
+    } else if fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} is not an index and not empty", dir.display()),
+        ));
+    }
+    fs::create_dir_all(dir)?;
+    let index = Index::create_in_dir(dir, Fields::schema()).map_err(tantivy_error)?;
+    let fields = Fields::new(&index.schema())?;
+    let mut writer: IndexWriter = index.writer(WRITER_MEMORY).map_err(tantivy_error)?;
+    let (export, records) = Export::open(input)?;
+    let progress = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+    progress.set_style(
+        ProgressStyle::with_template("{spinner} {pos} products indexed, {per_sec}")
+            .expect("the template is valid"),
+    );
+    for record in records {
+        let record = record?;
+        let Ok(product) = export.product(&record) else {
+            continue;
+        };
+        let mut document = TantivyDocument::default();
+        document.add_text(fields.code, export.barcode(&record));
+        if let Some(name) = &product.name {
+            document.add_text(fields.name, name);
+        }
+        if let Some(brands) = &product.brands {
+            document.add_text(fields.brands, brands);
+        }
+        for tag in &product.categories_tags {
+            document.add_text(fields.categories, tag);
+        }
+        document.add_text(fields.product, serde_json::to_string(&product)?);
+        writer.add_document(document).map_err(tantivy_error)?;
+        progress.inc(1);
+    }
+    writer.commit().map_err(tantivy_error)?;
+    progress.finish_and_clear();
+    println!("Products indexed: {}", progress.position());
+    Ok(())
+}
+
+/// Finds the products of the index that match the query, letting the user pick one to score
+/// like `search`. All words have to match, the ones of names and brands with a typo at most.
+pub fn search(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    dir: &Path,
+    query: &str,
+    limit: usize,
+    category: Option<Category>,
+) -> io::Result<Option<char>> {
+    if !dir.join("meta.json").exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "there is no index in {}, create it with index build",
+                dir.display()
+            ),
+        ));
+    }
+    let index = Index::open_in_dir(dir).map_err(tantivy_error)?;
+    let fields = Fields::new(&index.schema())?;
+    let searcher = index.reader().map_err(tantivy_error)?.searcher();
+    let mut parser =
+        QueryParser::for_index(&index, vec![fields.name, fields.brands, fields.categories]);
+    parser.set_conjunction_by_default();
+    parser.set_field_fuzzy(fields.name, false, 1, true);
+    parser.set_field_fuzzy(fields.brands, false, 1, true);
+    let parsed = parser
+        .parse_query(query)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let top = searcher
+        .search(&parsed, &TopDocs::with_limit(limit.max(1)))
+        .map_err(tantivy_error)?;
+    let mut hits = Vec::with_capacity(top.len());
+    for (rank, (_, address)) in top.into_iter().enumerate() {
+        let document: TantivyDocument = searcher.doc(address).map_err(tantivy_error)?;
+        let get = |field| document.get_first(field).and_then(|value| value.as_str());
+        let product: Product = serde_json::from_str(get(fields.product).unwrap_or_default())?;
+        hits.push(Hit {
+            // the best match first, like the hits of search
+            score: -i64::try_from(rank).unwrap_or(i64::MAX),
+            barcode: get(fields.code).unwrap_or_default().to_owned(),
+            product,
+        });
+    }
+    if hits.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no product in the index matches {query:?}"),
+        ));
+    }
+    search::pick(algorithm, format, text, &hits, category)
+}
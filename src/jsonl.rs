@@ -0,0 +1,68 @@
+use crate::schema::Validator;
+use crate::JsonResult;
+use nutriscore::{Algorithm, Product};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+
+#[derive(Deserialize)]
+struct Line {
+    #[serde(flatten)]
+    product: Product,
+    algorithm: Option<Algorithm>,
+}
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    algorithm: Algorithm,
+    #[serde(flatten)]
+    result: JsonResult<'a>,
+}
+
+/// Reads one product per line from stdin and prints one result per line, or an object with an
+/// `error` for lines that are not a product. Empty lines are skipped, and alcoholic drinks get
+/// `"applicable": false` instead of a grade.
+pub fn run(algorithm: Algorithm) -> io::Result<()> {
+    let stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+    let validator = Validator::new();
+    for (i, line) in stdin.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let json = match validator.parse::<Line>(&line) {
+            Ok(Line {
+                product,
+                algorithm: line_algorithm,
+            }) => {
+                let algorithm = line_algorithm.unwrap_or(algorithm);
+                if !product.is_applicable() {
+                    let json = serde_json::json!({
+                        "name": product.name,
+                        "algorithm": algorithm,
+                        "applicable": false,
+                        "alcohol": product.alcohol,
+                    });
+                    writeln!(stdout, "{json}")?;
+                    continue;
+                }
+                let score = product.score(algorithm);
+                serde_json::to_string(&JsonLine {
+                    name: product.name.as_deref(),
+                    algorithm,
+                    result: JsonResult {
+                        score: score.total,
+                        grade: product.letter(algorithm, &score),
+                        negative: &score.negative,
+                        positive: &score.positive,
+                    },
+                })?
+            }
+            Err(e) => serde_json::json!({ "line": i + 1, "error": e }).to_string(),
+        };
+        writeln!(stdout, "{json}")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::i18n::{tr, tr_with};
+use clap::ValueEnum;
+use nutriscore::Algorithm;
+use serde::Deserialize;
+use std::io;
+use std::time::SystemTime;
+
+/// A market with its own rules for the Nutri-Score, for `--jurisdiction`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Jurisdiction {
+    France,
+    Belgium,
+    Germany,
+    Luxembourg,
+    Netherlands,
+    Spain,
+    Switzerland,
+}
+
+impl Jurisdiction {
+    /// The first day of the 2023 algorithm and the last day the 2017 one may be used, `None` if
+    /// it was never used.
+    const fn dates(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Self::France
+            | Self::Belgium
+            | Self::Germany
+            | Self::Luxembourg
+            | Self::Spain
+            | Self::Switzerland => ("2024-01-01", Some("2025-12-31")),
+            // introduced together with the 2023 algorithm
+            Self::Netherlands => ("2024-01-01", None),
+        }
+    }
+
+    fn id(self) -> String {
+        self.to_possible_value()
+            .map(|v| v.get_name().to_owned())
+            .unwrap_or_default()
+    }
+
+    fn name(self) -> String {
+        tr(&format!("jurisdiction-{}", self.id()))
+    }
+
+    /// Whether an algorithm may be used on a day, given as `YYYY-MM-DD`.
+    fn allows(self, algorithm: Algorithm, date: &str) -> bool {
+        let (new_from, old_until) = self.dates();
+        match algorithm {
+            Algorithm::V2017 => old_until.is_some_and(|until| date <= until),
+            Algorithm::V2023 => date >= new_from,
+        }
+    }
+
+    /// The algorithm whose grade is valid in the market on a day, the requested one if it is
+    /// allowed and otherwise the newest one allowed. Prints the transition period and the
+    /// guidance of the market on stderr.
+    pub fn algorithm(self, requested: Option<Algorithm>, date: &str) -> io::Result<Algorithm> {
+        let algorithm = match requested {
+            Some(algorithm) => algorithm,
+            None if self.allows(Algorithm::V2023, date) => Algorithm::V2023,
+            None => Algorithm::V2017,
+        };
+        let args = [
+            ("country", self.name().into()),
+            ("algorithm", algorithm.to_string().into()),
+            ("date", date.to_owned().into()),
+        ];
+        if !self.allows(algorithm, date) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                tr_with("jurisdiction-not-valid", &args),
+            ));
+        }
+        let (_, old_until) = self.dates();
+        if let Some(until) = old_until
+            .filter(|_| self.allows(Algorithm::V2017, date) && self.allows(Algorithm::V2023, date))
+        {
+            let args = [("country", self.name().into()), ("until", until.into())];
+            eprintln!("{}", tr_with("jurisdiction-transition", &args));
+        }
+        eprintln!("{}", tr(&format!("jurisdiction-guidance-{}", self.id())));
+        Ok(algorithm)
+    }
+}
+
+/// Today as `YYYY-MM-DD`, in UTC.
+pub fn today() -> String {
+    humantime::format_rfc3339(SystemTime::now()).to_string()[..10].to_owned()
+}
+
+/// Parses a day for `--date`.
+pub fn date(text: &str) -> Result<String, String> {
+    humantime::parse_rfc3339(&format!("{text}T00:00:00Z"))
+        .map(|_| text.to_owned())
+        .map_err(|_| format!("{text:?} is not a date like 2024-06-30"))
+}
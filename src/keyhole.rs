@@ -0,0 +1,196 @@
+use crate::Nutrition;
+use serde::Serialize;
+use strum::{Display, EnumIter, EnumString};
+
+/// The food groups of the Nordic Keyhole label, see [`keyhole`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumIter, Serialize)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyholeGroup {
+    /// Processed vegetables, fruits, berries and legumes.
+    Vegetables,
+    Bread,
+    BreakfastCereals,
+    /// Pasta, rice and other grains.
+    Pasta,
+    Milk,
+    /// Yoghurt and other fermented milk.
+    Yoghurt,
+    Cheese,
+    /// Spreadable fats like margarine.
+    Spreads,
+    Oils,
+    Meat,
+    /// Sausages, ham and other meat products.
+    ProcessedMeat,
+    Fish,
+    ReadyMeals,
+}
+
+/// A limit of a criterion.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bound {
+    AtMost(f32),
+    AtLeast(f32),
+}
+
+/// One criterion of a food group and whether the product meets it.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct Criterion {
+    /// Name of the nutrient.
+    pub nutrient: &'static str,
+    /// The amount per 100g, or the percentage for the share of saturates in the fat.
+    pub amount: f32,
+    /// `g` or `%`.
+    pub unit: &'static str,
+    #[serde(flatten)]
+    pub bound: Bound,
+    pub met: bool,
+}
+
+/// The result of [`keyhole`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Keyhole {
+    pub group: KeyholeGroup,
+    /// Whether all criteria are met.
+    pub eligible: bool,
+    pub criteria: Vec<Criterion>,
+}
+
+type Field = fn(&Nutrition) -> f32;
+
+const FAT: (&str, Field, &str) = ("Fat", |n| n.fat, "g");
+const SUGARS: (&str, Field, &str) = ("Sugars", |n| n.sugar, "g");
+const SALT: (&str, Field, &str) = ("Salt", |n| n.salt, "g");
+const FIBERS: (&str, Field, &str) = ("Fibers", |n| n.fibers, "g");
+const SATURATES_OF_FAT: (&str, Field, &str) = ("Saturates of fat", Nutrition::saturates_ratio, "%");
+
+impl KeyholeGroup {
+    /// The nutrients with their limits per 100g.
+    fn criteria(self) -> Vec<((&'static str, Field, &'static str), Bound)> {
+        use Bound::{AtLeast, AtMost};
+        match self {
+            Self::Vegetables => vec![(FAT, AtMost(3.0)), (SALT, AtMost(0.5))],
+            Self::Bread => vec![
+                (FAT, AtMost(7.0)),
+                (SUGARS, AtMost(5.0)),
+                (SALT, AtMost(1.0)),
+                (FIBERS, AtLeast(5.0)),
+            ],
+            Self::BreakfastCereals => vec![
+                (FAT, AtMost(7.0)),
+                (SUGARS, AtMost(13.0)),
+                (SALT, AtMost(1.0)),
+                (FIBERS, AtLeast(6.0)),
+            ],
+            Self::Pasta => vec![(FAT, AtMost(3.0)), (FIBERS, AtLeast(6.0))],
+            Self::Milk => vec![(FAT, AtMost(0.7)), (SUGARS, AtMost(5.0))],
+            Self::Yoghurt => vec![(FAT, AtMost(3.0)), (SUGARS, AtMost(9.0))],
+            Self::Cheese => vec![(FAT, AtMost(17.0)), (SALT, AtMost(1.3))],
+            Self::Spreads => vec![
+                (FAT, AtMost(80.0)),
+                (SATURATES_OF_FAT, AtMost(33.0)),
+                (SALT, AtMost(1.3)),
+            ],
+            Self::Oils => vec![(SATURATES_OF_FAT, AtMost(20.0))],
+            Self::Meat => vec![(FAT, AtMost(10.0))],
+            Self::ProcessedMeat => vec![(FAT, AtMost(10.0)), (SALT, AtMost(1.5))],
+            Self::Fish => vec![(SALT, AtMost(1.5))],
+            Self::ReadyMeals => vec![(FAT, AtMost(4.5)), (SALT, AtMost(0.8))],
+        }
+    }
+}
+
+/// Checks the values per 100g against the criteria of the Keyhole label for a food group.
+///
+/// The product is eligible if it meets all of them. Criteria that the nutrition values cannot
+/// tell, like whole grain or added sugar, are not checked.
+#[must_use]
+pub fn keyhole(group: KeyholeGroup, nutrition: &Nutrition) -> Keyhole {
+    let criteria: Vec<Criterion> = group
+        .criteria()
+        .into_iter()
+        .map(|((nutrient, field, unit), bound)| {
+            let amount = field(nutrition);
+            let met = match bound {
+                Bound::AtMost(limit) => amount <= limit,
+                Bound::AtLeast(limit) => amount >= limit,
+            };
+            Criterion {
+                nutrient,
+                amount,
+                unit,
+                bound,
+                met,
+            }
+        })
+        .collect();
+    Keyhole {
+        group,
+        eligible: criteria.iter().all(|c| c.met),
+        criteria,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The nutrients of the criteria that are not met.
+    fn failed(keyhole: &Keyhole) -> Vec<&'static str> {
+        keyhole
+            .criteria
+            .iter()
+            .filter(|c| !c.met)
+            .map(|c| c.nutrient)
+            .collect()
+    }
+
+    #[test]
+    fn products_that_meet_all_criteria_are_eligible() {
+        let bread = Nutrition {
+            fat: 7.0,
+            sugar: 2.0,
+            salt: 1.0,
+            fibers: 5.0,
+            ..Nutrition::default()
+        };
+        let keyhole = keyhole(KeyholeGroup::Bread, &bread);
+        assert!(keyhole.eligible);
+        assert_eq!(keyhole.criteria.len(), 4);
+    }
+
+    #[test]
+    fn every_criterion_that_is_not_met_is_reported() {
+        let bread = Nutrition {
+            fat: 7.1,
+            sugar: 2.0,
+            salt: 1.0,
+            fibers: 4.9,
+            ..Nutrition::default()
+        };
+        let keyhole = keyhole(KeyholeGroup::Bread, &bread);
+        assert!(!keyhole.eligible);
+        assert_eq!(failed(&keyhole), ["Fat", "Fibers"]);
+    }
+
+    #[test]
+    fn oils_are_limited_by_the_share_of_saturates() {
+        let oil = |saturated_fats| Nutrition {
+            fat: 100.0,
+            saturated_fats,
+            ..Nutrition::default()
+        };
+        assert!(keyhole(KeyholeGroup::Oils, &oil(15.0)).eligible);
+        let palm = keyhole(KeyholeGroup::Oils, &oil(50.0));
+        assert_eq!(failed(&palm), ["Saturates of fat"]);
+        assert_eq!(palm.criteria[0].unit, "%");
+    }
+
+    #[test]
+    fn no_fat_has_no_saturates() {
+        let keyhole = keyhole(KeyholeGroup::Spreads, &Nutrition::default());
+        assert!(keyhole.eligible);
+    }
+}
@@ -0,0 +1,428 @@
+use crate::Nutrition;
+use serde::Serialize;
+
+/// The nutrients a line of a nutrition table can be about.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Nutrient {
+    Energy,
+    Fat,
+    SaturatedFats,
+    Carbohydrates,
+    Sugar,
+    Fibers,
+    Proteins,
+    Salt,
+    Sodium,
+}
+
+/// The beginnings of the names of the nutrients in English, German, French, Dutch, Italian and
+/// Spanish, in lower case. The first one that starts a word of a line decides its nutrient, so
+/// "of which saturates" and "of which sugars" come before fat and carbohydrates.
+const NAMES: &[(&str, Nutrient)] = &[
+    ("satur", Nutrient::SaturatedFats),
+    ("gesättigt", Nutrient::SaturatedFats),
+    ("verzadigd", Nutrient::SaturatedFats),
+    ("sugar", Nutrient::Sugar),
+    ("zucker", Nutrient::Sugar),
+    ("sucre", Nutrient::Sugar),
+    ("suiker", Nutrient::Sugar),
+    ("zuccher", Nutrient::Sugar),
+    ("azúcar", Nutrient::Sugar),
+    ("azucar", Nutrient::Sugar),
+    ("energ", Nutrient::Energy),
+    ("énerg", Nutrient::Energy),
+    ("brennwert", Nutrient::Energy),
+    ("fat", Nutrient::Fat),
+    ("fett", Nutrient::Fat),
+    ("lipid", Nutrient::Fat),
+    ("matières grasses", Nutrient::Fat),
+    ("vet", Nutrient::Fat),
+    ("gras", Nutrient::Fat),
+    ("carbohydrate", Nutrient::Carbohydrates),
+    ("kohlenhydrat", Nutrient::Carbohydrates),
+    ("glucide", Nutrient::Carbohydrates),
+    ("koolhydrat", Nutrient::Carbohydrates),
+    ("carboidrat", Nutrient::Carbohydrates),
+    ("hidratos de carbono", Nutrient::Carbohydrates),
+    ("fibre", Nutrient::Fibers),
+    ("fiber", Nutrient::Fibers),
+    ("ballaststoff", Nutrient::Fibers),
+    ("vezel", Nutrient::Fibers),
+    ("fibra", Nutrient::Fibers),
+    ("protein", Nutrient::Proteins),
+    ("protéine", Nutrient::Proteins),
+    ("proteïne", Nutrient::Proteins),
+    ("proteína", Nutrient::Proteins),
+    ("eiweiß", Nutrient::Proteins),
+    ("eiweiss", Nutrient::Proteins),
+    ("eiwit", Nutrient::Proteins),
+    ("salt", Nutrient::Salt),
+    ("salz", Nutrient::Salt),
+    ("sel", Nutrient::Salt),
+    ("zout", Nutrient::Salt),
+    ("sale", Nutrient::Salt),
+    ("sal", Nutrient::Salt),
+    ("sodium", Nutrient::Sodium),
+    ("natrium", Nutrient::Sodium),
+    ("sodio", Nutrient::Sodium),
+];
+
+/// Parts of the names of fats that are not counted, anywhere in a line like "monoinsaturi",
+/// which would be taken for fat or saturated fats.
+const IGNORED: &[&str] = &["unsatur", "ungesättigt", "insatur", "onverzadigd", "trans"];
+
+/// The values of a nutrition table per 100g or 100ml that were recognized in its text.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Label {
+    /// In kJ.
+    pub energy: Option<f32>,
+    /// This and the others in g.
+    pub fat: Option<f32>,
+    pub saturated_fats: Option<f32>,
+    pub carbohydrates: Option<f32>,
+    pub sugar: Option<f32>,
+    pub fibers: Option<f32>,
+    pub proteins: Option<f32>,
+    /// Calculated from the sodium if the table has no salt.
+    pub salt: Option<f32>,
+}
+
+/// A part of a line of the table, the spaces between them are left out.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f32),
+    /// A unit in lower case, like `kj` or `%`.
+    Unit(String),
+    /// Any other word or character, like the `/` between kJ and kcal.
+    Other,
+}
+
+/// The tokens of a line in lower case. Numbers can have a decimal point or comma, like `0,5`.
+fn tokens(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        let mut take_while = |keep: &dyn Fn(usize, char) -> bool| {
+            while let Some(&(i, next)) = chars.peek() {
+                if !keep(i, next) {
+                    break;
+                }
+                end = i + next.len_utf8();
+                chars.next();
+            }
+            end
+        };
+        if c.is_ascii_digit() {
+            let end = take_while(&|i, next| {
+                next.is_ascii_digit()
+                    || (matches!(next, '.' | ',')
+                        && line[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+            });
+            tokens.push(
+                line[start..end]
+                    .replace(',', ".")
+                    .parse()
+                    .map_or(Token::Other, Token::Number),
+            );
+        } else if c.is_alphabetic() {
+            let word = &line[start..take_while(&|_, next| next.is_alphabetic())];
+            tokens.push(match word {
+                "kj" | "kcal" | "g" | "mg" | "µg" | "μg" | "mcg" => Token::Unit(word.to_owned()),
+                _ => Token::Other,
+            });
+        } else if c == '%' {
+            tokens.push(Token::Unit("%".to_owned()));
+        } else if !c.is_whitespace() {
+            tokens.push(Token::Other);
+        }
+    }
+    tokens
+}
+
+/// The numbers of a line with their units, like `(2252.0, "kj")`. A number gets the unit right
+/// after it or else right before it, like in `kJ 2252`. Numbers without either get the units
+/// named before the first number in turn, like in `Energy (kJ/kcal) 2252/539`, or none.
+fn numbers(line: &str) -> Vec<(f32, String)> {
+    let tokens = tokens(line);
+    let unit = |i: Option<usize>| match i.and_then(|i| tokens.get(i)) {
+        Some(Token::Unit(unit)) => Some(unit),
+        _ => None,
+    };
+    let heading: Vec<&String> = tokens
+        .iter()
+        .take_while(|token| !matches!(token, Token::Number(_)))
+        .filter_map(|token| match token {
+            Token::Unit(unit) => Some(unit),
+            _ => None,
+        })
+        .collect();
+    let mut numbers = Vec::new();
+    let mut unnamed = 0;
+    // the unit after the last number, which is not the one before the next
+    let mut taken = None;
+    for (i, token) in tokens.iter().enumerate() {
+        let Token::Number(number) = token else {
+            continue;
+        };
+        let before = i.checked_sub(1).filter(|&before| taken != Some(before));
+        let unit = match (unit(Some(i + 1)), unit(before)) {
+            (Some(unit), _) => {
+                taken = Some(i + 1);
+                unit.clone()
+            }
+            (None, Some(unit)) => unit.clone(),
+            (None, None) if heading.is_empty() => String::new(),
+            (None, None) => {
+                unnamed += 1;
+                heading[(unnamed - 1) % heading.len()].clone()
+            }
+        };
+        numbers.push((*number, unit));
+    }
+    numbers
+}
+
+/// The first value in g of a line, numbers in % are the reference intake.
+fn grams(numbers: &[(f32, String)]) -> Option<f32> {
+    numbers
+        .iter()
+        .find_map(|(number, unit)| match unit.as_str() {
+            "g" | "" => Some(*number),
+            "mg" => Some(number / 1000.0),
+            "µg" | "μg" | "mcg" => Some(number / 1_000_000.0),
+            _ => None,
+        })
+}
+
+/// The first value in kJ of a line, or else the first in kcal.
+fn kilojoules(numbers: &[(f32, String)]) -> Option<f32> {
+    numbers
+        .iter()
+        .find(|(_, unit)| unit == "kj")
+        .map(|(number, _)| *number)
+        .or_else(|| {
+            numbers
+                .iter()
+                .find(|(_, unit)| unit == "kcal")
+                .map(|(number, _)| Nutrition::kj_from_kcal(*number))
+        })
+}
+
+/// Where `name` starts a word of the line.
+fn word_start(line: &str, name: &str) -> Option<usize> {
+    line.match_indices(name)
+        .map(|(i, _)| i)
+        .find(|&i| !line[..i].ends_with(char::is_alphabetic))
+}
+
+impl Label {
+    /// Recognizes the values in the text of a nutrition table with one nutrient per line, like
+    /// "Fat 17 g", "Energy 2252 kJ / 539 kcal" or "davon gesättigte Fettsäuren 10,6 g". Values
+    /// on the line after the name are found as well, like in text copied from a PDF. Of several
+    /// columns the first is taken, which is per 100g or 100ml on European labels.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let mut label = Self::default();
+        let mut sodium = None;
+        // the nutrient of the last line if it had no value
+        let mut pending = None;
+        for line in text.lines() {
+            let line = line.to_lowercase();
+            if IGNORED.iter().any(|name| line.contains(name)) {
+                pending = None;
+                continue;
+            }
+            let named = NAMES.iter().find_map(|&(name, nutrient)| {
+                Some((nutrient, word_start(&line, name)? + name.len()))
+            });
+            let (nutrient, numbers) = match (named, pending) {
+                // the numbers after the name, not ones like the 3 of omega-3
+                (Some((nutrient, end)), _) => (nutrient, numbers(&line[end..])),
+                (None, Some(nutrient)) => (nutrient, numbers(&line)),
+                (None, None) => continue,
+            };
+            let (field, value) = match nutrient {
+                Nutrient::Energy => (&mut label.energy, kilojoules(&numbers)),
+                Nutrient::Fat => (&mut label.fat, grams(&numbers)),
+                Nutrient::SaturatedFats => (&mut label.saturated_fats, grams(&numbers)),
+                Nutrient::Carbohydrates => (&mut label.carbohydrates, grams(&numbers)),
+                Nutrient::Sugar => (&mut label.sugar, grams(&numbers)),
+                Nutrient::Fibers => (&mut label.fibers, grams(&numbers)),
+                Nutrient::Proteins => (&mut label.proteins, grams(&numbers)),
+                Nutrient::Salt => (&mut label.salt, grams(&numbers)),
+                Nutrient::Sodium => (&mut sodium, grams(&numbers)),
+            };
+            pending = value.is_none().then_some(nutrient);
+            // a table per portion often follows the one per 100g
+            if field.is_none() {
+                *field = value;
+            }
+        }
+        if label.salt.is_none() {
+            label.salt = sodium.map(|grams| Nutrition::salt_from_sodium_mg(grams * 1000.0));
+        }
+        label
+    }
+
+    /// The names of the values the table has none for, the carbohydrates are optional.
+    #[must_use]
+    pub fn missing(&self) -> Vec<&'static str> {
+        [
+            ("energy", self.energy),
+            ("fat", self.fat),
+            ("saturated fats", self.saturated_fats),
+            ("sugar", self.sugar),
+            ("proteins", self.proteins),
+            ("salt", self.salt),
+            ("fibers", self.fibers),
+        ]
+        .into_iter()
+        .filter(|(_, value)| value.is_none())
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    /// The nutrition if the table has every value but the carbohydrates, see [`Label::missing`].
+    #[must_use]
+    pub fn nutrition(&self) -> Option<Nutrition> {
+        Some(Nutrition {
+            energy: self.energy?,
+            fat: self.fat?,
+            saturated_fats: self.saturated_fats?,
+            carbohydrates: self.carbohydrates,
+            sugar: self.sugar?,
+            proteins: self.proteins?,
+            salt: self.salt?,
+            fibers: self.fibers?,
+            contains_sweeteners: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The values of a milk chocolate, which every table below gives.
+    const CHOCOLATE: Label = Label {
+        energy: Some(2252.0),
+        fat: Some(30.9),
+        saturated_fats: Some(10.6),
+        carbohydrates: Some(57.5),
+        sugar: Some(56.3),
+        fibers: Some(3.4),
+        proteins: Some(6.3),
+        salt: Some(0.107),
+    };
+
+    fn salt(text: &str) -> Option<String> {
+        Label::parse(text).salt.map(|salt| format!("{salt:.2}"))
+    }
+
+    #[test]
+    fn tables_are_read_in_six_languages() {
+        let tables = [
+            "Nutrition information per 100g\nEnergy 2252 kJ / 539 kcal\nFat 30.9 g\n\
+             of which saturates 10.6 g\nCarbohydrate 57.5 g\nof which sugars 56.3 g\n\
+             Fibre 3.4 g\nProtein 6.3 g\nSalt 0.107 g",
+            "Nährwerte pro 100 g\nBrennwert 2252 kJ / 539 kcal\nFett 30,9 g\n\
+             davon gesättigte Fettsäuren 10,6 g\nKohlenhydrate 57,5 g\ndavon Zucker 56,3 g\n\
+             Ballaststoffe 3,4 g\nEiweiß 6,3 g\nSalz 0,107 g",
+            "Valeurs nutritionnelles pour 100 g\nÉnergie 2252 kJ / 539 kcal\n\
+             Matières grasses 30,9 g\ndont acides gras saturés 10,6 g\nGlucides 57,5 g\n\
+             dont sucres 56,3 g\nFibres alimentaires 3,4 g\nProtéines 6,3 g\nSel 0,107 g",
+            "Voedingswaarde per 100 g\nEnergie 2252 kJ / 539 kcal\nVetten 30,9 g\n\
+             waarvan verzadigde vetzuren 10,6 g\nKoolhydraten 57,5 g\nwaarvan suikers 56,3 g\n\
+             Vezels 3,4 g\nEiwitten 6,3 g\nZout 0,107 g",
+            "Valori nutrizionali per 100 g\nEnergia 2252 kJ / 539 kcal\nGrassi 30,9 g\n\
+             di cui acidi grassi saturi 10,6 g\nCarboidrati 57,5 g\ndi cui zuccheri 56,3 g\n\
+             Fibre 3,4 g\nProteine 6,3 g\nSale 0,107 g",
+            "Información nutricional por 100 g\nValor energético 2252 kJ / 539 kcal\n\
+             Grasas 30,9 g\nde las cuales saturadas 10,6 g\nHidratos de carbono 57,5 g\n\
+             de los cuales azúcares 56,3 g\nFibra alimentaria 3,4 g\nProteínas 6,3 g\n\
+             Sal 0,107 g",
+        ];
+        for table in tables {
+            assert_eq!(Label::parse(table), CHOCOLATE, "{table}");
+        }
+        assert!(CHOCOLATE.missing().is_empty());
+        assert!(CHOCOLATE.nutrition().is_some());
+    }
+
+    #[test]
+    fn the_first_name_of_a_line_decides_the_nutrient() {
+        // saturates and sugars come before the fat and the carbohydrates they are part of
+        let label = Label::parse(
+            "Fat 17 g\nof which saturated fat 10 g\nCarbohydrates 50 g\nof which sugars 20 g",
+        );
+        assert_eq!(
+            (
+                label.fat,
+                label.saturated_fats,
+                label.carbohydrates,
+                label.sugar
+            ),
+            (Some(17.0), Some(10.0), Some(50.0), Some(20.0))
+        );
+    }
+
+    #[test]
+    fn fats_that_are_not_counted_are_ignored() {
+        let label = Label::parse(
+            "Fat\nof which trans fat 0.2 g\nof which mono-unsaturates 8 g\n\
+             of which saturates 3 g\nGrassi monoinsaturi 5 g",
+        );
+        assert_eq!((label.fat, label.saturated_fats), (None, Some(3.0)));
+    }
+
+    #[test]
+    fn values_can_be_on_the_next_line() {
+        let label = Label::parse("Fat\n17 g\nProtein\n\n6 g");
+        assert_eq!((label.fat, label.proteins), (Some(17.0), Some(6.0)));
+    }
+
+    #[test]
+    fn the_salt_is_calculated_from_the_sodium() {
+        assert_eq!(salt("Sodium 400 mg"), Some("1.00".to_owned()));
+        assert_eq!(salt("Natrium 0,2 g"), Some("0.50".to_owned()));
+        // the salt of the table is preferred
+        assert_eq!(salt("Sodium 0.4 g\nSalt 1.2 g"), Some("1.20".to_owned()));
+    }
+
+    #[test]
+    fn the_energy_is_taken_in_kj() {
+        let energy = |text| Label::parse(text).energy.map(|kj| format!("{kj:.1}"));
+        assert_eq!(
+            energy("Energy 539 kcal / 2252 kJ"),
+            Some("2252.0".to_owned())
+        );
+        assert_eq!(
+            energy("Energy (kJ/kcal) 2252/539"),
+            Some("2252.0".to_owned())
+        );
+        // converted if the table only has kcal
+        assert_eq!(energy("Energy 100 kcal"), Some("418.4".to_owned()));
+    }
+
+    #[test]
+    fn the_first_column_is_per_100_g() {
+        let label = Label::parse("Per 100 g / per portion (30 g) / %RI*\nFat 17 g 5.1 g 7 %");
+        assert_eq!(label.fat, Some(17.0));
+        // reference intakes are no values
+        assert_eq!(Label::parse("Fat 7 %").fat, None);
+        // so is a second table per portion
+        assert_eq!(Label::parse("Fat 17 g\nFat 5.1 g").fat, Some(17.0));
+    }
+
+    #[test]
+    fn missing_values_have_no_nutrition() {
+        let label = Label {
+            fibers: None,
+            carbohydrates: None,
+            ..CHOCOLATE
+        };
+        assert_eq!(label.missing(), ["fibers"]);
+        assert_eq!(label.nutrition(), None);
+    }
+}
@@ -0,0 +1,594 @@
+//! Calculation of the Nutri-Score label.
+//!
+//! The score is computed from the nutritional values per 100g (or 100ml for drinks)
+//! with [`calculate_nutriscore`] and turned into a letter from A to E with
+//! [`Category::score_to_letter`]. Other front-of-pack models implement [`ScoringModel`] like
+//! the Nutri-Score does.
+
+#![warn(
+    clippy::suspicious,
+    clippy::pedantic,
+    clippy::style,
+    clippy::complexity,
+    clippy::nursery,
+    clippy::cargo
+)]
+#![allow(clippy::multiple_crate_versions)]
+
+mod advise;
+mod badge;
+mod barcode;
+mod cutoffs;
+#[cfg(feature = "arrow")]
+pub mod dataframe;
+mod definition;
+mod eco;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fruits;
+mod hsr;
+mod keyhole;
+mod label;
+mod margin;
+mod model;
+mod nova;
+#[cfg(feature = "off")]
+pub mod off;
+mod product;
+mod range;
+mod recipe;
+mod tolerance;
+mod traffic_lights;
+mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod who;
+
+pub use advise::{advise, Advice};
+pub use badge::{badge_svg, shield_svg, GRADE_COLORS};
+pub use barcode::{parse_barcode, BarcodeError};
+pub use cutoffs::{CustomCutoffs, CutoffTables};
+pub use definition::CategoryDefinition;
+pub use eco::{EcoCategory, EcoLabel, EcoProduct, EcoScore, Origin, Packaging};
+pub use fruits::{fruits_percentage, Ingredient, IngredientKind};
+pub use hsr::HealthStarRating;
+pub use keyhole::{keyhole, Bound, Criterion, Keyhole, KeyholeGroup};
+pub use label::Label;
+pub use margin::{margins, Margin};
+pub use model::ScoringModel;
+pub use nova::{nova_group, Nova};
+pub use product::{DrinkType, Product, ALCOHOL_LIMIT};
+pub use range::{calculate_score_range, ScoreRange, Unknown};
+pub use recipe::{Recipe, RecipeIngredient};
+pub use tolerance::{uncertainty, GradeShare, Tolerance, Tolerances, Uncertainty};
+pub use traffic_lights::{traffic_lights, Light, TrafficLight, TrafficLights};
+pub use validate::{Field, Issue, Severity};
+pub use who::{WhoCategory, WhoEurope};
+
+use crate::Category::{Cheese, Drinks, MilkDrinks, OilsAndFats, RedMeat};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use strum::{Display, EnumCount, EnumIter, EnumString, EnumVariantNames};
+
+/// The version of the Nutri-Score algorithm.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Display, EnumString)]
+pub enum Algorithm {
+    /// The original algorithm from 2017.
+    #[default]
+    #[strum(serialize = "2017")]
+    V2017,
+    /// The revised algorithm from 2023.
+    #[strum(serialize = "2023")]
+    V2023,
+}
+
+/// The food category, which decides the cutoff tables and special rules that apply.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumVariantNames, EnumIter, EnumCount,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Category {
+    Drinks,
+    /// Milk, drinkable yoghurts and plant milks, which count as drinks in the 2023 algorithm.
+    #[strum(to_string = "Milk Drinks", serialize = "milk-drinks")]
+    MilkDrinks,
+    Cheese,
+    #[strum(to_string = "Oils And Fats", serialize = "oils-and-fats")]
+    OilsAndFats,
+    /// Red meat and meat products, which have their protein points capped in the 2023 algorithm.
+    #[strum(to_string = "Red Meat", serialize = "red-meat")]
+    RedMeat,
+    Other,
+}
+
+impl Category {
+    /// Converts a score calculated with [`calculate_nutriscore`] into the letter from A to E.
+    #[must_use]
+    pub fn score_to_letter(self, algorithm: Algorithm, score: isize, is_water: bool) -> char {
+        self.definition(algorithm).letter(score, is_water)
+    }
+
+    /// Whether the category is scored with the drinks tables.
+    #[must_use]
+    pub fn is_beverage(self, algorithm: Algorithm) -> bool {
+        self.definition(algorithm).beverage
+    }
+
+    /// Maps the `categories_tags` of an Open Food Facts product onto a category.
+    ///
+    /// Returns `None` if no tag is known, which usually means [`Category::Other`].
+    #[must_use]
+    pub fn from_off_tags<S: AsRef<str>>(tags: &[S]) -> Option<Self> {
+        OFF_CATEGORIES.iter().find_map(|(category, off_tags)| {
+            tags.iter()
+                .any(|t| off_tags.contains(&t.as_ref()))
+                .then_some(*category)
+        })
+    }
+}
+
+// Open Food Facts category tags, the first matching category wins
+static OFF_CATEGORIES: [(Category, &[&str]); 5] = [
+    (
+        MilkDrinks,
+        &[
+            "en:milks",
+            "en:plant-based-milks",
+            "en:drinkable-yogurts",
+            "en:dairy-drinks",
+        ],
+    ),
+    (Drinks, &["en:beverages", "en:waters"]),
+    (Cheese, &["en:cheeses"]),
+    (
+        OilsAndFats,
+        &[
+            "en:fats",
+            "en:vegetable-oils",
+            "en:olive-oils",
+            "en:butters",
+            "en:margarines",
+        ],
+    ),
+    (
+        RedMeat,
+        &[
+            "en:red-meats",
+            "en:beef",
+            "en:pork",
+            "en:lamb",
+            "en:veal",
+            "en:hams",
+            "en:salamis",
+        ],
+    ),
+];
+
+impl Serialize for Algorithm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Algorithm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::custom(format!("unknown algorithm {s:?}")))
+    }
+}
+
+impl Serialize for Category {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Category {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::custom(format!("unknown category {s:?}")))
+    }
+}
+
+/// Nutritional values per 100g or 100ml.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Nutrition {
+    /// Energy in kJ.
+    pub energy: f32,
+    /// Fats in g.
+    pub fat: f32,
+    /// Saturated fats in g.
+    pub saturated_fats: f32,
+    /// Sugar in g.
+    pub sugar: f32,
+    /// Carbohydrates in g, including the sugar. This is not scored but used for validation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carbohydrates: Option<f32>,
+    /// Proteins in g.
+    pub proteins: f32,
+    /// Salt in g.
+    pub salt: f32,
+    /// Fibers in g.
+    pub fibers: f32,
+    /// Whether non-nutritive sweeteners were added, only relevant for drinks.
+    #[serde(default)]
+    pub contains_sweeteners: bool,
+}
+
+impl Nutrition {
+    /// Converts values per serving of `serving_size` g or ml into values per 100g or 100ml.
+    #[must_use]
+    pub fn per_100(self, serving_size: f32) -> Self {
+        let factor = 100.0 / serving_size;
+        Self {
+            energy: self.energy * factor,
+            fat: self.fat * factor,
+            saturated_fats: self.saturated_fats * factor,
+            sugar: self.sugar * factor,
+            carbohydrates: self.carbohydrates.map(|c| c * factor),
+            proteins: self.proteins * factor,
+            salt: self.salt * factor,
+            fibers: self.fibers * factor,
+            contains_sweeteners: self.contains_sweeteners,
+        }
+    }
+
+    /// Rounds the values like the official rules before they are compared with the cutoffs:
+    /// energy to 1 kJ, salt to 0.01g and everything else to 0.1g.
+    #[must_use]
+    pub fn rounded(self) -> Self {
+        Self {
+            energy: self.energy.round(),
+            fat: round(self.fat, 10.0),
+            saturated_fats: round(self.saturated_fats, 10.0),
+            sugar: round(self.sugar, 10.0),
+            carbohydrates: self.carbohydrates.map(|c| round(c, 10.0)),
+            proteins: round(self.proteins, 10.0),
+            salt: round(self.salt, 100.0),
+            fibers: round(self.fibers, 10.0),
+            contains_sweeteners: self.contains_sweeteners,
+        }
+    }
+
+    /// Converts energy in kcal into kJ.
+    #[must_use]
+    pub fn kj_from_kcal(kcal: f32) -> f32 {
+        kcal * 4.184
+    }
+
+    /// Converts sodium in mg into salt in g.
+    #[must_use]
+    pub fn salt_from_sodium_mg(sodium_mg: f32) -> f32 {
+        sodium_mg * 2.5 / 1000.0
+    }
+
+    /// Sodium in mg, derived from the salt content, like the sodium cutoffs of 2017.
+    #[must_use]
+    pub fn sodium(&self) -> f32 {
+        self.salt / 2.5 * 1000.0
+    }
+
+    /// The percentage of saturated fats in the fat, scored for oils and fats.
+    ///
+    /// It is 0 without fat instead of dividing by zero, and stays between 0 and 100 even if
+    /// the values are impossible, see [`Nutrition::validate`].
+    #[must_use]
+    pub fn saturates_ratio(&self) -> f32 {
+        if self.fat > 0.0 && self.saturated_fats > 0.0 {
+            (self.saturated_fats / self.fat * 100.0).min(100.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+// negative
+static ENERGY_CUTOFFS: [f32; 10] = [
+    335.0, 670.0, 1005.0, 1340.0, 1675.0, 2010.0, 2345.0, 2680.0, 3015.0, 3350.0,
+];
+static SUGAR_CUTOFFS: [f32; 10] = [4.5, 9.0, 13.5, 18.0, 22.5, 27.0, 31.0, 36.0, 40.0, 45.0];
+static SATURATED_FATS_CUTOFF: [f32; 10] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+static SODIUM_CUTOFF: [f32; 10] = [
+    90.0, 180.0, 270.0, 360.0, 450.0, 540.0, 630.0, 720.0, 810.0, 900.0,
+];
+
+// positive
+static FRUITS_CUTOFFS: [f32; 10] = [
+    40.0,
+    60.0,
+    80.0,
+    80.0,
+    80.0,
+    f32::INFINITY,
+    f32::INFINITY,
+    f32::INFINITY,
+    f32::INFINITY,
+    f32::INFINITY,
+];
+static FIBERS_CUTOFFS: [f32; 5] = [0.8, 1.9, 2.8, 3.7, 4.7];
+static PROTEIN_CUTOFFS: [f32; 5] = [1.6, 3.2, 4.8, 6.4, 8.0];
+
+// percentages of saturated fats / all fats
+static SATURATED_FATS_RATIO_CUTOFFS: [f32; 10] =
+    [10.0, 16.0, 22.0, 28.0, 34.0, 40.0, 46.0, 52.0, 58.0, 64.0];
+
+// negative, 2023
+static SUGAR_CUTOFFS_2023: [f32; 15] = [
+    3.4, 6.8, 10.0, 14.0, 17.0, 20.0, 24.0, 27.0, 31.0, 34.0, 37.0, 41.0, 44.0, 48.0, 51.0,
+];
+static SALT_CUTOFFS_2023: [f32; 20] = [
+    0.2, 0.4, 0.6, 0.8, 1.0, 1.2, 1.4, 1.6, 1.8, 2.0, 2.2, 2.4, 2.6, 2.8, 3.0, 3.2, 3.4, 3.6, 3.8,
+    4.0,
+];
+static ENERGY_FROM_SATURATES_CUTOFFS_2023: [f32; 10] = [
+    120.0, 240.0, 360.0, 480.0, 600.0, 720.0, 840.0, 960.0, 1080.0, 1200.0,
+];
+static DRINKS_ENERGY_CUTOFFS_2023: [f32; 10] = [
+    30.0, 90.0, 150.0, 210.0, 240.0, 270.0, 300.0, 330.0, 360.0, 390.0,
+];
+static DRINKS_SUGAR_CUTOFFS_2023: [f32; 10] = [0.5, 2.0, 3.5, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0];
+
+// positive, 2023
+static FRUITS_CUTOFFS_2023: [f32; 5] = [40.0, 60.0, 80.0, 80.0, 80.0];
+static FIBERS_CUTOFFS_2023: [f32; 5] = [3.0, 4.1, 5.2, 6.3, 7.4];
+static PROTEIN_CUTOFFS_2023: [f32; 7] = [2.4, 4.8, 7.2, 9.6, 12.0, 14.0, 17.0];
+static DRINKS_FRUITS_CUTOFFS_2023: [f32; 6] = [40.0, 40.0, 60.0, 60.0, 80.0, 80.0];
+static DRINKS_PROTEIN_CUTOFFS_2023: [f32; 7] = [1.2, 1.5, 1.8, 2.1, 2.4, 2.7, 3.0];
+
+// the scoring has no global state, so products can be scored on many threads at once
+const _: () = {
+    const fn thread_safe<T: Send + Sync>() {}
+    thread_safe::<Nutrition>();
+    thread_safe::<Product>();
+    thread_safe::<Score>();
+};
+
+// every cutoff table has to be sorted for `points` to work, checked at compile time
+const _: () = {
+    let tables: [&[f32]; 18] = [
+        &ENERGY_CUTOFFS,
+        &SUGAR_CUTOFFS,
+        &SATURATED_FATS_CUTOFF,
+        &SODIUM_CUTOFF,
+        &FRUITS_CUTOFFS,
+        &FIBERS_CUTOFFS,
+        &PROTEIN_CUTOFFS,
+        &SATURATED_FATS_RATIO_CUTOFFS,
+        &SUGAR_CUTOFFS_2023,
+        &SALT_CUTOFFS_2023,
+        &ENERGY_FROM_SATURATES_CUTOFFS_2023,
+        &DRINKS_ENERGY_CUTOFFS_2023,
+        &DRINKS_SUGAR_CUTOFFS_2023,
+        &FRUITS_CUTOFFS_2023,
+        &FIBERS_CUTOFFS_2023,
+        &PROTEIN_CUTOFFS_2023,
+        &DRINKS_FRUITS_CUTOFFS_2023,
+        &DRINKS_PROTEIN_CUTOFFS_2023,
+    ];
+    let mut t = 0;
+    while t < tables.len() {
+        assert!(is_sorted(tables[t]), "cutoff table is not sorted");
+        t += 1;
+    }
+};
+
+const fn is_sorted(arr: &[f32]) -> bool {
+    let mut i = 1;
+    while i < arr.len() {
+        if arr[i - 1] > arr[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// The points awarded for a single nutrient.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct Component {
+    /// Display name of the nutrient.
+    pub name: &'static str,
+    /// The value the points were looked up with.
+    pub value: f32,
+    /// The points awarded.
+    pub points: usize,
+    /// The maximum number of points for this nutrient.
+    pub max_points: usize,
+    /// The cutoff the value is above, if any.
+    pub lower: Option<f32>,
+    /// The cutoff the value is at or below, if any.
+    pub upper: Option<f32>,
+    /// Whether the points are part of the total score.
+    pub counted: bool,
+    /// The cutoffs the value was scored with, one per point.
+    #[serde(skip)]
+    pub cutoffs: &'static [f32],
+}
+
+/// The result of [`calculate_nutriscore`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Score {
+    /// Energy, sugar, saturated fats, sodium and sweeteners.
+    pub negative: Vec<Component>,
+    /// Fruits & vegetables, fibers and protein.
+    pub positive: Vec<Component>,
+    /// The total score, lower is better.
+    pub total: isize,
+}
+
+impl Score {
+    /// Sum of the negative points.
+    #[must_use]
+    pub fn negative_points(&self) -> usize {
+        self.negative.iter().map(|c| c.points).sum()
+    }
+
+    /// Sum of the positive points that are counted.
+    #[must_use]
+    pub fn positive_points(&self) -> usize {
+        self.positive
+            .iter()
+            .filter(|c| c.counted)
+            .map(|c| c.points)
+            .sum()
+    }
+}
+
+/// Rounds to `1 / factor`, e.g. to one decimal with a factor of 10.
+fn round(value: f32, factor: f32) -> f32 {
+    (value * factor).round() / factor
+}
+
+fn points<T>(arr: &[T], value: &T) -> usize
+where
+    T: PartialOrd,
+{
+    let idx: usize = arr.iter().rposition(|c| value > c).map_or(0, |n| n + 1);
+    assert!(idx <= arr.len());
+    idx
+}
+
+fn component(name: &'static str, arr: &'static [f32], value: f32) -> Component {
+    let points = points(arr, &value);
+    Component {
+        name,
+        value,
+        points,
+        max_points: arr.len(),
+        lower: points.checked_sub(1).map(|i| arr[i]),
+        upper: arr.get(points).copied(),
+        counted: true,
+        cutoffs: arr,
+    }
+}
+
+/// Calculates the Nutri-Score for a product.
+///
+/// `fruits_value` is the percentage of fruits, vegetables and nuts. The values are rounded with
+/// [`Nutrition::rounded`] and the percentage to 0.1% first.
+///
+/// # Panics
+///
+/// Panics if the points exceed the range of `isize`, which cannot happen with the built-in tables.
+#[must_use]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(nutrition))
+)]
+pub fn calculate_nutriscore(
+    algorithm: Algorithm,
+    cat: Category,
+    nutrition: &Nutrition,
+    fruits_value: f32,
+) -> Score {
+    cat.definition(algorithm).score(nutrition, fruits_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The points of the energy, sugar, saturated fats and sodium in 2017.
+    fn negative_points(nutrition: Nutrition) -> Vec<usize> {
+        let score = calculate_nutriscore(Algorithm::V2017, Category::Other, &nutrition, 0.0);
+        score.negative.iter().map(|c| c.points).collect()
+    }
+
+    fn sodium_points(salt: f32) -> usize {
+        negative_points(Nutrition {
+            salt,
+            ..Nutrition::default()
+        })[3]
+    }
+
+    #[test]
+    fn capped_protein_has_the_band_of_its_points() {
+        let nutrition = Nutrition {
+            proteins: 20.0,
+            ..Nutrition::default()
+        };
+        let score = calculate_nutriscore(Algorithm::V2023, Category::RedMeat, &nutrition, 0.0);
+        let protein = &score.positive[2];
+        assert_eq!(protein.points, 2);
+        assert_eq!(protein.lower, Some(protein.cutoffs[1]));
+        assert_eq!(protein.upper, None);
+    }
+
+    fn saturates_ratio(fat: f32, saturated_fats: f32) -> f32 {
+        Nutrition {
+            fat,
+            saturated_fats,
+            ..Nutrition::default()
+        }
+        .saturates_ratio()
+    }
+
+    #[test]
+    fn saturates_ratio_without_fat_is_0() {
+        assert_eq!(
+            (saturates_ratio(0.0, 0.0), saturates_ratio(0.0, 5.0)),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn saturates_ratio_of_more_saturates_than_fat_is_100() {
+        assert_eq!(
+            (saturates_ratio(10.0, 5.0), saturates_ratio(10.0, 20.0)),
+            (50.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn sodium_is_scored_in_mg_in_2017() {
+        // 88 mg and 92 mg of sodium, around the first cutoff of 90 mg
+        assert_eq!(sodium_points(0.22), 0);
+        assert_eq!(sodium_points(0.23), 1);
+        // 896 mg and 1000 mg, around the last cutoff of 900 mg
+        assert_eq!(sodium_points(2.24), 9);
+        assert_eq!(sodium_points(2.5), 10);
+    }
+
+    #[test]
+    fn values_are_rounded_with_the_official_precision() {
+        let rounded = Nutrition {
+            energy: 335.6,
+            sugar: 4.54,
+            carbohydrates: Some(12.35),
+            salt: 0.225,
+            ..Nutrition::default()
+        }
+        .rounded();
+        assert_eq!(
+            (
+                rounded.energy,
+                rounded.sugar,
+                rounded.carbohydrates,
+                rounded.salt
+            ),
+            (336.0, 4.5, Some(12.4), 0.23)
+        );
+    }
+
+    #[test]
+    fn rounding_decides_the_points_at_a_cutoff() {
+        // 335 kJ and 4.5 g are the first cutoffs of the energy and the sugar
+        let energy = |energy| {
+            negative_points(Nutrition {
+                energy,
+                ..Nutrition::default()
+            })[0]
+        };
+        assert_eq!((energy(335.4), energy(335.6)), (0, 1));
+        let sugar = |sugar| {
+            negative_points(Nutrition {
+                sugar,
+                ..Nutrition::default()
+            })[1]
+        };
+        assert_eq!((sugar(4.54), sugar(4.56)), (0, 1));
+        // 0.225 g of salt are 0.23 g, or 92 mg of sodium above the cutoff of 90 mg
+        assert_eq!((sodium_points(0.224), sodium_points(0.225)), (0, 1));
+    }
+}
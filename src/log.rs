@@ -0,0 +1,45 @@
+use clap::ValueEnum;
+use std::io;
+use tracing::level_filters::LevelFilter;
+
+/// The most verbose messages that are logged, for `--log-level`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    /// What the batch jobs, lookups and the server do.
+    Info,
+    /// Also every product that is scored.
+    Debug,
+    /// Also the points of every score.
+    Trace,
+}
+
+/// How log messages are written, for `--log-format`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum LogFormat {
+    /// One line of text per message.
+    Text,
+    /// One JSON object per line, with the fields of the message and its spans.
+    Json,
+}
+
+/// Logs the messages up to `level` on stderr, which keeps stdout for the results.
+pub fn init(level: LogLevel, format: LogFormat, color: bool) {
+    let level = match level {
+        LogLevel::Off => LevelFilter::OFF,
+        LogLevel::Error => LevelFilter::ERROR,
+        LogLevel::Warn => LevelFilter::WARN,
+        LogLevel::Info => LevelFilter::INFO,
+        LogLevel::Debug => LevelFilter::DEBUG,
+        LogLevel::Trace => LevelFilter::TRACE,
+    };
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(io::stderr);
+    match format {
+        LogFormat::Text => builder.with_ansi(color).init(),
+        LogFormat::Json => builder.json().with_current_span(true).init(),
+    }
+}
@@ -0,0 +1,187 @@
+use crate::cache::Cache;
+use crate::config::Config;
+use crate::i18n::{self, tr_with};
+use crate::network::NetworkArgs;
+use crate::{
+    draw_score, grade_style, print_breakdown, print_quiet, Format, JsonResult, TextOptions,
+};
+use clap::Args;
+use nutriscore::off::{self, Client, Product};
+use nutriscore::{calculate_nutriscore, Algorithm, Category};
+use serde::Serialize;
+use std::io;
+
+/// The flags of the client for Open Food Facts.
+#[derive(Args, Debug)]
+pub struct OffArgs {
+    /// The URL of the Open Food Facts instance to use instead of the public one
+    #[clap(long, value_name = "URL")]
+    off_url: Option<String>,
+    /// How often a request that failed because of the network or the server is repeated
+    #[clap(long, default_value_t = off::RETRIES)]
+    retries: u32,
+    /// Send at most this many requests per minute to Open Food Facts, 0 for no limit
+    #[clap(long, value_name = "REQUESTS", default_value_t = off::REQUESTS_PER_MINUTE)]
+    rate_limit: u32,
+    #[clap(flatten)]
+    network: NetworkArgs,
+}
+
+impl OffArgs {
+    /// The client of the flags, with the `off-url` of the config file as the default.
+    pub fn client(self, config: &Config) -> io::Result<Client> {
+        let url = self
+            .off_url
+            .or_else(|| config.off_url.clone())
+            .unwrap_or_else(|| off::PRODUCTION.to_owned());
+        let agent = self.network.network(config).agent(&url)?;
+        Ok(Client::with_agent(agent, url)
+            .retries(self.retries)
+            .requests_per_minute(self.rate_limit))
+    }
+}
+
+/// Where the category of a looked up product comes from.
+#[derive(Copy, Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum CategorySource {
+    Flag,
+    OpenFoodFacts,
+    Default,
+}
+
+#[derive(Serialize)]
+struct JsonLookup<'a> {
+    barcode: &'a str,
+    product_name: Option<&'a str>,
+    category: String,
+    category_source: CategorySource,
+    off_grade: Option<&'a str>,
+    #[serde(flatten)]
+    result: JsonResult<'a>,
+}
+
+/// The barcode of a GTIN or GS1 Digital Link in the form Open Food Facts uses, see
+/// [`nutriscore::parse_barcode`].
+pub fn barcode(text: &str) -> io::Result<String> {
+    nutriscore::parse_barcode(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Fetches the product with a barcode from [`barcode`] from the cache or else from Open Food
+/// Facts.
+pub fn fetch(client: &Client, cache: &Cache, barcode: &str) -> io::Result<Product> {
+    // products of other instances are cached apart from the ones of the public one
+    let key = if client.base_url() == off::PRODUCTION {
+        barcode.to_owned()
+    } else {
+        format!("{}/{barcode}", client.base_url())
+    };
+    cache.get("off", &key, || {
+        tracing::info!(
+            url = client.base_url(),
+            "fetching the product from Open Food Facts"
+        );
+        client.product(barcode).map_err(|e| {
+            let not_found = matches!(e, off::Error::NotFound(_));
+            let e = io::Error::from(e);
+            if not_found {
+                tracing::warn!("the product is not on Open Food Facts");
+            } else {
+                tracing::error!(error = %e, "the request failed");
+            }
+            e
+        })
+    })
+}
+
+/// Prints the score of a product and returns its grade.
+#[tracing::instrument(skip(format, text, client, cache), fields(%algorithm))]
+pub fn run(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    client: &Client,
+    cache: &Cache,
+    barcode: &str,
+    category: Option<Category>,
+) -> io::Result<char> {
+    let barcode = self::barcode(barcode)?;
+    let product = fetch(client, cache, &barcode)?;
+    show(algorithm, format, text, &barcode, &product, category)
+}
+
+/// Prints the score of a product of Open Food Facts, in its suggested category unless one is
+/// given, and returns its grade.
+pub fn show(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    barcode: &str,
+    product: &Product,
+    category: Option<Category>,
+) -> io::Result<char> {
+    let (category, source) = category.map_or_else(
+        || {
+            Category::from_off_tags(&product.categories_tags)
+                .map_or((Category::Other, CategorySource::Default), |category| {
+                    (category, CategorySource::OpenFoodFacts)
+                })
+        },
+        |category| (category, CategorySource::Flag),
+    );
+    tracing::info!(%category, ?source, "found the product");
+    for name in product.missing_nutrients() {
+        let name = i18n::nutrient(name);
+        eprintln!("{}", tr_with("assuming-zero", &[("name", name.into())]));
+    }
+    let nutrition = product.nutrition();
+    let score = calculate_nutriscore(algorithm, category, &nutrition, product.fruits());
+    let letter = category.score_to_letter(algorithm, score.total, product.is_water());
+    let off_grade = product.nutriscore_grade.as_deref();
+    match format {
+        Format::Text if text.quiet => print_quiet(text, score.total, letter),
+        Format::Text => {
+            if let Some(name) = &product.name {
+                println!("{name}");
+            }
+            let source = match source {
+                CategorySource::Flag => "given with --category",
+                CategorySource::OpenFoodFacts => "from Open Food Facts, override with --category",
+                CategorySource::Default => {
+                    "no known Open Food Facts category, override with --category"
+                }
+            };
+            println!("Category: {category} ({source})\n");
+            draw_score(algorithm, text, &score);
+            if text.breakdown {
+                print_breakdown(&nutrition, &score);
+            }
+            println!(
+                "\n{}",
+                grade_style(letter)
+                    .apply_to(format!("Computed grade: {letter} (score {})", score.total))
+            );
+            println!(
+                "Open Food Facts grade: {}",
+                off_grade.map_or_else(|| "unknown".to_owned(), str::to_uppercase)
+            );
+        }
+        Format::Json => {
+            let result = JsonLookup {
+                barcode,
+                product_name: product.name.as_deref(),
+                category: category.to_string(),
+                category_source: source,
+                off_grade,
+                result: JsonResult {
+                    score: score.total,
+                    grade: letter,
+                    negative: &score.negative,
+                    positive: &score.positive,
+                },
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(letter)
+}
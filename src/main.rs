@@ -1,8 +1,3 @@
-#![allow(incomplete_features)]
-#![feature(generic_const_exprs)]
-#![feature(half_open_range_patterns)]
-#![feature(precise_pointer_size_matching)]
-#![feature(is_sorted)]
 #![warn(
     clippy::suspicious,
     clippy::pedantic,
@@ -11,102 +6,745 @@
     clippy::nursery,
     clippy::cargo
 )]
+#![allow(clippy::multiple_crate_versions)]
+
+mod auth;
+mod badge_file;
+mod batch;
+#[cfg(feature = "parquet")]
+mod batch_parquet;
+#[cfg(feature = "xlsx")]
+mod batch_xlsx;
+mod cache;
+mod check;
+mod ciqual;
+mod compare;
+mod config;
+mod cutoffs_file;
+mod database;
+mod diff;
+mod dump;
+mod error;
+mod explain;
+mod graphql;
+#[cfg(feature = "grpc")]
+mod grpc;
+#[cfg(feature = "gui")]
+mod gui;
+mod history;
+mod i18n;
+#[cfg(feature = "index")]
+mod index;
+mod jsonl;
+mod jurisdiction;
+mod log;
+mod lookup;
+mod metrics;
+mod network;
+mod pasted;
+mod product_file;
+mod qr;
+mod recipe_file;
+mod report;
+mod schema;
+mod search;
+mod server;
+mod template;
+mod tui;
+mod units;
+mod usda;
+mod watch;
+mod what_if;
+mod wizard;
 
-use crate::Category::{Cheese, Drinks, OilsAndFats};
 use bauxite::BoxBuilder;
-use clap::Parser;
-use dialoguer::{Confirm, Input, Select};
+use cache::{Cache, CacheArgs};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use dialoguer::{Input, Select};
+use error::NutriscoreError;
+use i18n::{tr, tr_with, Lang};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
-use std::borrow::Cow;
-use std::fmt::Display;
-use std::io;
-use std::str::FromStr;
-use strum::{EnumCount, EnumIter, EnumVariantNames, IntoEnumIterator, VariantNames};
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq, EnumVariantNames, EnumIter, EnumCount)]
-enum Category {
-    Drinks,
-    Cheese,
-    #[strum(to_string = "Oils And Fats")]
-    OilsAndFats,
-    Other,
-}
-
-impl Category {
-    const fn score_to_letter(self, score: isize, is_water: bool) -> char {
-        match self {
-            Drinks => match score {
-                _ if is_water => 'A',
-                ..=1 => 'B',
-                2..=5 => 'C',
-                6..=9 => 'D',
-                10.. => 'E',
-            },
-            _ => match score {
-                ..=-1 => 'A',
-                0..=2 => 'B',
-                3..=10 => 'C',
-                11..=18 => 'D',
-                19.. => 'E',
-            },
-        }
-    }
-
-    fn all_cutoffs(&self) -> [&[f32]; 7] {
-        let energy = if *self == Drinks {
-            &[
-                0.0, 30.0, 60.0, 90.0, 120.0, 150.0, 180.0, 210.0, 240.0, 270.0,
-            ]
-        } else {
-            &ENERGY_CUTOFFS
-        };
-        let fats = if *self == OilsAndFats {
-            &[10.0, 16.0, 22.0, 28.0, 34.0, 40.0, 46.0, 52.0, 58.0, 64.0] // percentages of saturated fats / all fats
-        } else {
-            &SATURATED_FATS_CUTOFF
-        };
-        let sugar = if *self == Drinks {
-            &[0.0, 1.5, 3.0, 4.5, 6.0, 7.5, 9.0, 10.5, 12.0, 13.5]
-        } else {
-            &SUGAR_CUTOFFS
-        };
-        let fruits = if *self == Drinks {
-            &[0.0, 40.0, 40.0, 60.0, 60.0, 80.0, 80.0, 80.0, 80.0, 80.0]
-        } else {
-            &FRUITS_CUTOFFS
-        };
-        [
-            energy,
-            fats,
-            sugar,
-            &PROTEIN_CUTOFFS,
-            &SODIUM_CUTOFF,
-            &FIBERS_CUTOFFS,
-            fruits,
-        ]
-    }
+use jurisdiction::Jurisdiction;
+use log::{LogFormat, LogLevel};
+use lookup::OffArgs;
+use network::NetworkArgs;
+use nutriscore::{
+    advise, calculate_score_range, fruits_percentage, keyhole, margins, traffic_lights,
+    uncertainty, Advice, Algorithm, Bound, Category, Component, CustomCutoffs, DrinkType,
+    EcoCategory, EcoProduct, EcoScore, Field, HealthStarRating, Ingredient, IngredientKind,
+    Keyhole, KeyholeGroup, Light, Margin, Nova, Nutrition, Origin, Packaging, Product, Score,
+    ScoreRange, ScoringModel, Severity, Tolerance, Tolerances, TrafficLight, TrafficLights,
+    Uncertainty, Unknown, WhoCategory, WhoEurope, ALCOHOL_LIMIT,
+};
+use search::SearchArgs;
+use serde::{Deserialize, Serialize};
+use server::ServeArgs;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use strum::{EnumCount, IntoEnumIterator};
+use units::Unit;
+
+#[derive(Debug, Parser)]
+#[clap(version, about)]
+#[allow(clippy::struct_excessive_bools)]
+struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+    #[clap(flatten)]
+    nutrition: NutritionArgs,
+    /// Version of the Nutri-Score algorithm, 2017 or 2023 [default: 2017]
+    #[clap(long, global = true)]
+    algorithm: Option<Algorithm>,
+    /// Score with the algorithm whose grade is valid in this market
+    #[clap(long, global = true, value_enum)]
+    jurisdiction: Option<Jurisdiction>,
+    /// The day the grade has to be valid on with --jurisdiction, like 2024-06-30 [default: today]
+    #[clap(long, global = true, requires = "jurisdiction", value_parser = jurisdiction::date)]
+    date: Option<String>,
+    /// The front-of-pack model to score with
+    #[clap(long, global = true, value_enum, default_value_t = Model::NutriScore)]
+    model: Model,
+    /// Score with the cutoff tables from this TOML file instead of the built-in ones
+    #[clap(long, global = true, value_name = "FILE")]
+    cutoffs: Option<PathBuf>,
+    /// Score with a category from the categories of the config file
+    #[clap(long, global = true, value_name = "NAME", conflicts_with = "cutoffs")]
+    custom_category: Option<String>,
+    /// The food category for --model who
+    #[clap(long, global = true, required_if_eq("model", "who"))]
+    who_category: Option<WhoCategory>,
+    /// How to print the result [default: text]
+    #[clap(long, global = true, value_enum)]
+    format: Option<Format>,
+    /// Print a table with the points of every nutrient
+    #[clap(long, global = true)]
+    breakdown: bool,
+    /// Read defaults from this file instead of ~/.config/nutriscore/config.toml
+    #[clap(long, global = true)]
+    config: Option<PathBuf>,
+    /// Language of the prompts and messages, defaults to the locale
+    #[clap(long, global = true, value_enum)]
+    lang: Option<Lang>,
+    /// Also save a badge with the grade as SVG or PNG
+    #[clap(long, global = true, value_enum)]
+    badge: Option<BadgeFormat>,
+    /// What the badge looks like
+    #[clap(long, global = true, value_enum, default_value_t = BadgeStyle::Logo, requires = "badge")]
+    badge_style: BadgeStyle,
+    /// Width of a PNG badge in pixels
+    #[clap(long, global = true, requires = "badge")]
+    badge_width: Option<u32>,
+    /// Where to save the badge, defaults to label.svg or label.png
+    #[clap(long, global = true, requires = "badge")]
+    out: Option<PathBuf>,
+    /// Print a report of the scoring in this format
+    #[clap(long, global = true, value_enum)]
+    report: Option<ReportFormat>,
+    /// Save the report in this file instead of printing it
+    #[clap(long, global = true, requires = "report")]
+    report_out: Option<PathBuf>,
+    /// Print a QR code of the result on stderr, to read it with a phone
+    #[clap(long, global = true)]
+    qr: bool,
+    /// Save the QR code in this file, as SVG if it ends in .svg and otherwise as PNG
+    #[clap(long, global = true, value_name = "FILE")]
+    qr_out: Option<PathBuf>,
+    /// Encode a link to the report on this server of serve instead, implies --qr without --qr-out
+    #[clap(long, global = true, value_name = "URL")]
+    qr_url: Option<String>,
+    /// Print the result with this Handlebars template instead
+    #[clap(long, global = true)]
+    template: Option<PathBuf>,
+    /// Also print the UK traffic lights for fat, saturates, sugars and salt
+    #[clap(long, global = true)]
+    traffic_lights: bool,
+    /// Also print the traffic lights for a portion of this size in g or ml
+    #[clap(long, global = true, requires = "traffic-lights", value_parser = portion)]
+    portion: Option<f32>,
+    /// Also check the criteria of the Nordic Keyhole label for this food group
+    #[clap(long, global = true, value_name = "GROUP")]
+    keyhole: Option<KeyholeGroup>,
+    /// Save the entered product in a product file to score it again later
+    #[clap(long)]
+    save: Option<PathBuf>,
+    /// Exit with 0 for grade A or B, 1 for C and 2 for D or E, or the exit-codes of the config
+    #[clap(long, global = true)]
+    exit_code_by_grade: bool,
+    /// Print only the grade, like B, to use it in shell scripts
+    #[clap(long, global = true)]
+    quiet: bool,
+    /// With --quiet, print the score and the grade, like 2;B
+    #[clap(long, global = true, requires = "quiet")]
+    with_score: bool,
+    /// Print plain text without colors, progress bars and boxes
+    #[clap(long, global = true)]
+    plain: bool,
+    /// Describe the result in sentences, for screen readers
+    #[clap(long, global = true)]
+    accessible: bool,
+    /// Explain every step with the rule of the official algorithm that was applied
+    #[clap(long, global = true)]
+    explain: bool,
+    /// Show how far every nutrient is from the cutoffs below and above its value
+    #[clap(long, global = true)]
+    sensitivity: bool,
+    /// Also estimate the grades the product can get within the tolerances of its values
+    #[clap(long, global = true)]
+    uncertainty: bool,
+    /// A tolerance for --uncertainty instead of the EU one, like sugar=1.5g or salt=10%
+    #[clap(
+        long,
+        global = true,
+        value_name = "NUTRIENT=AMOUNT",
+        requires = "uncertainty",
+        use_value_delimiter = true,
+        value_parser = tolerance
+    )]
+    tolerance: Vec<(&'static str, Tolerance)>,
+    /// How many products with random values within the tolerances --uncertainty scores
+    #[clap(long, global = true, default_value_t = 10_000, requires = "uncertainty", value_parser = clap::value_parser!(u32).range(1..))]
+    samples: u32,
+    /// The most verbose log messages that are written to stderr
+    #[clap(long, global = true, value_enum, default_value_t = LogLevel::Warn)]
+    log_level: LogLevel,
+    /// How log messages are written
+    #[clap(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum Model {
+    /// The Nutri-Score with the letters A to E, in the version of --algorithm
+    NutriScore,
+    /// The Health Star Rating of Australia and New Zealand, from 0.5 to 5 stars
+    Hsr,
+    /// The WHO Europe nutrient profile model, whether marketing to children is permitted
+    Who,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum ReportFormat {
+    Markdown,
+    Html,
+    Pdf,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum BadgeFormat {
+    Svg,
+    Png,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum BadgeStyle {
+    /// The official logo with all five letters
+    Logo,
+    /// A small "Nutri-Score: B" badge like on shields.io
+    Shield,
+}
+
+/// The badge to save after scoring.
 #[derive(Debug)]
-struct Nutrition {
-    energy: f32,
-    fat: f32,
-    saturated_fats: f32,
-    sugar: f32,
-    proteins: f32,
-    salt: f32,
-    fibers: f32,
+struct Badge {
+    format: BadgeFormat,
+    style: BadgeStyle,
+    width: Option<u32>,
+    out: PathBuf,
 }
 
-#[derive(Debug, Parser)]
+/// The report to write after scoring, to stdout if `out` is not set.
+///
+/// PDF reports are never printed and go to report.pdf by default.
+#[derive(Debug)]
+struct Report {
+    format: ReportFormat,
+    out: Option<PathBuf>,
+}
+
+/// What to do with a result besides printing it.
+#[derive(Debug)]
+struct Outputs {
+    /// Another model than the Nutri-Score only prints its result.
+    model: Model,
+    /// The category of the WHO Europe model.
+    who_category: Option<WhoCategory>,
+    /// Replaces the cutoff tables of the Nutri-Score.
+    cutoffs: Option<CustomCutoffs>,
+    /// Replaces the category of the product with one of the config file.
+    custom_category: Option<config::CustomCategory>,
+    history: bool,
+    badge: Option<Badge>,
+    report: Option<Report>,
+    qr: Option<qr::Qr>,
+    /// Prints the result instead of the text or JSON.
+    template: Option<PathBuf>,
+    /// Prints the UK traffic lights next to the result.
+    traffic_lights: bool,
+    /// The portion size for the traffic lights per portion.
+    portion: Option<f32>,
+    /// Prints whether the product can have the Keyhole label of this group.
+    keyhole: Option<KeyholeGroup>,
+    /// Estimates the grades within these tolerances, see [`uncertainty`].
+    tolerances: Option<Tolerances>,
+    /// The number of random products for the estimate.
+    samples: u32,
+}
+
+impl Outputs {
+    fn new(args: &Args, algorithm: Algorithm, config: &config::Config) -> io::Result<Self> {
+        Ok(Self {
+            model: args.model,
+            who_category: args.who_category,
+            cutoffs: args
+                .cutoffs
+                .as_deref()
+                .map(|path| cutoffs_file::read(path, algorithm))
+                .transpose()?,
+            custom_category: args
+                .custom_category
+                .as_deref()
+                .map(|name| config::CustomCategory::new(config, name, algorithm))
+                .transpose()?,
+            history: config.history.unwrap_or(true),
+            badge: args.badge.map(|format| Badge {
+                format,
+                style: args.badge_style,
+                width: args.badge_width,
+                out: args.out.clone().unwrap_or_else(|| match format {
+                    BadgeFormat::Svg => PathBuf::from("label.svg"),
+                    BadgeFormat::Png => PathBuf::from("label.png"),
+                }),
+            }),
+            report: args.report.map(|format| Report {
+                format,
+                out: args
+                    .report_out
+                    .clone()
+                    .or_else(|| (format == ReportFormat::Pdf).then(|| PathBuf::from("report.pdf"))),
+            }),
+            qr: (args.qr || args.qr_out.is_some() || args.qr_url.is_some()).then(|| qr::Qr {
+                terminal: args.qr || args.qr_out.is_none(),
+                out: args.qr_out.clone(),
+                url: args.qr_url.clone(),
+            }),
+            template: args.template.clone(),
+            traffic_lights: args.traffic_lights,
+            portion: args.portion,
+            keyhole: args.keyhole,
+            tolerances: args.uncertainty.then(|| tolerances(&args.tolerance)),
+            samples: args.samples,
+        })
+    }
+
+    /// Whether the Nutri-Score is calculated with other tables or rules than the built-in ones.
+    const fn custom_scoring(&self) -> bool {
+        self.cutoffs.is_some() || self.custom_category.is_some()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    /// Progress bars and a boxed letter
+    Text,
+    /// A JSON object with the score, the letter and the points of every nutrient
+    Json,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum EnergyUnit {
+    Kj,
+    Kcal,
+}
+
+/// How the text output is drawn.
+#[derive(Copy, Clone, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+struct TextOptions {
+    breakdown: bool,
+    progress_bars: bool,
+    /// Only the grade is printed, see [`print_quiet`].
+    quiet: bool,
+    with_score: bool,
+    /// The grade is printed as text instead of in a box.
+    plain: bool,
+    /// Every component and the grade are described in a sentence, see [`describe`].
+    accessible: bool,
+    /// The result is followed by the rules that were applied, see [`explain::print`].
+    explain: bool,
+    /// The result is followed by the distances to the cutoffs, see [`print_margins`].
+    sensitivity: bool,
+}
+
+impl TextOptions {
+    fn new(args: &Args, config: &config::Config) -> Self {
+        let plain = args.plain || args.accessible;
+        if plain {
+            console::set_colors_enabled(false);
+            console::set_colors_enabled_stderr(false);
+        }
+        Self {
+            breakdown: args.breakdown,
+            // progress bars are not drawn at all if stdout is redirected
+            progress_bars: !plain
+                && console::Term::stdout().is_term()
+                && config.progress_bars.unwrap_or(true),
+            quiet: args.quiet,
+            with_score: args.with_score,
+            plain,
+            accessible: args.accessible,
+            explain: args.explain,
+            sensitivity: args.sensitivity,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    score: isize,
+    grade: char,
+    negative: &'a [Component],
+    positive: &'a [Component],
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Score a product file
+    Score {
+        /// TOML file with the product
+        #[clap(required_unless_present = "stdin")]
+        file: Option<PathBuf>,
+        /// Read the product as JSON from stdin instead
+        #[clap(long, conflicts_with = "file")]
+        stdin: bool,
+    },
+    /// Score a product file again whenever it is saved
+    Watch {
+        /// TOML file with the product
+        file: PathBuf,
+    },
+    /// Score every product of a CSV, Parquet or Excel file
+    Batch {
+        /// CSV, Parquet or Excel file with one product per row
+        input: PathBuf,
+        /// Where to write the file with the added score and grade columns, defaults to stdout
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        /// The columns of the output in this order instead, like barcode,grade
+        #[clap(long, use_value_delimiter = true)]
+        columns: Vec<String>,
+        /// The sheet of an Excel workbook with the products, by default the first one
+        #[clap(long)]
+        sheet: Option<String>,
+    },
+    /// Score every product of the Open Food Facts CSV export in parallel
+    Dump {
+        /// The export, en.openfoodfacts.org.products.csv
+        input: PathBuf,
+        /// Where to write the CSV with the scores and grades, defaults to stdout
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Read one JSON product per line from stdin and print one JSON result per line
+    Jsonl,
+    /// Show the smallest changes of single nutrients that lead to a better grade
+    Advise {
+        #[clap(flatten)]
+        nutrition: Box<NutritionArgs>,
+    },
+    /// Enter a product in a full-screen form that shows the breakdown and the grade while typing
+    Tui {
+        /// Start with the product of this file and save it there with Ctrl+S
+        file: Option<PathBuf>,
+    },
+    /// Open a window with fields for all values, the breakdown and the grade (needs --features gui)
+    Gui,
+    /// Score several product files and show them side by side
+    Compare {
+        /// TOML files with one product each
+        #[clap(required = true, min_values = 2)]
+        files: Vec<PathBuf>,
+    },
+    /// Fail if any product of the files has a worse grade than the minimum
+    Check {
+        /// The worst grade that passes
+        #[clap(long, value_parser = check::grade)]
+        min_grade: char,
+        /// Product files, or CSV files with one product per row like for batch
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Show how the values, points and grade of two product files differ
+    Diff {
+        /// The product file before the change
+        old: PathBuf,
+        /// The product file after the change
+        new: PathBuf,
+    },
+    /// Serve a JSON API with a POST /score endpoint
+    Serve(ServeArgs),
+    /// Score a product from Open Food Facts
+    Lookup {
+        /// The barcode of the product, a GTIN like 3017620422003 or a GS1 Digital Link URL
+        barcode: String,
+        /// Use this category instead of the one suggested from the Open Food Facts categories
+        #[clap(long)]
+        category: Option<Category>,
+        #[clap(flatten)]
+        off: OffArgs,
+        #[clap(flatten)]
+        cache: CacheArgs,
+    },
+    /// Find a product by name or brand among the looked up ones or an export and score it
+    Search(SearchArgs),
+    /// Score a generic food from the food database of the USDA
+    LookupUsda {
+        /// What to search for, like "apples raw"
+        query: String,
+        /// The category of the food
+        #[clap(long, default_value = "other")]
+        category: Category,
+        /// Percentage of fruits and vegetables, which the USDA database does not provide
+        #[clap(long, default_value_t = 0.0)]
+        fruits: f32,
+        /// Key for the USDA API, defaults to the key in the environment or a rate limited demo key
+        #[clap(long)]
+        api_key: Option<String>,
+        #[clap(flatten)]
+        cache: CacheArgs,
+        #[clap(flatten)]
+        network: NetworkArgs,
+    },
+    /// List past results, or print one of them again
+    History {
+        /// Print the result with this number again
+        #[clap(long)]
+        show: Option<usize>,
+    },
+    /// Score a product made from the ingredients of a recipe file
+    Recipe {
+        /// TOML file with the ingredients, their masses and nutritional values
+        file: PathBuf,
+        /// The database file for ingredients that name a product
+        #[clap(long)]
+        database: Option<PathBuf>,
+        /// The CIQUAL table as CSV, defaults to ~/.local/share/nutriscore/ciqual.csv
+        #[clap(long)]
+        ciqual: Option<PathBuf>,
+    },
+    /// Print the JSON Schema of the products read by jsonl and serve
+    Schema {
+        /// Print the description of the HTTP API of serve for client generators instead
+        #[clap(long)]
+        openapi: bool,
+        /// Print the GraphQL schema of serve instead
+        #[clap(long, conflicts_with = "openapi")]
+        graphql: bool,
+    },
+    /// Print a man page in roff format
+    Manpage,
+    /// Manage a local database of products
+    Product {
+        #[clap(subcommand)]
+        command: ProductCommand,
+        /// The database file, defaults to ~/.local/share/nutriscore/products.db
+        #[clap(long)]
+        database: Option<PathBuf>,
+    },
+    /// Manage the cache of the lookups in ~/.cache/nutriscore
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommand,
+    },
+    /// Search the Open Food Facts export with a full-text index (needs --features index)
+    Index {
+        #[clap(subcommand)]
+        command: IndexCommand,
+        /// The directory of the index, defaults to ~/.local/share/nutriscore/index
+        #[clap(long = "index", value_name = "DIR")]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum IndexCommand {
+    /// Index the products of the export, replacing the index
+    Build {
+        /// The export, en.openfoodfacts.org.products.csv
+        input: PathBuf,
+    },
+    /// Find products by name, brand or category, like "nutella categories:spreads", to score one
+    Search {
+        query: String,
+        /// Show at most this many matches
+        #[clap(long, default_value_t = 10)]
+        limit: usize,
+        /// Score the picked product in this category instead of the suggested one
+        #[clap(long)]
+        category: Option<Category>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommand {
+    /// Delete all cached products and foods
+    Clear,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProductCommand {
+    /// Save a product, from a product file or entered like for scoring
+    Add {
+        /// The name of the product
+        name: String,
+        /// Read the product from this TOML file
+        #[clap(long)]
+        file: Option<PathBuf>,
+        /// Replace an existing product with the same name
+        #[clap(long)]
+        replace: bool,
+        #[clap(flatten)]
+        nutrition: Box<NutritionArgs>,
+    },
+    /// List all products with their last result
+    List,
+    /// Show a product and its past results
+    Show {
+        /// The name of the product
+        name: String,
+    },
+    /// Ask for the values of a product again, with the current ones prefilled, and score it
+    Edit {
+        /// The name of the product
+        name: String,
+    },
+    /// Score a product and save the result
+    Score {
+        /// The name of the product
+        name: String,
+    },
+    /// Remove a product and its results
+    Remove {
+        /// The name of the product
+        name: String,
+    },
+}
+
+#[derive(Clone, Debug, Default, Parser)]
+#[allow(clippy::struct_excessive_bools)]
 struct NutritionArgs {
-    energy: Option<f32>,
-    fat: Option<f32>,
-    saturated_fats: Option<f32>,
-    sugar: Option<f32>,
-    proteins: Option<f32>,
-    salt: Option<f32>,
-    fibers: Option<f32>,
+    /// Energy in kJ per 100g or 100ml, or in kcal with --energy-unit kcal, or with a unit like 120kcal
+    energy: Option<String>,
+    /// Fat in g per 100g or 100ml
+    fat: Option<String>,
+    /// Saturated fats in g per 100g or 100ml
+    saturated_fats: Option<String>,
+    /// Sugar in g per 100g or 100ml
+    sugar: Option<String>,
+    /// Proteins in g per 100g or 100ml
+    proteins: Option<String>,
+    /// Salt in g per 100g or 100ml, or with a unit like 600mg
+    salt: Option<String>,
+    /// Fibers in g per 100g or 100ml
+    fibers: Option<String>,
+    /// Carbohydrates in g per 100g or 100ml, only used to check the other values
+    #[clap(long)]
+    carbohydrates: Option<String>,
+    /// The values are per serving of this size in g or ml instead of per 100g or 100ml
+    #[clap(long)]
+    serving_size: Option<f32>,
+    /// The unit of the energy [default: kj]
+    #[clap(long, value_enum)]
+    energy_unit: Option<EnergyUnit>,
+    /// Sodium in mg per 100g or 100ml instead of the salt, or with a unit like 0.4g
+    #[clap(long, value_name = "MG", conflicts_with = "salt")]
+    sodium: Option<String>,
+    /// The food category, e.g. drinks, cheese or oils-and-fats
+    #[clap(long)]
+    category: Option<Category>,
+    /// The product is milk, a drinkable yoghurt or a plant milk
+    #[clap(long, conflicts_with = "category")]
+    milk: bool,
+    /// Percentage of fruits and vegetables
+    #[clap(long)]
+    fruits: Option<String>,
+    /// Calculate the percentage of fruits and vegetables from the ingredients of a recipe
+    #[clap(long, conflicts_with_all = &["fruits", "non-interactive"])]
+    fruits_wizard: bool,
+    /// The drink is water, like --drink water
+    #[clap(long)]
+    water: bool,
+    /// The kind of drink: water, mineral-water, sparkling-water, flavored-water or other
+    #[clap(long, conflicts_with = "water")]
+    drink: Option<DrinkType>,
+    /// Alcohol by volume of a drink in %, above 1.2% the Nutri-Score does not apply
+    #[clap(long)]
+    alcohol: Option<String>,
+    /// The drink contains non-nutritive sweeteners (2023 algorithm only)
+    #[clap(long)]
+    sweeteners: bool,
+    /// Fail instead of asking for missing values
+    #[clap(long)]
+    non_interactive: bool,
+    /// Read the values from a nutrition table copied to the clipboard, asking only for the rest
+    #[clap(long)]
+    from_clipboard: bool,
+    /// Read the values from the text of a nutrition table in this file, or - for stdin
+    #[clap(long, value_name = "FILE", conflicts_with = "from-clipboard")]
+    from_label: Option<PathBuf>,
+    /// Change single values after scoring and see the new result
+    #[clap(long, conflicts_with = "non-interactive")]
+    what_if: bool,
+    /// Save the answers in this file after every question
+    #[clap(long, conflicts_with = "non-interactive")]
+    save_session: Option<PathBuf>,
+    /// Continue with the answers saved by quit or --save-session, by default of the last quit
+    #[clap(long, conflicts_with = "non-interactive")]
+    #[allow(clippy::option_option)]
+    resume: Option<Option<PathBuf>>,
+    /// Do not offer the answers of the last run for editing
+    #[clap(long)]
+    no_prefill: bool,
+    /// The name of the product, shown in reports
+    #[clap(long = "name")]
+    product_name: Option<String>,
+    /// The ingredient list from the package, to estimate the NOVA group
+    #[clap(long)]
+    ingredients: Option<String>,
+    /// Also calculate the Eco-Score, asking for what it needs
+    #[clap(long)]
+    eco: bool,
+    /// The kind of food for the Eco-Score, e.g. beef, cheese or vegetables
+    #[clap(long)]
+    eco_category: Option<EcoCategory>,
+    /// What the product is packed in, for the Eco-Score, e.g. glass or plastic
+    #[clap(long)]
+    packaging: Option<Packaging>,
+    /// Where the ingredients come from, for the Eco-Score: local, europe, world or unknown
+    #[clap(long)]
+    origin: Option<Origin>,
+    /// Labels of the production, for the Eco-Score, e.g. organic,msc or "" for none
+    #[clap(long)]
+    labels: Option<String>,
+    /// The product contains palm oil that is not certified as sustainable, for the Eco-Score
+    #[clap(long)]
+    palm_oil: bool,
+}
+
+impl NutritionArgs {
+    /// Whether the Eco-Score is wanted, with --eco or any of its values.
+    const fn wants_eco(&self) -> bool {
+        self.eco
+            || self.eco_category.is_some()
+            || self.packaging.is_some()
+            || self.origin.is_some()
+            || self.labels.is_some()
+            || self.palm_oil
+    }
 }
 
 #[derive(Parser)]
@@ -114,153 +752,1400 @@ struct X {
     x: Option<f32>,
 }
 
-impl Nutrition {
-    fn saturated_fat_value(&self, cat: Category) -> f32 {
-        if cat == OilsAndFats {
-            self.saturated_fats / self.fat * 100.0
-        } else {
-            self.saturated_fats
-        }
-    }
-
-    fn sodium(&self) -> f32 {
-        self.salt / 2.5
-    }
-}
-
-// negative
-static ENERGY_CUTOFFS: [f32; 10] = [
-    335.0, 670.0, 1005.0, 1340.0, 1675.0, 2010.0, 2345.0, 2680.0, 3015.0, 3350.0,
-];
-static SUGAR_CUTOFFS: [f32; 10] = [4.5, 9.0, 13.5, 18.0, 22.5, 27.0, 31.0, 36.0, 40.0, 45.0];
-static SATURATED_FATS_CUTOFF: [f32; 10] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
-static SODIUM_CUTOFF: [f32; 10] = [
-    90.0, 180.0, 270.0, 360.0, 450.0, 540.0, 630.0, 720.0, 810.0, 900.0,
-];
-
-// positive
-static FRUITS_CUTOFFS: [f32; 10] = [
-    40.0,
-    60.0,
-    80.0,
-    80.0,
-    80.0,
-    f32::INFINITY,
-    f32::INFINITY,
-    f32::INFINITY,
-    f32::INFINITY,
-    f32::INFINITY,
-];
-static FIBERS_CUTOFFS: [f32; 5] = [0.8, 1.9, 2.8, 3.7, 4.7];
-static PROTEIN_CUTOFFS: [f32; 5] = [1.6, 3.2, 4.8, 6.4, 8.0];
-
-fn main() -> io::Result<()> {
-    let args: NutritionArgs = NutritionArgs::parse();
-    let nutrition = Nutrition {
-        energy: args.energy.unwrap_or_else(|| ask("Energy (kJ)")),
-        fat: args.fat.unwrap_or_else(|| ask("Fats")),
-        saturated_fats: args.saturated_fats.unwrap_or_else(|| ask("Saturated fats")),
-        sugar: args.sugar.unwrap_or_else(|| ask("Sugar")),
-        proteins: args.proteins.unwrap_or_else(|| ask("Protein")),
-        salt: args.salt.unwrap_or_else(|| ask("Salt")),
-        fibers: args.fibers.unwrap_or_else(|| ask("Fibers")),
+/// Sets the language of `--lang`, the config file or the locale, in this order.
+fn set_lang(args: &Args, config: &config::Config) {
+    let lang = args
+        .lang
+        .or_else(|| config.locale.as_deref().and_then(Lang::from_locale))
+        .or_else(Lang::from_env)
+        .unwrap_or_default();
+    i18n::init(
+        lang,
+        config.decimal_comma.unwrap_or_else(|| lang.decimal_comma()),
+    );
+}
+
+/// Runs a subcommand, returns the grade for `--exit-code-by-grade` if there is one.
+fn run_command(
+    command: Command,
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    config: &config::Config,
+    outputs: &Outputs,
+) -> io::Result<Option<char>> {
+    match command {
+        Command::Score { file, .. } => {
+            let product = match file {
+                Some(file) => product_file::read(&file)?,
+                None => product_file::read_stdin()?,
+            };
+            show(algorithm, format, text, product, false, outputs)
+        }
+        Command::Watch { file } => {
+            watch::run(algorithm, format, text, &file, outputs).map(|()| None)
+        }
+        Command::Batch {
+            input,
+            output,
+            columns,
+            sheet,
+        } => batch::run(
+            algorithm,
+            &input,
+            output.as_deref(),
+            sheet.as_deref(),
+            &columns,
+        )
+        .map(|()| None),
+        Command::Jsonl => jsonl::run(algorithm).map(|()| None),
+        Command::Dump { input, output } => {
+            dump::run(algorithm, &input, output.as_deref()).map(|()| None)
+        }
+        Command::Lookup {
+            barcode,
+            category,
+            off,
+            cache,
+        } => {
+            let client = off.client(config)?;
+            let cache = Cache::new(&cache, config.cache_ttl.as_deref())?;
+            lookup::run(algorithm, format, text, &client, &cache, &barcode, category).map(Some)
+        }
+        Command::Serve(args) => server::run(algorithm, config, args).map(|()| None),
+        Command::Check { min_grade, files } => {
+            check::run(algorithm, format, min_grade, &files).map(|()| None)
+        }
+        Command::Diff { old, new } => diff::run(algorithm, format, &old, &new).map(|()| None),
+        Command::Tui { file } => tui::run(algorithm, text, file.as_deref()),
+        Command::Gui => gui(algorithm).map(|()| None),
+        Command::Compare { files } => compare::run(algorithm, format, &files).map(|()| None),
+        Command::Product {
+            mut command,
+            database,
+        } => {
+            if let ProductCommand::Add { nutrition, .. } = &mut command {
+                config.apply(nutrition);
+            }
+            database::run(algorithm, format, text, database.as_deref(), command)
+        }
+        Command::Search(args) => search::run(algorithm, format, text, &args),
+        Command::Index { command, dir } => index(algorithm, format, text, dir.as_deref(), command),
+        Command::LookupUsda {
+            query,
+            category,
+            fruits,
+            api_key,
+            cache,
+            network,
+        } => {
+            let cache = Cache::new(&cache, config.cache_ttl.as_deref())?;
+            let foods = usda::search(&query, api_key, &cache, &network.network(config))?;
+            usda::run(algorithm, format, text, &foods, category, fruits).map(Some)
+        }
+        Command::Cache {
+            command: CacheCommand::Clear,
+        } => cache::clear().map(|()| None),
+        Command::History { show } => history::run(format, text, show).map(|()| None),
+        Command::Recipe {
+            file,
+            database,
+            ciqual,
+        } => recipe_file::run(
+            algorithm,
+            format,
+            text,
+            &file,
+            database.as_deref(),
+            ciqual.as_deref(),
+        )
+        .map(Some),
+        Command::Schema { graphql: true, .. } => {
+            println!("{}", graphql::schema().as_sdl());
+            Ok(None)
+        }
+        Command::Schema { openapi, .. } => schema::run(openapi).map(|()| None),
+        Command::Manpage => clap_mangen::Man::new(Args::command())
+            .render(&mut io::stdout())
+            .map(|()| None),
+        Command::Advise { mut nutrition } => {
+            config.apply(&mut nutrition);
+            let product = read_product(algorithm, &nutrition)?;
+            print_advice(algorithm, format, &product).map(|()| None)
+        }
+    }
+}
+
+fn main() -> miette::Result<()> {
+    run().map_err(|e| NutriscoreError::from(e).into())
+}
+
+/// Runs the command, `main` shows its error as a diagnostic.
+fn run() -> io::Result<()> {
+    let mut args = Args::parse();
+    log::init(
+        args.log_level,
+        args.log_format,
+        !args.plain && console::colors_enabled_stderr(),
+    );
+    let config = config::load(args.config.as_deref())?;
+    set_lang(&args, &config);
+    let requested = args.algorithm.or(config.algorithm);
+    let algorithm = match args.jurisdiction.or(config.jurisdiction) {
+        Some(jurisdiction) => {
+            let date = args.date.clone().unwrap_or_else(jurisdiction::today);
+            jurisdiction.algorithm(requested, &date)?
+        }
+        None => requested.unwrap_or_default(),
+    };
+    let format = if args.quiet {
+        Format::Text
+    } else {
+        args.format.or(config.format).unwrap_or(Format::Text)
+    };
+    let text = TextOptions::new(&args, &config);
+    let outputs = Outputs::new(&args, algorithm, &config)?;
+    let grade = if let Some(command) = args.command {
+        run_command(command, algorithm, format, text, &config, &outputs)?
+    } else {
+        config.apply(&mut args.nutrition);
+        interactive(
+            algorithm,
+            format,
+            text,
+            &args.nutrition,
+            args.save.as_deref(),
+            &outputs,
+        )?
+    };
+    if let Some(letter) = grade.filter(|_| args.exit_code_by_grade) {
+        io::stdout().flush()?;
+        std::process::exit(exit_code(&config.exit_codes, letter));
+    }
+    Ok(())
+}
+
+/// The exit code for a grade with `--exit-code-by-grade`.
+fn exit_code(codes: &BTreeMap<char, i32>, letter: char) -> i32 {
+    codes.get(&letter).copied().unwrap_or(match letter {
+        'A' | 'B' => 0,
+        'C' => 1,
+        _ => 2,
+    })
+}
+
+fn interactive(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    args: &NutritionArgs,
+    save: Option<&Path>,
+    outputs: &Outputs,
+) -> io::Result<Option<char>> {
+    let allow_unknown =
+        !args.what_if && outputs.model == Model::NutriScore && !outputs.custom_scoring();
+    let (product, unknown) = read_product_with_unknown(algorithm, args, allow_unknown)?;
+    if let Some(path) = save {
+        product_file::write(path, &product)?;
+    }
+    if !product.is_applicable() {
+        return print_not_applicable(format, text, &product).map(|()| None);
+    }
+    if !unknown.is_empty() {
+        let range = calculate_score_range(
+            algorithm,
+            product.category,
+            &product.nutrition,
+            product.fruits,
+            product.is_water(),
+            &unknown,
+        );
+        print_range(algorithm, format, text, &product, &unknown, &range)?;
+        return Ok(Some(product.letter(algorithm, &range.worst)));
+    }
+    show(algorithm, format, text, product, args.what_if, outputs)
+}
+
+/// Prints the result of a product and writes the outputs, returns the grade of the Nutri-Score.
+fn show(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    product: Product,
+    what_if: bool,
+    outputs: &Outputs,
+) -> io::Result<Option<char>> {
+    if !product.is_applicable() {
+        return print_not_applicable(format, text, &product).map(|()| None);
+    }
+    let mut extras = Extras::new(&product, outputs);
+    if outputs.custom_scoring() && outputs.model != Model::NutriScore {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--cutoffs and --custom-category only work with the Nutri-Score",
+        ));
+    }
+    let model: Option<Box<dyn ScoringModel>> = match outputs.model {
+        Model::NutriScore => match (&outputs.cutoffs, &outputs.custom_category) {
+            (Some(cutoffs), _) => Some(Box::new(cutoffs.clone())),
+            (None, Some(category)) => Some(Box::new(category.clone())),
+            (None, None) => None,
+        },
+        Model::Hsr => Some(Box::new(HealthStarRating)),
+        Model::Who => {
+            let category = outputs
+                .who_category
+                .ok_or_else(|| missing("--who-category"))?;
+            Some(Box::new(WhoEurope { category }))
+        }
+    };
+    if let Some(model) = model {
+        return show_model(
+            model.as_ref(),
+            format,
+            text,
+            &product,
+            what_if,
+            outputs,
+            extras,
+        )
+        .map(|()| None);
+    }
+    let score = product.score(algorithm);
+    let letter = product.letter(algorithm, &score);
+    if text.sensitivity {
+        extras.margins = Some(margins(algorithm, product.category, &score));
+    }
+    if let Some(tolerances) = &outputs.tolerances {
+        let samples = outputs.samples as usize;
+        extras.uncertainty = Some(uncertainty(algorithm, &product, tolerances, samples));
+    }
+    if outputs.history {
+        if let Err(e) = history::record(algorithm, &product, &score, letter) {
+            eprintln!("Could not save the result in the history: {e}");
+        }
+    }
+    if let Some(badge) = &outputs.badge {
+        badge_file::write(badge, letter)?;
+    }
+    if let Some(qr) = &outputs.qr {
+        qr::write(qr, algorithm, &product, &score, letter)?;
+    }
+    if let Some(r) = &outputs.report {
+        let report: Vec<u8> = match r.format {
+            ReportFormat::Markdown => report::markdown(algorithm, &product, &score, letter).into(),
+            ReportFormat::Html => report::html(algorithm, &product, &score, letter).into(),
+            ReportFormat::Pdf => report::pdf(algorithm, &product, &score, letter)?,
+        };
+        let Some(out) = &r.out else {
+            io::stdout().write_all(&report)?;
+            return Ok(Some(letter));
+        };
+        fs::write(out, report)?;
+    }
+    if let Some(template) = &outputs.template {
+        print!(
+            "{}",
+            template::render(template, algorithm, &product, &score, letter)?
+        );
+        return Ok(Some(letter));
+    }
+    match format {
+        Format::Text => {
+            print_text(algorithm, text, &product.nutrition, &score, letter);
+            if !text.quiet {
+                extras.print();
+            }
+            if text.explain && !text.quiet {
+                explain::print(algorithm, &product, &score, letter);
+            }
+            if what_if && !text.quiet {
+                what_if::run(algorithm, text, product)?;
+            }
+        }
+        Format::Json => {
+            let result = JsonResult {
+                score: score.total,
+                grade: letter,
+                negative: &score.negative,
+                positive: &score.positive,
+            };
+            let result = JsonWithExtras { result, extras };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+
+    Ok(Some(letter))
+}
+
+/// What is shown next to the result of any model.
+#[derive(Serialize)]
+struct Extras {
+    /// Only for the Nutri-Score, see [`margins`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    margins: Option<Vec<Margin>>,
+    /// Only for the Nutri-Score, see [`uncertainty`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uncertainty: Option<Uncertainty>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    traffic_lights: Option<TrafficLights>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keyhole: Option<Keyhole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nova: Option<Nova>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eco_score: Option<EcoScore>,
+}
+
+impl Extras {
+    fn new(product: &Product, outputs: &Outputs) -> Self {
+        Self {
+            margins: None,
+            uncertainty: None,
+            traffic_lights: outputs
+                .traffic_lights
+                .then(|| traffic_lights(product.category, &product.nutrition, outputs.portion)),
+            keyhole: outputs
+                .keyhole
+                .map(|group| keyhole(group, &product.nutrition)),
+            nova: product.nova(),
+            eco_score: product.eco.as_ref().map(EcoProduct::score),
+        }
+    }
+
+    fn print(&self) {
+        if let Some(margins) = &self.margins {
+            print_margins(margins);
+        }
+        if let Some(uncertainty) = &self.uncertainty {
+            print_uncertainty(uncertainty);
+        }
+        if let Some(lights) = &self.traffic_lights {
+            print_traffic_lights(lights);
+        }
+        if let Some(keyhole) = &self.keyhole {
+            print_keyhole(keyhole);
+        }
+        if let Some(nova) = &self.nova {
+            print_nova(nova);
+        }
+        if let Some(score) = &self.eco_score {
+            print_eco_score(score);
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonWithExtras<'a> {
+    #[serde(flatten)]
+    result: JsonResult<'a>,
+    #[serde(flatten)]
+    extras: Extras,
+}
+
+#[derive(Serialize)]
+struct JsonModelResult<'a> {
+    model: String,
+    score: isize,
+    grade: String,
+    negative: &'a [Component],
+    positive: &'a [Component],
+    #[serde(flatten)]
+    extras: Extras,
+}
+
+/// Prints the result of another model than the Nutri-Score, which has no badge, report, template,
+/// history or what-if mode.
+fn show_model(
+    model: &dyn ScoringModel,
+    format: Format,
+    text: TextOptions,
+    product: &Product,
+    what_if: bool,
+    outputs: &Outputs,
+    extras: Extras,
+) -> io::Result<()> {
+    if what_if
+        || outputs.badge.is_some()
+        || outputs.report.is_some()
+        || outputs.qr.is_some()
+        || outputs.template.is_some()
+        || text.sensitivity
+        || outputs.tolerances.is_some()
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--what-if, --badge, --report, --qr, --template, --sensitivity and --uncertainty only work with the built-in Nutri-Score",
+        ));
+    }
+    let score = model.score(product);
+    let grade = model.grade(product, &score);
+    match format {
+        Format::Text if text.quiet => print_quiet(text, score.total, &grade),
+        Format::Text => {
+            draw_components(text, &score);
+            if text.breakdown {
+                print_breakdown(&product.nutrition, &score);
+            }
+            let result = format!("{}: {grade}", model.name());
+            if text.accessible {
+                let args = [("result", result.into()), ("score", score.total.into())];
+                println!("\n{}", tr_with("accessible-model", &args));
+            } else {
+                println!("\n{}", console::style(result).bold());
+            }
+            extras.print();
+        }
+        Format::Json => {
+            let result = JsonModelResult {
+                model: model.name(),
+                score: score.total,
+                grade,
+                negative: &score.negative,
+                positive: &score.positive,
+                extras,
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonNotApplicable {
+    applicable: bool,
+    alcohol: Option<f32>,
+    reason: String,
+}
+
+/// Explains why a product has no grade, see [`Product::is_applicable`].
+fn print_not_applicable(format: Format, text: TextOptions, product: &Product) -> io::Result<()> {
+    let args = [
+        (
+            "alcohol",
+            product.alcohol.unwrap_or_default().to_string().into(),
+        ),
+        ("limit", ALCOHOL_LIMIT.to_string().into()),
+    ];
+    let reason = tr_with("not-applicable-alcohol", &args);
+    match format {
+        Format::Text if text.quiet => eprintln!("{reason}"),
+        Format::Text => println!("{reason}"),
+        Format::Json => {
+            let result = JsonNotApplicable {
+                applicable: false,
+                alcohol: product.alcohol,
+                reason,
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(())
+}
+
+/// Prints the traffic lights per 100g and per portion, see [`traffic_lights`].
+fn print_traffic_lights(lights: &TrafficLights) {
+    println!("\n{}", tr("traffic-lights-per-100"));
+    print_lights(&lights.per_100);
+    if let Some(portion) = lights.portion {
+        let args = [("portion", portion.into())];
+        println!("\n{}", tr_with("traffic-lights-per-portion", &args));
+        print_lights(&lights.per_portion);
+    }
+}
+
+/// Prints how far every nutrient is from the cutoffs around its value, like
+/// "Sugar  9.4 g: 0.4 g above 9 g, 0.6 g below 10 g".
+fn print_margins(margins: &[Margin]) {
+    println!("\n{}", tr("sensitivity-intro"));
+    for m in margins {
+        let sides = [
+            ("sensitivity-above", m.lower, m.above),
+            ("sensitivity-below", m.upper, m.below),
+        ];
+        let distances: Vec<String> = sides
+            .into_iter()
+            .filter_map(|(id, cutoff, by)| {
+                let args = [
+                    ("by", by?.to_string().into()),
+                    ("cutoff", cutoff?.to_string().into()),
+                    ("unit", m.unit.into()),
+                ];
+                Some(tr_with(id, &args))
+            })
+            .collect();
+        println!(
+            "  {:13} {} {}: {}",
+            i18n::component(m.nutrient),
+            m.value,
+            m.unit,
+            distances.join(", ")
+        );
+    }
+}
+
+/// Prints the grades the samples of [`uncertainty`] got and whether the grade is stable.
+fn print_uncertainty(uncertainty: &Uncertainty) {
+    let args = [("samples", uncertainty.samples.into())];
+    println!("\n{}", tr_with("uncertainty-grades", &args));
+    for g in &uncertainty.grades {
+        println!("  {} {:5.1} %", g.grade, g.share * 100.0);
+    }
+    let args = [
+        ("grade", uncertainty.grade.to_string().into()),
+        ("best", uncertainty.best.into()),
+        ("worst", uncertainty.worst.into()),
+        ("best-grade", uncertainty.best_grade.to_string().into()),
+        ("worst-grade", uncertainty.worst_grade.to_string().into()),
+    ];
+    println!("{}", tr_with("uncertainty-range", &args));
+    let id = if uncertainty.stable {
+        "uncertainty-stable"
+    } else {
+        "uncertainty-unstable"
+    };
+    println!("{}", tr_with(id, &args));
+}
+
+/// Parses a tolerance for `--tolerance` like `sugar=1.5g`, `energy=50kcal` or `salt=10%`, a
+/// percentage is one of the value.
+fn tolerance(text: &str) -> Result<(&'static str, Tolerance), String> {
+    let (name, amount) = text
+        .split_once('=')
+        .ok_or_else(|| format!("{text:?} is not a nutrient and a tolerance like sugar=2g"))?;
+    let (name, unit) = match name.trim().to_lowercase().as_str() {
+        "energy" => ("energy", Unit::Kj),
+        "fat" | "fats" => ("fat", Unit::G),
+        "saturated" | "saturated-fats" | "saturated_fats" => ("saturated_fats", Unit::G),
+        "sugar" | "sugars" => ("sugar", Unit::G),
+        "protein" | "proteins" => ("proteins", Unit::G),
+        "salt" => ("salt", Unit::G),
+        "fiber" | "fibers" => ("fibers", Unit::G),
+        "fruits" => ("fruits", Unit::Percent),
+        _ => return Err(format!("unknown nutrient {name:?}")),
+    };
+    let amount = amount.trim();
+    let tolerance = match amount.strip_suffix('%') {
+        Some(percent) => Tolerance::Percent(units::parse(percent, Unit::Percent)?),
+        None => Tolerance::Amount(units::parse(amount, unit)?),
+    };
+    Ok((name, tolerance))
+}
+
+/// The tolerances of `--tolerance`, the EU ones for the other values.
+fn tolerances(given: &[(&'static str, Tolerance)]) -> Tolerances {
+    let mut tolerances = Tolerances::default();
+    for &(name, tolerance) in given {
+        let field = match name {
+            "energy" => &mut tolerances.energy,
+            "fat" => &mut tolerances.fat,
+            "saturated_fats" => &mut tolerances.saturated_fats,
+            "sugar" => &mut tolerances.sugar,
+            "proteins" => &mut tolerances.proteins,
+            "salt" => &mut tolerances.salt,
+            "fibers" => &mut tolerances.fibers,
+            _ => &mut tolerances.fruits,
+        };
+        *field = Some(tolerance);
+    }
+    tolerances
+}
+
+/// Prints whether the product can have the Keyhole label and the criteria it fails.
+fn print_keyhole(keyhole: &Keyhole) {
+    let args = [("group", keyhole.group.to_string().into())];
+    if keyhole.eligible {
+        println!("\n{}", tr_with("keyhole-eligible", &args));
+        return;
+    }
+    println!("\n{}", tr_with("keyhole-not-eligible", &args));
+    for c in keyhole.criteria.iter().filter(|c| !c.met) {
+        let (id, limit) = match c.bound {
+            Bound::AtMost(limit) => ("keyhole-at-most", limit),
+            Bound::AtLeast(limit) => ("keyhole-at-least", limit),
+        };
+        let args = [
+            ("amount", format!("{:.2}", c.amount).into()),
+            ("limit", limit.into()),
+            ("unit", c.unit.into()),
+        ];
+        println!(
+            "  {:20} {}",
+            i18n::component(c.nutrient),
+            tr_with(id, &args)
+        );
+    }
+}
+
+/// Parses a positive portion size for `--portion`.
+fn portion(text: &str) -> Result<f32, String> {
+    match text.parse() {
+        Ok(size) if size > 0.0 => Ok(size),
+        _ => Err(format!("{text:?} is not a positive portion size")),
+    }
+}
+
+/// Prints the NOVA group with the ingredients it was estimated from, see [`Product::nova`].
+fn print_nova(nova: &Nova) {
+    let args = [
+        ("group", nova.group.into()),
+        ("description", tr(&format!("nova-{}", nova.group)).into()),
+    ];
+    println!("\n{}", tr_with("nova-group", &args));
+    if !nova.markers.is_empty() {
+        let args = [("markers", nova.markers.join(", ").into())];
+        println!("{}", tr_with("nova-markers", &args));
+    }
+}
+
+/// Prints the Eco-Score with the bonuses and maluses that led to it.
+fn print_eco_score(score: &EcoScore) {
+    let category = tr(&format!("eco-category-{}", score.category));
+    let rows = [
+        (
+            tr_with("eco-baseline", &[("category", category.into())]),
+            score.baseline,
+        ),
+        (tr("eco-labels"), score.labels),
+        (tr("eco-origin"), score.origin),
+        (tr("eco-packaging"), -score.packaging),
+        (tr("eco-palm-oil"), -score.palm_oil),
+    ];
+    let width = rows.iter().map(|(label, _)| label.chars().count()).max();
+    let width = width.unwrap_or_default();
+    println!("\n{}", tr("eco-title"));
+    for (label, points) in rows {
+        println!("  {label:width$} {points:>+4}");
+    }
+    println!("  {:width$} {:>4}", tr("eco-total"), score.total);
+    let grade = tr_with("eco-grade", &[("grade", score.grade.to_string().into())]);
+    println!("\n{}", grade_style(score.grade).apply_to(grade));
+}
+
+fn print_lights(lights: &[TrafficLight]) {
+    for light in lights {
+        let style = match light.light {
+            Light::Green => console::Style::new().green(),
+            Light::Amber => console::Style::new().yellow(),
+            Light::Red => console::Style::new().red(),
+        };
+        println!(
+            "  {:20} {:>7.2} g  {}",
+            i18n::component(light.nutrient),
+            light.amount,
+            style.apply_to(tr(&format!("light-{}", light.light)))
+        );
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRange<'a> {
+    unknown: &'a [Unknown],
+    grades: &'a [char],
+    best: JsonResult<'a>,
+    worst: JsonResult<'a>,
+}
+
+fn print_range<'a>(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    product: &Product,
+    unknown: &'a [Unknown],
+    range: &'a ScoreRange,
+) -> io::Result<()> {
+    match format {
+        // the worst grade, as for the exit code
+        Format::Text if text.quiet => {
+            print_quiet(
+                text,
+                range.worst.total,
+                product.letter(algorithm, &range.worst),
+            );
+        }
+        Format::Text => {
+            let names: Vec<String> = unknown
+                .iter()
+                .map(|u| match u {
+                    Unknown::Fibers => tr("range-fibers"),
+                    Unknown::Fruits => tr("range-fruits"),
+                })
+                .collect();
+            println!(
+                "{}",
+                tr_with("range-unknown", &[("values", names.join(", ").into())])
+            );
+            for (name, score) in [("range-best", &range.best), ("range-worst", &range.worst)] {
+                println!("\n{}", tr(name));
+                draw_score(algorithm, text, score);
+                if text.breakdown {
+                    print_breakdown(&product.nutrition, score);
+                }
+            }
+            let result = tr_with(
+                "range-result",
+                &[
+                    ("best", range.best.total.into()),
+                    ("worst", range.worst.total.into()),
+                ],
+            );
+            let letters: Vec<String> = range.letters.iter().map(char::to_string).collect();
+            if text.accessible {
+                let args = [
+                    ("grades", letters.join(", ").into()),
+                    ("best", range.best.total.into()),
+                    ("worst", range.worst.total.into()),
+                ];
+                println!("\n{}", tr_with("accessible-range", &args));
+            } else if text.plain {
+                println!("\n{result} {}", letters.join(" "));
+            } else {
+                println!("\n{result}");
+                println!("{}", BoxBuilder::new(letters.join(" ")));
+            }
+        }
+        Format::Json => {
+            let result = |score: &'a Score| JsonResult {
+                score: score.total,
+                grade: product.letter(algorithm, score),
+                negative: &score.negative,
+                positive: &score.positive,
+            };
+            let range = JsonRange {
+                unknown,
+                grades: &range.letters,
+                best: result(&range.best),
+                worst: result(&range.worst),
+            };
+            println!("{}", serde_json::to_string_pretty(&range)?);
+        }
+    }
+    Ok(())
+}
+
+fn print_text(
+    algorithm: Algorithm,
+    text: TextOptions,
+    nutrition: &Nutrition,
+    score: &Score,
+    letter: char,
+) {
+    if text.quiet {
+        print_quiet(text, score.total, letter);
+        return;
+    }
+    draw_score(algorithm, text, score);
+    if text.breakdown {
+        print_breakdown(nutrition, score);
+    }
+    let style = grade_style(letter);
+    if text.accessible {
+        let args = [
+            ("grade", letter.to_string().into()),
+            ("score", score.total.into()),
+        ];
+        println!("\n{}", tr_with("accessible-grade", &args));
+        return;
+    }
+    if text.plain {
+        println!("\n{} {letter}", tr("total-score"));
+        return;
+    }
+    println!("\n{}", style.apply_to(tr("total-score")));
+    for line in BoxBuilder::new(format!("{letter}")).to_string().lines() {
+        println!("{}", style.apply_to(line));
+    }
+}
+
+/// Prints only the grade for `--quiet`, or the score and the grade separated by `;`.
+fn print_quiet(text: TextOptions, score: isize, letter: impl fmt::Display) {
+    if text.with_score {
+        println!("{score};{letter}");
+    } else {
+        println!("{letter}");
+    }
+}
+
+/// The 256-color code of a grade.
+const fn grade_color(letter: char) -> u8 {
+    match letter {
+        'A' => 28,
+        'B' => 112,
+        'C' => 220,
+        'D' => 208,
+        'E' => 196,
+        _ => 7,
+    }
+}
+
+/// The terminal color of a grade, close to the colors of the official logo.
+fn grade_style(letter: char) -> console::Style {
+    console::Style::new().color256(grade_color(letter)).bold()
+}
+
+#[derive(Serialize)]
+struct JsonAdvice<'a> {
+    score: isize,
+    grade: char,
+    advice: &'a [Advice],
+}
+
+fn print_advice(algorithm: Algorithm, format: Format, product: &Product) -> io::Result<()> {
+    let score = product.score(algorithm);
+    let letter = product.letter(algorithm, &score);
+    let advice = advise(
+        algorithm,
+        product.category,
+        &product.nutrition,
+        product.fruits,
+        product.is_water(),
+    );
+    match format {
+        Format::Text => {
+            let args = [
+                ("grade", letter.to_string().into()),
+                ("score", score.total.into()),
+            ];
+            println!("{}", tr_with("advice-current", &args));
+            if advice.is_empty() {
+                println!("{}", tr("advice-none"));
+            } else {
+                println!("{}", tr("advice-intro"));
+            }
+            for a in &advice {
+                let id = if a.to > a.from {
+                    "advice-increase"
+                } else {
+                    "advice-reduce"
+                };
+                let args = [
+                    ("nutrient", i18n::nutrient(a.nutrient).into()),
+                    ("by", format!("{:.2}", (a.to - a.from).abs()).into()),
+                    ("to", format!("{:.2}", a.to).into()),
+                    ("unit", a.unit.into()),
+                ];
+                println!("  {}", tr_with(id, &args));
+            }
+        }
+        Format::Json => {
+            let result = JsonAdvice {
+                score: score.total,
+                grade: letter,
+                advice: &advice,
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "gui")]
+fn gui(algorithm: Algorithm) -> io::Result<()> {
+    gui::run(algorithm)
+}
+
+#[cfg(not(feature = "gui"))]
+fn gui(_: Algorithm) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this build has no GUI, build it with --features gui",
+    ))
+}
+
+#[cfg(feature = "index")]
+fn index(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    dir: Option<&Path>,
+    command: IndexCommand,
+) -> io::Result<Option<char>> {
+    let dir = index::dir(dir)?;
+    match command {
+        IndexCommand::Build { input } => index::build(&dir, &input).map(|()| None),
+        IndexCommand::Search {
+            query,
+            limit,
+            category,
+        } => index::search(algorithm, format, text, &dir, &query, limit, category),
+    }
+}
+
+#[cfg(not(feature = "index"))]
+fn index(
+    _: Algorithm,
+    _: Format,
+    _: TextOptions,
+    _: Option<&Path>,
+    _: IndexCommand,
+) -> io::Result<Option<char>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this build has no index, build it with --features index",
+    ))
+}
+
+fn read_product(algorithm: Algorithm, args: &NutritionArgs) -> io::Result<Product> {
+    read_product_with_unknown(algorithm, args, false).map(|(product, _)| product)
+}
+
+/// Reads a product like [`read_product`], but if `allow_unknown` is set, missing fibers and fruits
+/// are not asked for again and returned as unknown instead.
+fn read_product_with_unknown(
+    algorithm: Algorithm,
+    args: &NutritionArgs,
+    allow_unknown: bool,
+) -> io::Result<(Product, Vec<Unknown>)> {
+    let pasted;
+    let args = if args.from_clipboard || args.from_label.is_some() {
+        pasted = pasted::fill_args(args)?;
+        &pasted
+    } else {
+        args
     };
-    let category: Category = ask_enum("Category")?;
-    let fruits: f32 = ask("Percentage of fruits and vegetables");
-    let is_water: bool = if category == Drinks {
-        Confirm::new().with_prompt("Is it water").interact()?
+    let answered;
+    let values = if args.non_interactive {
+        args
     } else {
-        false
+        answered = wizard::run(algorithm, args, allow_unknown)?;
+        &answered
+    };
+    product_from(args, values, allow_unknown)
+}
+
+/// Asks for all values of a saved product again, with the current ones offered for editing.
+fn edit_product(algorithm: Algorithm, product: &Product) -> io::Result<Product> {
+    let args = NutritionArgs {
+        product_name: product.name.clone(),
+        ingredients: product.ingredients.clone(),
+        eco: product.eco.is_some(),
+        ..NutritionArgs::default()
+    };
+    let values = wizard::edit(algorithm, &args, product)?;
+    product_from(&args, &values, false).map(|(product, _)| product)
+}
+
+/// Builds the product from the arguments completed with the answers in `values`.
+fn product_from(
+    args: &NutritionArgs,
+    values: &NutritionArgs,
+    allow_unknown: bool,
+) -> io::Result<(Product, Vec<Unknown>)> {
+    let mut unknown = Vec::new();
+    let mut maybe = |text: Option<&str>, prompt: &str, unit: Unit, field: Unknown| match argument(
+        text, prompt, unit,
+    )? {
+        Some(value) => Ok(value),
+        None if allow_unknown => {
+            unknown.push(field);
+            Ok(0.0)
+        }
+        None => Err(missing(&tr(prompt))),
+    };
+    let fibers = maybe(
+        values.fibers.as_deref(),
+        "prompt-fibers",
+        Unit::G,
+        Unknown::Fibers,
+    )?;
+    if args.serving_size.is_some_and(|size| size <= 0.0) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the serving size must be positive",
+        ));
+    }
+    let mut nutrition = Nutrition {
+        energy: energy(values)?,
+        fat: grams(values.fat.as_deref(), "prompt-fat")?,
+        saturated_fats: grams(values.saturated_fats.as_deref(), "prompt-saturated-fats")?,
+        carbohydrates: argument(
+            values.carbohydrates.as_deref(),
+            "prompt-carbohydrates",
+            Unit::G,
+        )?,
+        sugar: grams(values.sugar.as_deref(), "prompt-sugar")?,
+        proteins: grams(values.proteins.as_deref(), "prompt-proteins")?,
+        salt: salt(values)?,
+        fibers,
+        contains_sweeteners: values.sweeteners,
+    };
+    if let Some(size) = args.serving_size {
+        nutrition = nutrition.per_100(size);
+    }
+    let category: Category = match values.category {
+        Some(category) => category,
+        None if values.milk => Category::MilkDrinks,
+        None => return Err(missing(&tr("prompt-category"))),
     };
+    let fruits = maybe(
+        values.fruits.as_deref(),
+        "prompt-fruits",
+        Unit::Percent,
+        Unknown::Fruits,
+    )?;
+    let drink = values
+        .drink
+        .or_else(|| values.water.then_some(DrinkType::Water))
+        .filter(|_| category == Category::Drinks);
 
-    let score = calculate_nutriscore(category, &nutrition, fruits);
-    let letter = category.score_to_letter(score, is_water);
+    let product = Product {
+        name: values.product_name.clone(),
+        category,
+        nutrition,
+        fruits,
+        water: drink.is_some_and(DrinkType::is_water),
+        drink,
+        alcohol: argument(values.alcohol.as_deref(), "prompt-alcohol", Unit::Percent)?,
+        ingredients: args.ingredients.clone(),
+        eco: eco_product(values)?,
+    };
+    Ok((check(product, args)?, unknown))
+}
 
-    println!("\nTotal Score:");
-    println!("{}", BoxBuilder::new(format!("{letter}")));
+/// What the Eco-Score is calculated from, if it is wanted.
+fn eco_product(values: &NutritionArgs) -> io::Result<Option<EcoProduct>> {
+    if !values.wants_eco() {
+        return Ok(None);
+    }
+    Ok(Some(EcoProduct {
+        category: values
+            .eco_category
+            .ok_or_else(|| missing(&tr("prompt-eco-category")))?,
+        packaging: values
+            .packaging
+            .ok_or_else(|| missing(&tr("prompt-packaging")))?,
+        origin: values.origin.ok_or_else(|| missing(&tr("prompt-origin")))?,
+        labels: match &values.labels {
+            Some(text) => {
+                wizard::labels(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            }
+            None => Vec::new(),
+        },
+        palm_oil: values.palm_oil,
+    }))
+}
 
+/// Fails on impossible values, or asks for them again in interactive mode, and prints warnings.
+fn check(mut product: Product, args: &NutritionArgs) -> io::Result<Product> {
+    loop {
+        let issues = product.validate();
+        let (errors, warnings): (Vec<_>, Vec<_>) = issues
+            .iter()
+            .partition(|issue| issue.severity == Severity::Error);
+        if errors.is_empty() {
+            for warning in warnings {
+                eprintln!(
+                    "{}",
+                    tr_with("warning", &[("message", i18n::issue(warning).into())])
+                );
+            }
+            return Ok(product);
+        }
+        if args.non_interactive {
+            let messages: Vec<String> = errors.iter().map(|e| i18n::issue(e)).collect();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                messages.join("\n"),
+            ));
+        }
+        let mut fields = Vec::new();
+        for error in errors {
+            eprintln!("{}", i18n::issue(error));
+            for &field in error.fields {
+                if !fields.contains(&field) {
+                    fields.push(field);
+                }
+            }
+        }
+        for field in fields {
+            ask_again(&mut product, field, args)?;
+        }
+    }
+}
+
+fn ask_again(product: &mut Product, field: Field, args: &NutritionArgs) -> io::Result<()> {
+    let factor = args.serving_size.map_or(1.0, |size| 100.0 / size);
+    let nutrition = &mut product.nutrition;
+    match field {
+        Field::Energy if args.energy_unit == Some(EnergyUnit::Kcal) => {
+            nutrition.energy =
+                Nutrition::kj_from_kcal(ask(&tr("prompt-energy-kcal"), Unit::Kcal)?) * factor;
+        }
+        Field::Energy => nutrition.energy = ask(&tr("prompt-energy-kj"), Unit::Kj)? * factor,
+        Field::Fat => nutrition.fat = ask(&tr("prompt-fat"), Unit::G)? * factor,
+        Field::SaturatedFats => {
+            nutrition.saturated_fats = ask(&tr("prompt-saturated-fats"), Unit::G)? * factor;
+        }
+        Field::Carbohydrates => {
+            nutrition.carbohydrates = Some(ask(&tr("prompt-carbohydrates"), Unit::G)? * factor);
+        }
+        Field::Sugar => nutrition.sugar = ask(&tr("prompt-sugar"), Unit::G)? * factor,
+        Field::Proteins => nutrition.proteins = ask(&tr("prompt-proteins"), Unit::G)? * factor,
+        Field::Salt if args.sodium.is_some() => {
+            nutrition.salt =
+                Nutrition::salt_from_sodium_mg(ask(&tr("prompt-sodium"), Unit::Mg)?) * factor;
+        }
+        Field::Salt => nutrition.salt = ask(&tr("prompt-salt"), Unit::G)? * factor,
+        Field::Fibers => nutrition.fibers = ask(&tr("prompt-fibers"), Unit::G)? * factor,
+        Field::Fruits => product.fruits = ask(&tr("prompt-fruits"), Unit::Percent)?,
+    }
     Ok(())
 }
 
-fn ask<T>(prompt: &str) -> T
-where
-    T: Clone + FromStr + Display,
-    <T as FromStr>::Err: Display,
-{
-    Input::new().with_prompt(prompt).interact().unwrap()
+/// Asks for a value in `unit`, which can also be entered in another unit like `600 mg`.
+fn ask(prompt: &str, unit: Unit) -> io::Result<f32> {
+    ask_with(prompt, |s| units::parse(s, unit))
+}
+
+fn ask_with(prompt: &str, parse: impl Fn(&str) -> Result<f32, String>) -> io::Result<f32> {
+    let input: String = Input::new()
+        .with_prompt(prompt)
+        .validate_with(|s: &String| parse(s).map(drop))
+        .interact()
+        .map_err(error::terminal(prompt))?;
+    parse(&input).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+fn missing(prompt: &str) -> io::Error {
+    error::missing(tr_with("missing-value", &[("value", prompt.into())]))
+}
+
+/// Parses a value given on the command line into `unit`, the prompt names it in errors.
+fn argument(text: Option<&str>, prompt: &str, unit: Unit) -> io::Result<Option<f32>> {
+    text.map(|text| {
+        units::parse(text, unit).map_err(|e| error::invalid_value(&tr(prompt), text, e))
+    })
+    .transpose()
+}
+
+/// Parses a value that is required, in `unit`.
+fn required(text: Option<&str>, prompt: &str, unit: Unit) -> io::Result<f32> {
+    argument(text, prompt, unit)?.ok_or_else(|| missing(&tr(prompt)))
+}
+
+/// Parses a nutrient in g.
+fn grams(text: Option<&str>, prompt: &str) -> io::Result<f32> {
+    required(text, prompt, Unit::G)
+}
+
+fn energy(args: &NutritionArgs) -> io::Result<f32> {
+    match args.energy_unit {
+        Some(EnergyUnit::Kcal) => Ok(Nutrition::kj_from_kcal(required(
+            args.energy.as_deref(),
+            "prompt-energy-kcal",
+            Unit::Kcal,
+        )?)),
+        Some(EnergyUnit::Kj) | None => {
+            required(args.energy.as_deref(), "prompt-energy-kj", Unit::Kj)
+        }
+    }
+}
+
+/// Asks for the ingredients of a recipe and calculates their percentage of fruits and vegetables.
+fn fruits_wizard() -> io::Result<f32> {
+    println!("{}", tr("wizard-intro"));
+    let mut ingredients = Vec::new();
+    while let Some(mass) = ask_optional(&tr("wizard-mass"), Unit::G)? {
+        let kind: IngredientKind = ask_enum(&tr("wizard-kind"), |kind| {
+            tr(match kind {
+                IngredientKind::FruitsAndVegetables => "ingredient-fruits",
+                IngredientKind::Dried => "ingredient-dried",
+                IngredientKind::Concentrated => "ingredient-concentrated",
+                IngredientKind::Other => "ingredient-other",
+            })
+        })?;
+        let concentration = if kind == IngredientKind::Concentrated {
+            ask_with(&tr("wizard-concentration"), i18n::number)?
+        } else {
+            1.0
+        };
+        ingredients.push(Ingredient {
+            kind,
+            mass,
+            concentration,
+        });
+    }
+    let fruits = fruits_percentage(&ingredients);
+    println!(
+        "{}",
+        tr_with(
+            "wizard-result",
+            &[("fruits", format!("{fruits:.1}").into())]
+        )
+    );
+    Ok(fruits)
+}
+
+fn ask_optional(prompt: &str, unit: Unit) -> io::Result<Option<f32>> {
+    let input: String = Input::new()
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .validate_with(|s: &String| {
+            if s.is_empty() {
+                Ok(())
+            } else {
+                units::parse(s, unit).map(drop)
+            }
+        })
+        .interact()
+        .map_err(error::terminal(prompt))?;
+    Ok(units::parse(&input, unit).ok())
+}
+
+fn salt(args: &NutritionArgs) -> io::Result<f32> {
+    if let Some(sodium) = args.sodium.as_deref() {
+        let sodium = required(Some(sodium), "prompt-sodium", Unit::Mg)?;
+        return Ok(Nutrition::salt_from_sodium_mg(sodium));
+    }
+    grams(args.salt.as_deref(), "prompt-salt")
 }
 
-fn ask_enum<T: VariantNames + IntoEnumIterator + EnumCount>(prompt: &str) -> io::Result<T>
-where
-    [(); T::COUNT - 1]:,
-{
+fn ask_enum<T: IntoEnumIterator + EnumCount>(
+    prompt: &str,
+    label: impl Fn(T) -> String,
+) -> io::Result<T> {
+    const { assert!(T::COUNT > 0, "the enum needs at least one variant") };
+    let labels: Vec<String> = T::iter().map(label).collect();
     let idx = Select::new()
-        .items(T::VARIANTS)
+        .items(&labels)
         .with_prompt(prompt)
         .default(T::COUNT - 1)
-        .interact()?;
-    Ok(T::iter().nth(idx).unwrap())
-}
-
-fn points<T>(arr: &[T], value: &T) -> usize
-where
-    T: PartialOrd,
-{
-    assert!(arr.is_sorted());
-    let idx: usize = arr.iter().rposition(|c| value > c).map_or(0, |n| n + 1);
-    assert!(idx <= arr.len());
-    idx
-}
-
-fn calculate_nutriscore(cat: Category, nutrition: &Nutrition, fruits_value: f32) -> isize {
-    let [energy, fats, sugar, protein, sodium, fibers, fruits] = cat.all_cutoffs();
-    let fat_value = nutrition.saturated_fat_value(cat);
-    let negative = draw_negative("Energy", energy, &nutrition.energy)
-        + draw_negative("Sugar", sugar, &nutrition.sugar)
-        + draw_negative("Fats", fats, &fat_value)
-        + draw_negative("Sodium", sodium, &nutrition.sodium());
-    let negative = isize::try_from(negative).unwrap();
-    let fruits_points = draw_positive("Fruits & Vegs", fruits, &fruits_value);
-    let positive = || {
-        isize::try_from(
-            fruits_points
-                + draw_positive("Fibers", fibers, &nutrition.fibers)
-                + draw_positive("Protein", protein, &nutrition.proteins),
-        )
-        .unwrap()
-    };
-    if cat == Cheese {
-        negative - positive()
-    } else if negative >= 11 && fruits_points < 5 {
-        println!("\nThe negative score {negative} is more than 10 and the fruit score {fruits_points} is less than 5.");
-        println!("Fibers and Proteins will not be counted!");
-        negative - isize::try_from(fruits_points).unwrap()
+        .interact()
+        .map_err(error::terminal(prompt))?;
+    Ok(T::iter()
+        .nth(idx)
+        .expect("the selection is one of the items"))
+}
+
+fn draw_score(algorithm: Algorithm, text: TextOptions, score: &Score) {
+    draw_components(text, score);
+    if score.positive.iter().any(|c| !c.counted) {
+        let negative = score.negative_points();
+        match algorithm {
+            Algorithm::V2017 => {
+                let args = [
+                    ("negative", negative.into()),
+                    ("fruits", score.positive[0].points.into()),
+                ];
+                println!("\n{}", tr_with("not-counted-2017", &args));
+            }
+            Algorithm::V2023 => {
+                println!(
+                    "\n{}",
+                    tr_with("not-counted-2023", &[("negative", negative.into())])
+                );
+            }
+        }
+    }
+}
+
+/// Draws the points of every counted component, or describes them with `--accessible`.
+fn draw_components(text: TextOptions, score: &Score) {
+    if text.accessible {
+        describe(score);
     } else {
-        negative - positive()
+        for c in &score.negative {
+            draw(c, text.progress_bars, "red");
+        }
+        for c in score.positive.iter().filter(|c| c.counted) {
+            draw(c, text.progress_bars, "green");
+        }
     }
 }
 
-fn draw_positive<T: PartialOrd>(name: &str, arr: &[T], value: &T) -> usize {
-    draw(name, arr, value, "green")
+fn print_breakdown(nutrition: &Nutrition, score: &Score) {
+    println!(
+        "\n{:13} {:>8} {:>17} {:>6} {:>4}",
+        tr("breakdown-nutrient"),
+        tr("breakdown-value"),
+        tr("breakdown-band"),
+        tr("breakdown-points"),
+        tr("breakdown-max")
+    );
+    for c in score.negative.iter().chain(&score.positive) {
+        let band = band(c);
+        let note = if c.counted {
+            String::new()
+        } else {
+            format!(" {}", tr("breakdown-not-counted"))
+        };
+        println!(
+            "{:13} {:>8.2} {band:>17} {:>6} {:>4}{note}",
+            i18n::component(c.name),
+            c.value,
+            c.points,
+            c.max_points
+        );
+    }
+    if let Some(carbohydrates) = nutrition.carbohydrates {
+        println!(
+            "{:13} {carbohydrates:>8.2} {:>28}",
+            tr("prompt-carbohydrates"),
+            tr("breakdown-not-scored")
+        );
+    }
+    println!(
+        "{:13} {:>33}",
+        tr("breakdown-negative"),
+        score.negative_points()
+    );
+    println!(
+        "{:13} {:>33}",
+        tr("breakdown-positive"),
+        score.positive_points()
+    );
+    println!("{:13} {:>33}", tr("breakdown-total"), score.total);
 }
 
-fn draw_negative<T: PartialOrd>(name: &str, arr: &[T], value: &T) -> usize {
-    draw(name, arr, value, "red")
+/// The cutoffs around the value of `c`, like `1 < x <= 2`.
+fn band(c: &Component) -> String {
+    match (c.lower, c.upper) {
+        (Some(lower), Some(upper)) if upper.is_finite() => format!("{lower} < x <= {upper}"),
+        (Some(lower), _) => format!("x > {lower}"),
+        (None, Some(upper)) => format!("x <= {upper}"),
+        (None, None) => String::new(),
+    }
 }
 
-fn draw<T: PartialOrd>(name: &str, arr: &[T], value: &T, style: &str) -> usize {
-    let p = points(arr, value);
-    let bar = ProgressBar::with_draw_target(Some(arr.len() as u64), ProgressDrawTarget::stdout());
+/// Prints a sentence for every component instead of drawing it, for screen readers.
+fn describe(score: &Score) {
+    let negative = score.negative.iter().map(|c| ("accessible-negative", c));
+    let positive = score.positive.iter().map(|c| {
+        if c.counted {
+            ("accessible-positive", c)
+        } else {
+            ("accessible-not-counted", c)
+        }
+    });
+    for (id, c) in negative.chain(positive) {
+        let args = [
+            ("name", i18n::component(c.name).into()),
+            ("points", c.points.into()),
+            ("max", c.max_points.into()),
+        ];
+        println!("{}", tr_with(id, &args));
+    }
+}
+
+fn draw(component: &Component, progress_bars: bool, style: &str) {
+    if !progress_bars {
+        println!(
+            "{:13} {:>2}/{:2}",
+            i18n::component(component.name),
+            component.points,
+            component.max_points
+        );
+        return;
+    }
+    let len = component.max_points;
+    let bar = ProgressBar::with_draw_target(Some(len as u64), ProgressDrawTarget::stdout());
     bar.set_style(
         ProgressStyle::with_template(&format!(
-            "{{msg:13}} {{pos:>2}}/{{len:2}} {{bar:{}.{}}}",
-            arr.len(),
-            style
+            "{{msg:13}} {{pos:>2}}/{{len:2}} {{bar:{len}.{style}}}"
         ))
-        .unwrap(),
+        .expect("the template is valid"),
     );
-    bar.set_message(Cow::Owned(name.to_owned()));
-    bar.set_position(p as u64);
+    bar.set_message(i18n::component(component.name));
+    bar.set_position(component.points as u64);
     bar.abandon();
-    p
 }
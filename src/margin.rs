@@ -0,0 +1,144 @@
+use crate::{round, Algorithm, Category, Score};
+use serde::Serialize;
+
+/// How far the value of a component is from the cutoffs around it, see [`margins`].
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct Margin {
+    /// Name of the component.
+    pub nutrient: &'static str,
+    /// Unit of the value and the cutoffs, `kJ`, `g`, `mg` or `%`.
+    pub unit: &'static str,
+    /// The rounded value the points were looked up with.
+    pub value: f32,
+    /// The points the value gets.
+    pub points: usize,
+    /// Whether fewer points are better, like for sugar.
+    pub negative: bool,
+    /// The cutoff the value is above, at or below it the component gets a point less.
+    pub lower: Option<f32>,
+    /// How far the value is above `lower`.
+    pub above: Option<f32>,
+    /// The cutoff the value is at or below, above it the component gets a point more.
+    pub upper: Option<f32>,
+    /// How far the value is below `upper`.
+    pub below: Option<f32>,
+}
+
+/// Finds how far every nutrient of a score is from the cutoff below and above its value, to see
+/// which one needs the smallest change for a better score.
+///
+/// The score has to be of the category, as the units depend on it, like the saturated fats of
+/// oils and fats that are scored as their percentage of the fats. Components without cutoffs,
+/// like the sweeteners of 2023, are left out.
+#[must_use]
+pub fn margins(algorithm: Algorithm, category: Category, score: &Score) -> Vec<Margin> {
+    let definition = category.definition(algorithm);
+    let negative = score.negative.iter().map(|c| (c, true));
+    let positive = score.positive.iter().map(|c| (c, false));
+    negative
+        .chain(positive)
+        .filter(|(c, _)| !c.cutoffs.is_empty())
+        .map(|(c, negative)| {
+            // the units of the values `CategoryDefinition::score` looks the points up with
+            let unit = match c.name {
+                "Energy" => "kJ",
+                // `Nutrition::sodium`, in mg like the sodium cutoffs
+                "Sodium" => "mg",
+                "Fruits & Vegs" => "%",
+                "Fats" if definition.saturates_ratio => "%",
+                _ => "g",
+            };
+            let upper = c.upper.filter(|upper| upper.is_finite());
+            Margin {
+                nutrient: c.name,
+                unit,
+                value: c.value,
+                points: c.points,
+                negative,
+                lower: c.lower,
+                // rounded so that 9.4 is 0.4 above 9 and not 0.39999962
+                above: c.lower.map(|lower| round(c.value - lower, 1000.0)),
+                upper,
+                below: upper.map(|upper| round(upper - c.value, 1000.0)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_nutriscore, Nutrition};
+
+    fn margins_of(algorithm: Algorithm, category: Category, nutrition: &Nutrition) -> Vec<Margin> {
+        let score = calculate_nutriscore(algorithm, category, nutrition, 0.0);
+        margins(algorithm, category, &score)
+    }
+
+    fn sugar(nutrition: &Nutrition) -> Margin {
+        margins_of(Algorithm::V2017, Category::Other, nutrition)
+            .into_iter()
+            .find(|m| m.nutrient == "Sugar")
+            .unwrap()
+    }
+
+    #[test]
+    fn values_are_between_two_cutoffs() {
+        let sugar = sugar(&Nutrition {
+            sugar: 9.4,
+            ..Nutrition::default()
+        });
+        assert_eq!((sugar.points, sugar.negative), (2, true));
+        assert_eq!(
+            format!("{:?}", (sugar.lower, sugar.above, sugar.upper, sugar.below)),
+            "(Some(9.0), Some(0.4), Some(13.5), Some(4.1))"
+        );
+    }
+
+    #[test]
+    fn the_lowest_and_highest_points_have_one_cutoff() {
+        let none = sugar(&Nutrition::default());
+        assert_eq!((none.lower, none.above), (None, None));
+        assert_eq!(format!("{:?}", none.below), "Some(4.5)");
+        let most = sugar(&Nutrition {
+            sugar: 60.0,
+            ..Nutrition::default()
+        });
+        assert_eq!((most.points, most.upper, most.below), (10, None, None));
+    }
+
+    #[test]
+    fn the_units_depend_on_the_category() {
+        let units = |algorithm, category| -> Vec<(&str, &str)> {
+            margins_of(algorithm, category, &Nutrition::default())
+                .iter()
+                .map(|m| (m.nutrient, m.unit))
+                .collect()
+        };
+        assert_eq!(
+            units(Algorithm::V2017, Category::Other),
+            [
+                ("Energy", "kJ"),
+                ("Sugar", "g"),
+                ("Fats", "g"),
+                ("Sodium", "mg"),
+                ("Fruits & Vegs", "%"),
+                ("Fibers", "g"),
+                ("Protein", "g"),
+            ]
+        );
+        let oils = units(Algorithm::V2023, Category::OilsAndFats);
+        assert!(oils.contains(&("Fats", "%")) && oils.contains(&("Salt", "g")));
+    }
+
+    #[test]
+    fn components_without_cutoffs_are_left_out() {
+        let drink = Nutrition {
+            contains_sweeteners: true,
+            ..Nutrition::default()
+        };
+        let margins = margins_of(Algorithm::V2023, Category::Drinks, &drink);
+        assert!(margins.iter().all(|m| m.nutrient != "Sweeteners"));
+        assert_eq!(margins.len(), 7);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::cache::Cache;
+use nutriscore::Algorithm;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The upper bounds of the buckets of the request durations, in seconds.
+const BUCKETS: [f64; 8] = [0.000_5, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0];
+
+#[derive(Default)]
+struct Histogram {
+    /// The number of observations in each bucket, not cumulative.
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if let Some(i) = BUCKETS.iter().position(|&bound| value <= bound) {
+            self.buckets[i] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// The metrics of `serve`, in the text format of Prometheus at `/metrics`.
+#[derive(Default)]
+pub struct Metrics {
+    /// By method, path and status.
+    requests: BTreeMap<(String, &'static str, u16), u64>,
+    /// By path.
+    durations: BTreeMap<&'static str, Histogram>,
+    /// By algorithm and grade.
+    grades: BTreeMap<(String, char), u64>,
+}
+
+impl Metrics {
+    /// Counts a request to one of the paths of the server, or `other`.
+    pub fn request(&mut self, method: &str, path: &'static str, status: u16, seconds: f64) {
+        *self
+            .requests
+            .entry((method.to_owned(), path, status))
+            .or_default() += 1;
+        self.durations.entry(path).or_default().observe(seconds);
+    }
+
+    /// Counts a grade that was returned.
+    pub fn grade(&mut self, algorithm: Algorithm, grade: char) {
+        *self
+            .grades
+            .entry((algorithm.to_string(), grade))
+            .or_default() += 1;
+    }
+
+    /// The metrics in the text exposition format, with the hits and misses of the `cache` of
+    /// the lookups.
+    pub fn render(&self, cache: &Cache) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP nutriscore_requests_total Requests by method, path and status.\n");
+        out.push_str("# TYPE nutriscore_requests_total counter\n");
+        for ((method, path, status), count) in &self.requests {
+            let _ = writeln!(
+                out,
+                "nutriscore_requests_total{{method=\"{method}\",path=\"{path}\",status=\"{status}\"}} {count}"
+            );
+        }
+        out.push_str("# HELP nutriscore_request_duration_seconds Time to answer a request.\n");
+        out.push_str("# TYPE nutriscore_request_duration_seconds histogram\n");
+        for (path, histogram) in &self.durations {
+            let mut cumulative = 0;
+            for (bound, count) in BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "nutriscore_request_duration_seconds_bucket{{path=\"{path}\",le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "nutriscore_request_duration_seconds_bucket{{path=\"{path}\",le=\"+Inf\"}} {}\n\
+                 nutriscore_request_duration_seconds_sum{{path=\"{path}\"}} {}\n\
+                 nutriscore_request_duration_seconds_count{{path=\"{path}\"}} {}",
+                histogram.count, histogram.sum, histogram.count
+            );
+        }
+        out.push_str("# HELP nutriscore_grades_total Grades returned by algorithm.\n");
+        out.push_str("# TYPE nutriscore_grades_total counter\n");
+        for ((algorithm, grade), count) in &self.grades {
+            let _ = writeln!(
+                out,
+                "nutriscore_grades_total{{algorithm=\"{algorithm}\",grade=\"{grade}\"}} {count}"
+            );
+        }
+        let (hits, misses) = cache.counts();
+        let _ = writeln!(
+            out,
+            "# HELP nutriscore_cache_hits_total Lookups answered from the cache.\n\
+             # TYPE nutriscore_cache_hits_total counter\n\
+             nutriscore_cache_hits_total {hits}\n\
+             # HELP nutriscore_cache_misses_total Lookups that were fetched.\n\
+             # TYPE nutriscore_cache_misses_total counter\n\
+             nutriscore_cache_misses_total {misses}"
+        );
+        out
+    }
+}
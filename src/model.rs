@@ -0,0 +1,31 @@
+use crate::{Algorithm, Product, Score};
+
+/// A front-of-pack model that turns the values of a product into points for its components, a
+/// total and a grade.
+///
+/// Both versions of the Nutri-Score implement it through [`Algorithm`], so that other models can
+/// be used wherever a product is scored.
+pub trait ScoringModel {
+    /// The name of the model, like "Nutri-Score 2023".
+    fn name(&self) -> String;
+
+    /// The points of every component and the total.
+    fn score(&self, product: &Product) -> Score;
+
+    /// The grade of a score calculated with [`ScoringModel::score`], like "B".
+    fn grade(&self, product: &Product, score: &Score) -> String;
+}
+
+impl ScoringModel for Algorithm {
+    fn name(&self) -> String {
+        format!("Nutri-Score {self}")
+    }
+
+    fn score(&self, product: &Product) -> Score {
+        product.score(*self)
+    }
+
+    fn grade(&self, product: &Product, score: &Score) -> String {
+        product.letter(*self, score).to_string()
+    }
+}
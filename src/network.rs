@@ -0,0 +1,29 @@
+use crate::config::Config;
+use clap::Args;
+use nutriscore::off::Network;
+use std::env;
+use std::path::PathBuf;
+
+/// The flags of the commands that send requests to the internet.
+#[derive(Args, Debug)]
+pub struct NetworkArgs {
+    /// Send the requests through this proxy instead of the one of the environment
+    #[clap(long, value_name = "URL")]
+    proxy: Option<String>,
+    /// Also trust the certificate authorities in this PEM file, or in the one of the environment
+    #[clap(long, value_name = "FILE")]
+    ca_bundle: Option<PathBuf>,
+}
+
+impl NetworkArgs {
+    /// The settings of the flags, or else of the config file or the environment.
+    pub fn network(self, config: &Config) -> Network {
+        Network {
+            proxy: self.proxy.or_else(|| config.proxy.clone()),
+            ca_bundle: self
+                .ca_bundle
+                .or_else(|| config.ca_bundle.clone())
+                .or_else(|| env::var_os("SSL_CERT_FILE").map(PathBuf::from)),
+        }
+    }
+}
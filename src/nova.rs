@@ -0,0 +1,200 @@
+use serde::Serialize;
+
+/// The NOVA group of an ingredient list, see [`nova_group`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Nova {
+    /// The group from 1 for unprocessed to 4 for ultra-processed foods.
+    pub group: u8,
+    /// The ingredients that led to the group.
+    pub markers: Vec<String>,
+}
+
+// (all words of) ingredients and kinds of additives that are only used in ultra-processed foods
+const ULTRA_PROCESSED: [&str; 40] = [
+    "glucose syrup",
+    "fructose syrup",
+    "corn syrup",
+    "invert sugar",
+    "maltodextrin",
+    "dextrose",
+    "hydrogenated",
+    "interesterified",
+    "hydrolysed protein",
+    "hydrolyzed protein",
+    "protein isolate",
+    "whey protein",
+    "casein",
+    "gluten",
+    "mechanically separated",
+    "modified starch",
+    "flavouring",
+    "flavoring",
+    "flavour",
+    "flavor",
+    "emulsifier",
+    "thickener",
+    "stabiliser",
+    "stabilizer",
+    "colour",
+    "color",
+    "colouring",
+    "coloring",
+    "sweetener",
+    "aspartame",
+    "acesulfame",
+    "sucralose",
+    "saccharin",
+    "glazing agent",
+    "humectant",
+    "bulking agent",
+    "firming agent",
+    "anti caking agent",
+    "foaming agent",
+    "carbonating agent",
+];
+
+// processed culinary ingredients, which make other foods processed
+const CULINARY: [&str; 12] = [
+    "salt", "sugar", "oil", "butter", "lard", "vinegar", "honey", "starch", "cream", "syrup",
+    "molasses", "ghee",
+];
+
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Whether all words of `marker` appear in the ingredient, also in the plural.
+fn matches(ingredient: &[&str], marker: &str) -> bool {
+    words(marker).iter().all(|m| {
+        ingredient
+            .iter()
+            .any(|word| word == m || word.strip_suffix('s') == Some(m))
+    })
+}
+
+/// Whether an additive like E471 is only used in ultra-processed foods: colours, emulsifiers and
+/// thickeners, flavour enhancers, glazing agents and sweeteners. `None` if it is no E number.
+fn ultra_processed_additive(word: &str) -> Option<bool> {
+    let digits = word.strip_prefix('e')?;
+    let digits = digits.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let number: u16 = digits.parse().ok()?;
+    Some(matches!(number, 100..=199 | 400..=499 | 620..=650 | 900..=999))
+}
+
+/// Estimates the NOVA group of a product from its ingredient list in English, like
+/// "wheat flour, sugar, palm oil, emulsifier (E471)", or `None` if the list is empty.
+///
+/// Ingredients and additives that are typical for industrial products make it ultra-processed,
+/// group 4. Otherwise, salt, sugar, oils and other culinary ingredients or preservatives make it
+/// processed, group 3, or a processed culinary ingredient, group 2, if it only consists of them.
+/// Everything else is group 1. This is an estimate, the official group is decided from the
+/// whole product.
+#[must_use]
+pub fn nova_group(ingredients: &str) -> Option<Nova> {
+    let lowercase = ingredients.to_lowercase();
+    let list: Vec<&str> = lowercase
+        .split([',', ';', '(', ')', '[', ']', ':'])
+        .map(str::trim)
+        .filter(|i| i.chars().any(char::is_alphabetic) && *i != "ingredients")
+        .collect();
+    if list.is_empty() {
+        return None;
+    }
+    let mut ultra = Vec::new();
+    let mut processed = Vec::new();
+    let mut culinary = 0;
+    for ingredient in &list {
+        let ingredient_words = words(ingredient);
+        let additive = ingredient_words
+            .iter()
+            .find_map(|word| ultra_processed_additive(word));
+        if additive == Some(true)
+            || ULTRA_PROCESSED
+                .iter()
+                .any(|m| matches(&ingredient_words, m))
+        {
+            ultra.push((*ingredient).to_owned());
+        } else if additive == Some(false) {
+            processed.push((*ingredient).to_owned());
+        } else if CULINARY.iter().any(|m| matches(&ingredient_words, m)) {
+            processed.push((*ingredient).to_owned());
+            culinary += 1;
+        }
+    }
+    Some(if !ultra.is_empty() {
+        Nova {
+            group: 4,
+            markers: ultra,
+        }
+    } else if culinary == list.len() {
+        Nova {
+            group: 2,
+            markers: processed,
+        }
+    } else if !processed.is_empty() {
+        Nova {
+            group: 3,
+            markers: processed,
+        }
+    } else {
+        Nova {
+            group: 1,
+            markers: Vec::new(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(ingredients: &str) -> u8 {
+        nova_group(ingredients).unwrap().group
+    }
+
+    #[test]
+    fn an_empty_list_has_no_group() {
+        assert_eq!(nova_group(""), None);
+        assert_eq!(nova_group("Ingredients: "), None);
+    }
+
+    #[test]
+    fn unprocessed_foods_are_group_1() {
+        assert_eq!(group("Ingredients: apples, pears"), 1);
+        assert_eq!(nova_group("oats").unwrap().markers, Vec::<String>::new());
+    }
+
+    #[test]
+    fn culinary_ingredients_alone_are_group_2() {
+        assert_eq!(group("sea salt"), 2);
+        assert_eq!(group("sunflower oil"), 2);
+    }
+
+    #[test]
+    fn foods_with_culinary_ingredients_are_group_3() {
+        let nova = nova_group("tomatoes, salt, olive oil").unwrap();
+        assert_eq!(nova.group, 3);
+        assert_eq!(nova.markers, ["salt", "olive oil"]);
+        // a preservative that is not ultra-processed
+        assert_eq!(group("peas, water, preservative (e202)"), 3);
+    }
+
+    #[test]
+    fn industrial_ingredients_and_additives_are_group_4() {
+        let nova =
+            nova_group("Wheat flour, sugar, palm oil, emulsifier (E471), flavourings").unwrap();
+        assert_eq!(nova.group, 4);
+        assert_eq!(nova.markers, ["emulsifier", "e471", "flavourings"]);
+        assert_eq!(group("yoghurt, glucose-fructose syrup"), 4);
+        assert_eq!(group("water, e950"), 4);
+    }
+
+    #[test]
+    fn markers_match_whole_words() {
+        // "oil" is no word of "boiled", and "colour" none of "colourless"
+        assert_eq!(group("boiled eggs"), 1);
+        assert_eq!(group("colourless water"), 1);
+    }
+}
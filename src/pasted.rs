@@ -0,0 +1,74 @@
+use crate::i18n::{self, tr, tr_with};
+use crate::NutritionArgs;
+use nutriscore::Label;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+#[cfg(feature = "clipboard")]
+fn clipboard() -> io::Result<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| io::Error::other(format!("cannot read the clipboard: {e}")))
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn clipboard() -> io::Result<String> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this build has no clipboard, build it with --features clipboard",
+    ))
+}
+
+/// Sets a value that is not given yet, with its unit so it is read right with `--energy-unit`.
+fn fill(arg: &mut Option<String>, value: Option<f32>, unit: &str) {
+    if arg.is_none() {
+        *arg = value.map(|value| format!("{value} {unit}"));
+    }
+}
+
+/// The arguments with the values of the nutrition table of `--from-label` or `--from-clipboard`,
+/// the ones given on the command line are kept. What is not recognized is asked for as usual.
+pub fn fill_args(args: &NutritionArgs) -> io::Result<NutritionArgs> {
+    let (text, source) = match &args.from_label {
+        Some(path) if path == Path::new("-") => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            (text, "stdin".to_owned())
+        }
+        Some(path) => (
+            fs::read_to_string(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?,
+            path.display().to_string(),
+        ),
+        None => (clipboard()?, tr("source-clipboard")),
+    };
+    let label = Label::parse(&text);
+    if label == Label::default() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            tr_with("no-nutrition-table", &[("source", source.into())]),
+        ));
+    }
+    let missing = label.missing();
+    if !missing.is_empty() {
+        let names: Vec<String> = missing.into_iter().map(i18n::nutrient).collect();
+        let args = [
+            ("source", source.as_str().into()),
+            ("names", names.join(", ").into()),
+        ];
+        eprintln!("{}", tr_with("not-found-in", &args));
+    }
+    let mut args = args.clone();
+    fill(&mut args.energy, label.energy, "kJ");
+    fill(&mut args.fat, label.fat, "g");
+    fill(&mut args.saturated_fats, label.saturated_fats, "g");
+    fill(&mut args.carbohydrates, label.carbohydrates, "g");
+    fill(&mut args.sugar, label.sugar, "g");
+    fill(&mut args.proteins, label.proteins, "g");
+    if args.sodium.is_none() {
+        fill(&mut args.salt, label.salt, "g");
+    }
+    fill(&mut args.fibers, label.fibers, "g");
+    Ok(args)
+}
@@ -0,0 +1,103 @@
+use crate::{
+    calculate_nutriscore, nova_group, Algorithm, Category, EcoProduct, Nova, Nutrition, Score,
+};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+/// A product with everything needed to calculate its Nutri-Score.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+    /// Name of the product.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The food category.
+    pub category: Category,
+    /// Nutritional values per 100g or 100ml.
+    pub nutrition: Nutrition,
+    /// Percentage of fruits, vegetables and nuts.
+    pub fruits: f32,
+    /// Whether the product is water, only relevant for drinks, see [`Product::is_water`].
+    #[serde(default)]
+    pub water: bool,
+    /// The kind of drink, only relevant for drinks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drink: Option<DrinkType>,
+    /// Alcohol by volume in %, only relevant for drinks, see [`Product::is_applicable`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alcohol: Option<f32>,
+    /// The ingredient list from the package, for the NOVA group with [`nova_group`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingredients: Option<String>,
+    /// What the Eco-Score is calculated from, if it is wanted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eco: Option<EcoProduct>,
+}
+
+/// The kind of a drink, which decides the rules it is scored with.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumIter, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+#[serde(rename_all = "kebab-case")]
+pub enum DrinkType {
+    /// Plain or spring water.
+    Water,
+    /// Natural mineral water.
+    MineralWater,
+    /// Water with added or natural carbon dioxide and nothing else.
+    SparklingWater,
+    /// Water with flavorings, scored like other drinks, including sweeteners in 2023.
+    FlavoredWater,
+    /// Any other drink.
+    Other,
+}
+
+impl DrinkType {
+    /// Whether the drink always gets an A.
+    #[must_use]
+    pub const fn is_water(self) -> bool {
+        matches!(
+            self,
+            Self::Water | Self::MineralWater | Self::SparklingWater
+        )
+    }
+}
+
+/// Drinks with more than this much alcohol by volume in % have no Nutri-Score.
+pub const ALCOHOL_LIMIT: f32 = 1.2;
+
+impl Product {
+    /// Whether the Nutri-Score applies to the product. Drinks with more than [`ALCOHOL_LIMIT`]
+    /// alcohol are exempt from the nutrition declaration and out of its scope.
+    #[must_use]
+    pub fn is_applicable(&self) -> bool {
+        !matches!(self.category, Category::Drinks | Category::MilkDrinks)
+            || self.alcohol.is_none_or(|abv| abv <= ALCOHOL_LIMIT)
+    }
+
+    /// Whether the product is plain, mineral or sparkling water, from [`Product::water`] or
+    /// [`Product::drink`].
+    #[must_use]
+    pub fn is_water(&self) -> bool {
+        self.water || self.drink.is_some_and(DrinkType::is_water)
+    }
+
+    /// Calculates the Nutri-Score with [`calculate_nutriscore`].
+    #[must_use]
+    pub fn score(&self, algorithm: Algorithm) -> Score {
+        calculate_nutriscore(algorithm, self.category, &self.nutrition, self.fruits)
+    }
+
+    /// The NOVA group estimated from the ingredient list, if there is one.
+    #[must_use]
+    pub fn nova(&self) -> Option<Nova> {
+        nova_group(self.ingredients.as_deref()?)
+    }
+
+    /// The letter for a score calculated with [`Product::score`].
+    #[must_use]
+    pub fn letter(&self, algorithm: Algorithm, score: &Score) -> char {
+        self.category
+            .score_to_letter(algorithm, score.total, self.is_water())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::error;
+use crate::schema::Validator;
+use nutriscore::Product;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Whether the file is a YAML file instead of a TOML file, by its extension.
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml"))
+}
+
+/// Reads a product file in TOML or YAML, see the README for the format.
+pub fn read(path: &Path) -> io::Result<Product> {
+    let text = fs::read_to_string(path)?;
+    if is_yaml(path) {
+        serde_yaml::from_str(&text).map_err(|e| error::yaml(path, text.clone(), &e))
+    } else {
+        toml::from_str(&text).map_err(|e| error::toml(path, text.clone(), &e))
+    }
+}
+
+/// Reads one product as JSON from stdin, in the format of the lines of `jsonl`.
+pub fn read_stdin() -> io::Result<Product> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    Validator::new()
+        .parse(&text)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("stdin: {e}")))
+}
+
+/// Rounds floats that were converted from `f32` to the shortest decimal of the `f32`, so that
+/// 0.8 is not written as 0.800000011920929.
+#[allow(clippy::cast_possible_truncation)]
+fn shorten(value: &mut toml::Value) {
+    match value {
+        toml::Value::Float(f) => {
+            *f = (*f as f32).to_string().parse().unwrap_or(*f);
+        }
+        toml::Value::Table(table) => table.iter_mut().for_each(|(_, v)| shorten(v)),
+        toml::Value::Array(array) => array.iter_mut().for_each(shorten),
+        _ => {}
+    }
+}
+
+/// Like [`shorten`], for YAML.
+#[allow(clippy::cast_possible_truncation)]
+fn shorten_yaml(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::Number(n) if n.is_f64() => {
+            if let Some(f) = n
+                .as_f64()
+                .and_then(|f| (f as f32).to_string().parse::<f64>().ok())
+            {
+                *n = f.into();
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => mapping.values_mut().for_each(shorten_yaml),
+        serde_yaml::Value::Sequence(sequence) => sequence.iter_mut().for_each(shorten_yaml),
+        _ => {}
+    }
+}
+
+/// The product as the content of a product file.
+pub fn to_string(product: &Product) -> io::Result<String> {
+    let mut value = toml::Value::try_from(product).map_err(io::Error::other)?;
+    shorten(&mut value);
+    toml::to_string(&value).map_err(io::Error::other)
+}
+
+/// Saves a product in a product file that can be scored again later.
+pub fn write(path: &Path, product: &Product) -> io::Result<()> {
+    let text = if is_yaml(path) {
+        let mut value = serde_yaml::to_value(product).map_err(io::Error::other)?;
+        shorten_yaml(&mut value);
+        serde_yaml::to_string(&value).map_err(io::Error::other)?
+    } else {
+        to_string(product)?
+    };
+    fs::write(path, text)
+}
@@ -0,0 +1,96 @@
+use crate::badge_file;
+use nutriscore::{Algorithm, Product, Score};
+use qrcode::render::{svg, unicode};
+use qrcode::QrCode;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// The QR code to show or save after scoring.
+#[derive(Debug)]
+pub struct Qr {
+    /// Prints the code on stderr, so that the result on stdout can still be piped.
+    pub terminal: bool,
+    /// Saves the code as SVG if the file ends in `.svg`, otherwise as PNG.
+    pub out: Option<PathBuf>,
+    /// The address of a server of `serve`, to encode the link to the report of the product
+    /// instead of the result.
+    pub url: Option<String>,
+}
+
+/// The result in the QR code, short so that the code stays small enough to scan on a terminal.
+#[derive(Serialize)]
+struct Result<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    algorithm: Algorithm,
+    score: isize,
+    grade: char,
+}
+
+/// The request of `/score` for the product, the JSON that `/report` takes as `product`.
+#[derive(Serialize)]
+struct Request<'a> {
+    #[serde(flatten)]
+    product: &'a Product,
+    algorithm: Algorithm,
+}
+
+/// The text of the code, the link to `/report` of the server or the result as JSON.
+fn text(
+    qr: &Qr,
+    algorithm: Algorithm,
+    product: &Product,
+    score: &Score,
+    letter: char,
+) -> io::Result<String> {
+    Ok(match &qr.url {
+        Some(url) => {
+            let request = serde_json::to_string(&Request { product, algorithm })?;
+            let query: String = form_urlencoded::byte_serialize(request.as_bytes()).collect();
+            format!("{}/report?product={query}", url.trim_end_matches('/'))
+        }
+        None => serde_json::to_string(&Result {
+            name: product.name.as_deref(),
+            algorithm,
+            score: score.total,
+            grade: letter,
+        })?,
+    })
+}
+
+/// Prints and saves the QR code of a result.
+pub fn write(
+    qr: &Qr,
+    algorithm: Algorithm,
+    product: &Product,
+    score: &Score,
+    letter: char,
+) -> io::Result<()> {
+    let text = text(qr, algorithm, product, score, letter)?;
+    let code = QrCode::new(text).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot make a QR code of the result: {e}"),
+        )
+    })?;
+    if qr.terminal {
+        // light blocks on the dark background of most terminals
+        let image = code
+            .render::<unicode::Dense1x2>()
+            .dark_color(unicode::Dense1x2::Light)
+            .light_color(unicode::Dense1x2::Dark)
+            .build();
+        eprintln!("{image}");
+    }
+    if let Some(out) = &qr.out {
+        let image = code.render::<svg::Color>().min_dimensions(256, 256).build();
+        if out.extension().is_some_and(|extension| extension == "svg") {
+            fs::write(out, image)?;
+        } else {
+            fs::write(out, badge_file::png(&image, None)?)?;
+        }
+    }
+    Ok(())
+}
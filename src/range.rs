@@ -0,0 +1,145 @@
+use crate::{calculate_nutriscore, Algorithm, Category, Nutrition, Score};
+use serde::Serialize;
+
+/// A value that is not known, see [`calculate_score_range`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unknown {
+    Fibers,
+    Fruits,
+}
+
+/// The best and the worst possible score when some values are unknown.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ScoreRange {
+    /// The lowest score, with the unknown values that lead to it.
+    pub best: Score,
+    /// The highest score, with the unknown values that lead to it.
+    pub worst: Score,
+    /// Every letter that one of the possible scores gets, from best to worst.
+    pub letters: Vec<char>,
+}
+
+/// Values that reach every possible number of points with the given cutoffs, at most `max`.
+fn candidates(cutoffs: &[f32], max: f32) -> Vec<f32> {
+    let mut values: Vec<f32> = cutoffs.iter().copied().filter(|&c| c < max).collect();
+    values.insert(0, 0.0);
+    values.push(max);
+    values
+}
+
+/// Calculates every score the product can get when the `unknown` values can be anything.
+///
+/// The values of `nutrition` and `fruits_value` for the unknown fields are ignored.
+#[must_use]
+pub fn calculate_score_range(
+    algorithm: Algorithm,
+    cat: Category,
+    nutrition: &Nutrition,
+    fruits_value: f32,
+    is_water: bool,
+    unknown: &[Unknown],
+) -> ScoreRange {
+    let [_, _, _, _, _, fibers, fruits] = cat.definition(algorithm).cutoffs;
+    let fibers = if unknown.contains(&Unknown::Fibers) {
+        candidates(fibers, 100.0)
+    } else {
+        vec![nutrition.fibers]
+    };
+    let fruits = if unknown.contains(&Unknown::Fruits) {
+        candidates(fruits, 100.0)
+    } else {
+        vec![fruits_value]
+    };
+
+    let score = |fibers: f32, fruits: f32| {
+        let nutrition = Nutrition {
+            fibers,
+            ..*nutrition
+        };
+        calculate_nutriscore(algorithm, cat, &nutrition, fruits)
+    };
+    let mut best = score(fibers[0], fruits[0]);
+    let mut worst = best.clone();
+    let mut letters = Vec::new();
+    for &fibers in &fibers {
+        for &fruits in &fruits {
+            let score = score(fibers, fruits);
+            letters.push(cat.score_to_letter(algorithm, score.total, is_water));
+            if score.total < best.total {
+                best = score.clone();
+            }
+            if score.total > worst.total {
+                worst = score;
+            }
+        }
+    }
+    letters.sort_unstable();
+    letters.dedup();
+    ScoreRange {
+        best,
+        worst,
+        letters,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Category::Other;
+
+    /// 4 points for the sugar, a C without the fibers.
+    fn sugar() -> Nutrition {
+        Nutrition {
+            sugar: 20.0,
+            ..Nutrition::default()
+        }
+    }
+
+    #[test]
+    fn candidates_reach_every_number_of_points() {
+        assert_eq!(candidates(&[0.8, 1.9], 100.0), [0.0, 0.8, 1.9, 100.0]);
+        assert_eq!(
+            candidates(&[40.0, 60.0, f32::INFINITY], 100.0),
+            [0.0, 40.0, 60.0, 100.0]
+        );
+    }
+
+    #[test]
+    fn known_values_have_one_score() {
+        let range = calculate_score_range(Algorithm::V2017, Other, &sugar(), 0.0, false, &[]);
+        assert_eq!(range.best, range.worst);
+        assert_eq!(range.best.total, 4);
+        assert_eq!(range.letters, ['C']);
+    }
+
+    #[test]
+    fn unknown_fibers_can_give_every_fibers_points() {
+        let range = calculate_score_range(
+            Algorithm::V2017,
+            Other,
+            &sugar(),
+            0.0,
+            false,
+            &[Unknown::Fibers],
+        );
+        assert_eq!((range.best.total, range.worst.total), (-1, 4));
+        assert_eq!(range.best.positive[1].points, 5);
+        assert_eq!(range.worst.positive[1].points, 0);
+        assert_eq!(range.letters, ['A', 'B', 'C']);
+    }
+
+    #[test]
+    fn unknown_values_are_combined() {
+        let range = calculate_score_range(
+            Algorithm::V2017,
+            Other,
+            &sugar(),
+            0.0,
+            false,
+            &[Unknown::Fibers, Unknown::Fruits],
+        );
+        // 5 points for the fibers and 5 for the fruits
+        assert_eq!((range.best.total, range.worst.total), (-6, 4));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{fruits_percentage, Category, Ingredient, Nutrition, Product};
+use serde::{Deserialize, Serialize};
+
+/// An ingredient of a [`Recipe`] with its nutritional values.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecipeIngredient {
+    /// Name of the ingredient.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The mass and how the ingredient counts towards the fruits and vegetables.
+    #[serde(flatten)]
+    pub ingredient: Ingredient,
+    /// Nutritional values per 100g of the ingredient.
+    pub nutrition: Nutrition,
+}
+
+/// A product made from several ingredients.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Recipe {
+    /// Name of the product.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The food category of the product.
+    pub category: Category,
+    /// The ingredients with their masses.
+    pub ingredients: Vec<RecipeIngredient>,
+    /// Mass of the finished product in g, if it differs from the sum of the ingredients,
+    /// e.g. because water evaporated while baking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_mass: Option<f32>,
+}
+
+impl Recipe {
+    /// The sum of the masses of the ingredients in g.
+    #[must_use]
+    pub fn mass(&self) -> f32 {
+        self.ingredients.iter().map(|i| i.ingredient.mass).sum()
+    }
+
+    /// The nutritional values per 100g of the finished product.
+    ///
+    /// The carbohydrates are only known if they are known for every ingredient.
+    #[must_use]
+    pub fn nutrition(&self) -> Nutrition {
+        let mut total = Nutrition {
+            carbohydrates: Some(0.0),
+            ..Nutrition::default()
+        };
+        for RecipeIngredient {
+            ingredient,
+            nutrition: n,
+            ..
+        } in &self.ingredients
+        {
+            let factor = ingredient.mass / 100.0;
+            total.energy = n.energy.mul_add(factor, total.energy);
+            total.fat = n.fat.mul_add(factor, total.fat);
+            total.saturated_fats = n.saturated_fats.mul_add(factor, total.saturated_fats);
+            total.sugar = n.sugar.mul_add(factor, total.sugar);
+            total.carbohydrates = total
+                .carbohydrates
+                .zip(n.carbohydrates)
+                .map(|(total, c)| c.mul_add(factor, total));
+            total.proteins = n.proteins.mul_add(factor, total.proteins);
+            total.salt = n.salt.mul_add(factor, total.salt);
+            total.fibers = n.fibers.mul_add(factor, total.fibers);
+            total.contains_sweeteners |= n.contains_sweeteners;
+        }
+        let mass = self.final_mass.unwrap_or_else(|| self.mass());
+        total.per_100(mass)
+    }
+
+    /// The finished product, with the percentage of fruits and vegetables from
+    /// [`fruits_percentage`].
+    #[must_use]
+    pub fn product(&self) -> Product {
+        let ingredients: Vec<Ingredient> = self.ingredients.iter().map(|i| i.ingredient).collect();
+        Product {
+            name: self.name.clone(),
+            category: self.category,
+            nutrition: self.nutrition(),
+            fruits: fruits_percentage(&ingredients),
+            water: false,
+            drink: None,
+            alcohol: None,
+            ingredients: None,
+            eco: None,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::ciqual;
+use crate::database::Database;
+use crate::error;
+use crate::i18n::{self, tr_with};
+use crate::{print_quiet, print_text, report, Format, TextOptions};
+use nutriscore::{
+    Algorithm, Category, Component, Ingredient, Nutrition, Product, Recipe, RecipeIngredient,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A recipe file, where ingredients can also be taken from the product database or the CIQUAL
+/// table.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RecipeFile {
+    name: Option<String>,
+    category: Category,
+    final_mass: Option<f32>,
+    ingredients: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    name: Option<String>,
+    #[serde(flatten)]
+    ingredient: Ingredient,
+    /// Nutritional values per 100g, if not taken from the database.
+    nutrition: Option<Nutrition>,
+    /// The name of a product in the database to take the nutritional values from.
+    product: Option<String>,
+    /// The code or name of a CIQUAL food to take the nutritional values from.
+    ciqual: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonRecipe<'a> {
+    product: &'a Product,
+    score: isize,
+    grade: char,
+    negative: &'a [Component],
+    positive: &'a [Component],
+}
+
+fn invalid(path: &Path, message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {message}", path.display()),
+    )
+}
+
+/// Reads a recipe file, looking up the ingredients that name a product in the database or a
+/// CIQUAL food.
+fn read(path: &Path, database: Option<&Path>, ciqual: Option<&Path>) -> io::Result<Recipe> {
+    let text = fs::read_to_string(path)?;
+    let file: RecipeFile =
+        toml::from_str(&text).map_err(|e| error::toml(path, text.clone(), &e))?;
+    let mut db = None;
+    let mut table = None;
+    let mut ingredients = Vec::with_capacity(file.ingredients.len());
+    for (i, entry) in file.ingredients.into_iter().enumerate() {
+        let mut label = entry
+            .name
+            .clone()
+            .or_else(|| entry.product.clone())
+            .unwrap_or_else(|| format!("ingredient {}", i + 1));
+        if entry.ingredient.mass.is_nan() || entry.ingredient.mass < 0.0 {
+            return Err(invalid(path, format!("{label} has a negative mass")));
+        }
+        let nutrition = match (entry.nutrition, &entry.product, &entry.ciqual) {
+            (Some(nutrition), None, None) => nutrition,
+            (None, Some(product), None) => {
+                let db = match &mut db {
+                    Some(db) => db,
+                    None => db.insert(Database::open(database)?),
+                };
+                db.get(product)?.1.nutrition
+            }
+            (None, None, Some(query)) => {
+                let table = match &mut table {
+                    Some(table) => table,
+                    None => table.insert(ciqual::Table::read(ciqual)?),
+                };
+                let food = table.find(query)?;
+                if entry.name.is_none() {
+                    label.clone_from(&food.name);
+                }
+                food.nutrition
+            }
+            _ => {
+                return Err(invalid(
+                    path,
+                    format!(
+                        "{label} needs exactly one of nutrition values, a product or a CIQUAL food"
+                    ),
+                ))
+            }
+        };
+        ingredients.push(RecipeIngredient {
+            name: Some(label),
+            ingredient: entry.ingredient,
+            nutrition,
+        });
+    }
+    let recipe = Recipe {
+        name: file.name,
+        category: file.category,
+        ingredients,
+        final_mass: file.final_mass,
+    };
+    let mass = recipe.final_mass.unwrap_or_else(|| recipe.mass());
+    if mass.is_nan() || mass <= 0.0 {
+        return Err(invalid(path, "the recipe has no mass"));
+    }
+    Ok(recipe)
+}
+
+pub fn run(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    path: &Path,
+    database: Option<&Path>,
+    ciqual: Option<&Path>,
+) -> io::Result<char> {
+    let recipe = read(path, database, ciqual)?;
+    let mut product = recipe.product();
+    // only for printing, the score is calculated with the same rounded values
+    product.nutrition = product.nutrition.rounded();
+    product.fruits = (product.fruits * 10.0).round() / 10.0;
+    for issue in product.validate() {
+        eprintln!(
+            "{}",
+            tr_with("warning", &[("message", i18n::issue(&issue).into())])
+        );
+    }
+    let score = product.score(algorithm);
+    let letter = product.letter(algorithm, &score);
+    match format {
+        Format::Text if text.quiet => print_quiet(text, score.total, letter),
+        Format::Text => {
+            let width = recipe
+                .ingredients
+                .iter()
+                .filter_map(|i| i.name.as_ref())
+                .map(|name| name.chars().count())
+                .fold(30, usize::max);
+            println!("{:width$} {:>8}", "Ingredient", "Mass");
+            for i in &recipe.ingredients {
+                println!(
+                    "{:width$} {:>6} g",
+                    i.name.as_deref().unwrap_or_default(),
+                    i.ingredient.mass
+                );
+            }
+            println!("\nPer 100 g of the finished product:");
+            for (nutrient, value) in report::inputs(&product) {
+                println!("{nutrient:30} {value:>8}");
+            }
+            print_text(algorithm, text, &product.nutrition, &score, letter);
+        }
+        Format::Json => {
+            let json = JsonRecipe {
+                product: &product,
+                score: score.total,
+                grade: letter,
+                negative: &score.negative,
+                positive: &score.positive,
+            };
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+    }
+    Ok(letter)
+}
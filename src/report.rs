@@ -0,0 +1,498 @@
+use crate::{badge_file, band};
+use nutriscore::{badge_svg, Algorithm, Category, DrinkType, Product, Score, GRADE_COLORS};
+use std::fmt::Write;
+use std::io;
+
+/// The nutrients that were entered, with their units.
+pub fn inputs(product: &Product) -> Vec<(&'static str, String)> {
+    let n = &product.nutrition;
+    let mut inputs = vec![
+        ("Energy", format!("{} kJ", n.energy)),
+        ("Fats", format!("{} g", n.fat)),
+        ("Saturated fats", format!("{} g", n.saturated_fats)),
+    ];
+    if let Some(carbohydrates) = n.carbohydrates {
+        inputs.push(("Carbohydrates", format!("{carbohydrates} g")));
+    }
+    inputs.extend([
+        ("Sugar", format!("{} g", n.sugar)),
+        ("Protein", format!("{} g", n.proteins)),
+        ("Salt", format!("{} g", n.salt)),
+        ("Fibers", format!("{} g", n.fibers)),
+        ("Fruits & Vegs", format!("{} %", product.fruits)),
+    ]);
+    if product.category == Category::Drinks {
+        inputs.push(("Water", yes_no(product.is_water()).into()));
+        if let Some(drink) = product.drink {
+            inputs.push(("Drink", drink.to_string()));
+        }
+    }
+    if n.contains_sweeteners {
+        inputs.push(("Sweeteners", yes_no(true).into()));
+    }
+    inputs
+}
+
+const fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// The special rules of the algorithm that applied to the product.
+pub fn rules(algorithm: Algorithm, product: &Product, score: &Score) -> Vec<String> {
+    let category = product.category;
+    let negative = score.negative_points();
+    let not_counted: Vec<&str> = score
+        .positive
+        .iter()
+        .filter(|c| !c.counted)
+        .map(|c| c.name)
+        .collect();
+    let mut rules = Vec::new();
+    match (algorithm, category) {
+        (_, Category::Drinks) if product.is_water() => {
+            rules.push("Water always gets grade A.".into());
+        }
+        (_, c) if c.is_beverage(algorithm) => {
+            rules.push(format!("The cutoffs for drinks apply ({c})."));
+            if product.drink == Some(DrinkType::FlavoredWater) {
+                rules.push("Flavored water is scored like other drinks.".into());
+            }
+        }
+        (Algorithm::V2017, Category::Cheese) => {
+            rules.push("Cheese: the positive points are always counted.".into());
+        }
+        (Algorithm::V2023, Category::Cheese) => {
+            rules.push("Cheese: the proteins are always counted.".into());
+        }
+        (_, Category::OilsAndFats) => {
+            rules.push(
+                "Oils and fats: the saturated fats are scored as their percentage of the fats."
+                    .into(),
+            );
+            if algorithm == Algorithm::V2023 {
+                rules.push(
+                    "Oils and fats: the energy is scored as the energy from saturated fats \
+                     (37 kJ per gram)."
+                        .into(),
+                );
+            }
+        }
+        (Algorithm::V2023, Category::RedMeat) => {
+            rules.push("Red meat: the protein points are capped at 2.".into());
+        }
+        _ => {}
+    }
+    if algorithm == Algorithm::V2023 && product.nutrition.contains_sweeteners {
+        rules.push("Non-nutritive sweeteners add 4 negative points.".into());
+    }
+    let threshold = match (algorithm, category) {
+        (Algorithm::V2023, c) if c.is_beverage(algorithm) => None,
+        (_, Category::Cheese) => None,
+        (Algorithm::V2023, Category::OilsAndFats) => Some(7),
+        _ => Some(11),
+    };
+    if let Some(threshold) = threshold {
+        if negative < threshold {
+            rules.push(format!(
+                "The negative points are {negative} (less than {threshold}), \
+                 so all positive points are counted."
+            ));
+        } else if not_counted.is_empty() {
+            rules.push(format!(
+                "The negative points are {negative} (at least {threshold}), but the fruits \
+                 and vegetables earn at least 5 points, so all positive points are counted."
+            ));
+        } else {
+            rules.push(format!(
+                "The negative points are {negative} (at least {threshold}), \
+                 so {} not counted.",
+                list(&not_counted)
+            ));
+        }
+    }
+    rules
+}
+
+fn list(names: &[&str]) -> String {
+    match names {
+        [name] => format!("{name} is"),
+        [init @ .., last] => format!("{} and {last} are", init.join(", ")),
+        [] => String::new(),
+    }
+}
+
+/// A Markdown document describing how the grade came about.
+pub fn markdown(algorithm: Algorithm, product: &Product, score: &Score, letter: char) -> String {
+    let mut out = String::new();
+    let name = product.name.as_deref().unwrap_or("Unnamed product");
+    let _ = writeln!(out, "# Nutri-Score report: {name}\n");
+    let _ = writeln!(out, "**Grade {letter}** with a score of {}\n", score.total);
+    let _ = writeln!(out, "- Algorithm: {algorithm}");
+    let _ = writeln!(out, "- Category: {}\n", product.category);
+
+    let _ = writeln!(out, "## Inputs per 100 g or 100 ml\n");
+    let _ = writeln!(out, "| Nutrient | Value |");
+    let _ = writeln!(out, "| --- | ---: |");
+    for (nutrient, value) in inputs(product) {
+        let _ = writeln!(out, "| {nutrient} | {value} |");
+    }
+
+    let _ = writeln!(out, "\n## Points\n");
+    let _ = writeln!(out, "| Component | Value | Band | Points | Max | Counted |");
+    let _ = writeln!(out, "| --- | ---: | --- | ---: | ---: | --- |");
+    for c in score.negative.iter().chain(&score.positive) {
+        let _ = writeln!(
+            out,
+            "| {} | {:.2} | {} | {} | {} | {} |",
+            c.name,
+            c.value,
+            band(c),
+            c.points,
+            c.max_points,
+            yes_no(c.counted)
+        );
+    }
+    let _ = writeln!(
+        out,
+        "| **Negative** | | | {} | | |",
+        score.negative_points()
+    );
+    let _ = writeln!(
+        out,
+        "| **Positive** | | | {} | | |",
+        score.positive_points()
+    );
+    let _ = writeln!(out, "| **Total** | | | {} | | |", score.total);
+
+    let _ = writeln!(out, "\n## Applied rules\n");
+    for rule in rules(algorithm, product, score) {
+        let _ = writeln!(out, "- {rule}");
+    }
+    let _ = writeln!(out, "\n## Grade\n");
+    let _ = writeln!(
+        out,
+        "The score of {} gives grade **{letter}**.",
+        score.total
+    );
+    out
+}
+
+/// The scores that give each grade, like `3 to 10` for C.
+pub fn grade_thresholds(algorithm: Algorithm, product: &Product) -> Vec<(char, String)> {
+    const SCORES: std::ops::RangeInclusive<isize> = -20..=60;
+    let mut bands: Vec<(char, isize, isize)> = Vec::new();
+    for score in SCORES {
+        let letter = product
+            .category
+            .score_to_letter(algorithm, score, product.is_water());
+        match bands.last_mut() {
+            Some((last, _, upper)) if *last == letter => *upper = score,
+            _ => bands.push((letter, score, score)),
+        }
+    }
+    let last = bands.len() - 1;
+    bands
+        .iter()
+        .enumerate()
+        .map(|(i, &(letter, lower, upper))| {
+            let scores = match (i, i == last) {
+                (0, true) => "any score".into(),
+                (0, false) => format!("{upper} or less"),
+                (_, true) => format!("{lower} or more"),
+                _ if lower == upper => lower.to_string(),
+                _ => format!("{lower} to {upper}"),
+            };
+            (letter, scores)
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "\
+body { font-family: sans-serif; max-width: 60em; margin: 2em auto; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; }
+th { background: #f0f0f0; text-align: left; }
+td.number { text-align: right; }
+td.current { font-weight: bold; outline: 2px solid #222; }
+tr.not-counted { color: #888; }
+.banner { display: flex; align-items: center; gap: 2em; padding: 1em; color: white; }
+.banner h1 { margin: 0; }
+.banner svg { background: white; border-radius: 8px; padding: 4px; height: 6em; width: auto; }
+";
+
+/// A self-contained HTML document with the grade, the breakdown and the tables of cutoffs.
+pub fn html(algorithm: Algorithm, product: &Product, score: &Score, letter: char) -> String {
+    let mut out = String::new();
+    let name = escape(product.name.as_deref().unwrap_or("Unnamed product"));
+    let color = grade_color(letter);
+    let _ = writeln!(out, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>");
+    let _ = writeln!(out, "<meta charset=\"utf-8\">");
+    let _ = writeln!(out, "<title>Nutri-Score report: {name}</title>");
+    let _ = writeln!(out, "<style>\n{STYLE}</style>\n</head>\n<body>");
+    let _ = writeln!(out, "<div class=\"banner\" style=\"background: {color}\">");
+    let _ = writeln!(out, "{}", badge_svg(letter));
+    let _ = writeln!(
+        out,
+        "<div><h1>{name}</h1><p>Grade {letter} with a score of {}, \
+         algorithm {algorithm}, category {}</p></div>\n</div>",
+        score.total, product.category
+    );
+
+    let _ = writeln!(out, "<h2>Inputs per 100 g or 100 ml</h2>\n<table>");
+    let _ = writeln!(out, "<tr><th>Nutrient</th><th>Value</th></tr>");
+    for (nutrient, value) in inputs(product) {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td class=\"number\">{value}</td></tr>",
+            escape(nutrient)
+        );
+    }
+    let _ = writeln!(out, "</table>");
+
+    let _ = writeln!(out, "<h2>Points</h2>\n<table>");
+    let _ = writeln!(
+        out,
+        "<tr><th>Component</th><th>Value</th><th>Band</th><th>Points</th><th>Max</th></tr>"
+    );
+    for c in score.negative.iter().chain(&score.positive) {
+        let class = if c.counted {
+            ""
+        } else {
+            " class=\"not-counted\""
+        };
+        let note = if c.counted { "" } else { " (not counted)" };
+        let _ = writeln!(
+            out,
+            "<tr{class}><td>{}{note}</td><td class=\"number\">{:.2}</td><td>{}</td>\
+             <td class=\"number\">{}</td><td class=\"number\">{}</td></tr>",
+            escape(c.name),
+            c.value,
+            escape(&band(c)),
+            c.points,
+            c.max_points
+        );
+    }
+    for (total, points) in [
+        ("Negative", score.negative_points().to_string()),
+        ("Positive", score.positive_points().to_string()),
+        ("Total", score.total.to_string()),
+    ] {
+        let _ = writeln!(
+            out,
+            "<tr><th>{total}</th><td></td><td></td><td class=\"number\">{points}</td><td></td></tr>"
+        );
+    }
+    let _ = writeln!(out, "</table>");
+
+    let _ = writeln!(out, "<h2>Applied rules</h2>\n<ul>");
+    for rule in rules(algorithm, product, score) {
+        let _ = writeln!(out, "<li>{}</li>", escape(&rule));
+    }
+    let _ = writeln!(out, "</ul>");
+
+    html_cutoffs(&mut out, score);
+    html_grades(&mut out, algorithm, product, letter);
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+/// The cutoffs of every nutrient, with the ones of the product highlighted.
+fn html_cutoffs(out: &mut String, score: &Score) {
+    let _ = writeln!(out, "<h2>Cutoffs</h2>\n<table>");
+    let columns = score
+        .negative
+        .iter()
+        .chain(&score.positive)
+        .map(|c| c.cutoffs.len())
+        .max()
+        .unwrap_or(0);
+    let _ = write!(out, "<tr><th>Points</th>");
+    for points in 0..=columns {
+        let _ = write!(out, "<th>{points}</th>");
+    }
+    let _ = writeln!(out, "</tr>");
+    for c in score.negative.iter().chain(&score.positive) {
+        if c.cutoffs.is_empty() {
+            continue;
+        }
+        let _ = write!(out, "<tr><td>{}</td>", escape(c.name));
+        for points in 0..=c.cutoffs.len() {
+            let class = if points == c.points {
+                " class=\"number current\""
+            } else {
+                " class=\"number\""
+            };
+            let cutoff = c.cutoffs.get(points).map_or_else(
+                || format!("&gt; {}", c.cutoffs[points - 1]),
+                |cutoff| format!("&le; {cutoff}"),
+            );
+            let _ = write!(out, "<td{class}>{cutoff}</td>");
+        }
+        let _ = writeln!(out, "</tr>");
+    }
+    let _ = writeln!(out, "</table>");
+}
+
+fn html_grades(out: &mut String, algorithm: Algorithm, product: &Product, letter: char) {
+    let _ = writeln!(out, "<h2>Grades</h2>\n<table>");
+    let _ = writeln!(out, "<tr><th>Grade</th><th>Score</th></tr>");
+    for (grade, scores) in grade_thresholds(algorithm, product) {
+        let class = if grade == letter {
+            " class=\"current\""
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "<tr><td{class} style=\"background: {}; color: white\">{grade}</td><td>{scores}</td></tr>",
+            grade_color(grade)
+        );
+    }
+    let _ = writeln!(out, "</table>");
+}
+
+fn grade_color(letter: char) -> &'static str {
+    GRADE_COLORS
+        .iter()
+        .find(|(l, _)| *l == letter)
+        .map_or("#888888", |(_, color)| color)
+}
+
+/// The cells of a row of the points table on the PDF page.
+const fn columns<'a>(
+    name: &'a str,
+    value: &'a str,
+    band: &'a str,
+    points: &'a str,
+    max: &'a str,
+) -> [(u32, &'static str, &'a str); 5] {
+    [
+        (40, "start", name),
+        (220, "end", value),
+        (240, "start", band),
+        (420, "end", points),
+        (460, "end", max),
+    ]
+}
+
+/// An A4 page as SVG with the grade, the inputs, the points and the applied rules.
+fn page_svg(algorithm: Algorithm, product: &Product, score: &Score, letter: char) -> String {
+    const FONT: &str = "font-family=\"Arial, Helvetica, DejaVu Sans, sans-serif\"";
+    let mut svg = String::new();
+    let name = escape(product.name.as_deref().unwrap_or("Unnamed product"));
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"595\" height=\"842\" viewBox=\"0 0 595 842\">\n\
+         <rect width=\"595\" height=\"842\" fill=\"#FFFFFF\"/>\n\
+         <rect width=\"595\" height=\"170\" fill=\"{}\"/>",
+        grade_color(letter)
+    );
+    let _ = writeln!(
+        svg,
+        "{}",
+        badge_svg(letter).replacen("<svg ", "<svg x=\"40\" y=\"20\" ", 1)
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"310\" y=\"70\" {FONT} font-size=\"20\" font-weight=\"bold\" fill=\"#FFFFFF\">{name}</text>\n\
+         <text x=\"310\" y=\"95\" {FONT} font-size=\"12\" fill=\"#FFFFFF\">Grade {letter}, score {}</text>\n\
+         <text x=\"310\" y=\"115\" {FONT} font-size=\"12\" fill=\"#FFFFFF\">Algorithm {algorithm}, category {}</text>",
+        score.total, product.category
+    );
+    let mut y = 200;
+    let mut line = |svg: &mut String, cells: &[(u32, &str, &str)], bold: bool| {
+        let weight = if bold { " font-weight=\"bold\"" } else { "" };
+        for (x, anchor, text) in cells {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{x}\" y=\"{y}\" text-anchor=\"{anchor}\" {FONT} font-size=\"10\"{weight}>{}</text>",
+                escape(text)
+            );
+        }
+        y += 15;
+    };
+    line(
+        &mut svg,
+        &[(40, "start", "Inputs per 100 g or 100 ml")],
+        true,
+    );
+    for (nutrient, value) in inputs(product) {
+        line(
+            &mut svg,
+            &[(40, "start", nutrient), (260, "end", &value)],
+            false,
+        );
+    }
+    line(&mut svg, &[], false);
+    line(
+        &mut svg,
+        &columns("Component", "Value", "Band", "Points", "Max"),
+        true,
+    );
+    for c in score.negative.iter().chain(&score.positive) {
+        let name = if c.counted {
+            c.name.to_string()
+        } else {
+            format!("{} (not counted)", c.name)
+        };
+        let value = format!("{:.2}", c.value);
+        let points = c.points.to_string();
+        let max = c.max_points.to_string();
+        let band = band(c);
+        line(
+            &mut svg,
+            &columns(&name, &value, &band, &points, &max),
+            false,
+        );
+    }
+    for (total, points) in [
+        ("Negative", score.negative_points().to_string()),
+        ("Positive", score.positive_points().to_string()),
+        ("Total", score.total.to_string()),
+    ] {
+        line(&mut svg, &columns(total, "", "", &points, ""), true);
+    }
+    line(&mut svg, &[], false);
+    line(&mut svg, &[(40, "start", "Applied rules")], true);
+    for rule in rules(algorithm, product, score) {
+        let mut text = String::from("• ");
+        for word in rule.split(' ') {
+            if text.len() + word.len() > 100 {
+                line(&mut svg, &[(40, "start", &text)], false);
+                text = String::from("   ");
+            }
+            text.push_str(word);
+            text.push(' ');
+        }
+        line(&mut svg, &[(40, "start", &text)], false);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// A one-page PDF with the grade graphic and the breakdown.
+pub fn pdf(
+    algorithm: Algorithm,
+    product: &Product,
+    score: &Score,
+    letter: char,
+) -> io::Result<Vec<u8>> {
+    let tree = badge_file::parse(&page_svg(algorithm, product, score, letter))?;
+    svg2pdf::to_pdf(
+        &tree,
+        svg2pdf::ConversionOptions::default(),
+        svg2pdf::PageOptions::default(),
+    )
+    .map_err(|e| io::Error::other(e.to_string()))
+}
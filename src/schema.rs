@@ -0,0 +1,404 @@
+use nutriscore::{Category, DrinkType, EcoCategory, EcoLabel, Origin, Packaging};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::io;
+use strum::IntoEnumIterator;
+
+/// The names a category can be given with, like `Milk Drinks` and `milk-drinks`.
+fn category_names() -> Vec<String> {
+    let mut names = Vec::new();
+    for category in Category::iter() {
+        let name = category.to_string();
+        let kebab = name.to_lowercase().replace(' ', "-");
+        names.push(name);
+        if !names.contains(&kebab) {
+            names.push(kebab);
+        }
+    }
+    names
+}
+
+/// The kebab-case names of an enum, as it is deserialized.
+fn names<T: IntoEnumIterator + ToString>() -> Vec<String> {
+    T::iter().map(|value| value.to_string()).collect()
+}
+
+/// The JSON Schema of a product as it is read by `jsonl` and `serve`.
+pub fn product() -> Value {
+    let grams = |description: &str| json!({ "type": "number", "minimum": 0, "maximum": 100, "description": description });
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Nutri-Score product",
+        "type": "object",
+        "required": ["category", "nutrition", "fruits"],
+        "properties": {
+            "name": { "type": "string", "description": "Name of the product" },
+            "category": {
+                "enum": category_names(),
+                "description": "The food category, which decides the cutoffs and special rules",
+            },
+            "fruits": {
+                "type": "number",
+                "minimum": 0,
+                "maximum": 100,
+                "description": "Percentage of fruits, vegetables and nuts",
+            },
+            "water": {
+                "type": "boolean",
+                "description": "Whether the product is water, only relevant for drinks",
+            },
+            "drink": {
+                "enum": names::<DrinkType>(),
+                "description": "The kind of drink, plain, mineral and sparkling water always get an A",
+            },
+            "alcohol": {
+                "type": "number",
+                "minimum": 0,
+                "maximum": 100,
+                "description": "Alcohol by volume in %, drinks with more than 1.2% have no Nutri-Score",
+            },
+            "ingredients": {
+                "type": "string",
+                "description": "The ingredient list from the package, for the NOVA group",
+            },
+            "eco": {
+                "type": "object",
+                "description": "What the Eco-Score is calculated from",
+                "required": ["category", "packaging", "origin"],
+                "properties": {
+                    "category": { "enum": names::<EcoCategory>() },
+                    "packaging": { "enum": names::<Packaging>() },
+                    "origin": { "enum": names::<Origin>() },
+                    "labels": { "type": "array", "items": { "enum": names::<EcoLabel>() } },
+                    "palm_oil": {
+                        "type": "boolean",
+                        "description": "Whether it contains palm oil not certified as sustainable",
+                    },
+                },
+            },
+            "algorithm": {
+                "enum": ["2017", "2023"],
+                "description": "Version of the algorithm, overrides --algorithm",
+            },
+            "nutrition": {
+                "type": "object",
+                "description": "Nutritional values per 100g or 100ml",
+                "required": [
+                    "energy", "fat", "saturated_fats", "sugar", "proteins", "salt", "fibers"
+                ],
+                "properties": {
+                    "energy": {
+                        "type": "number",
+                        "minimum": 0,
+                        "maximum": 3800,
+                        "description": "Energy in kJ",
+                    },
+                    "fat": grams("Fats in g"),
+                    "saturated_fats": grams("Saturated fats in g"),
+                    "sugar": grams("Sugar in g"),
+                    "carbohydrates": grams("Carbohydrates in g including the sugar, only checked"),
+                    "proteins": grams("Proteins in g"),
+                    "salt": grams("Salt in g"),
+                    "fibers": grams("Fibers in g"),
+                    "contains_sweeteners": {
+                        "type": "boolean",
+                        "description": "Whether non-nutritive sweeteners were added",
+                    },
+                },
+            },
+        },
+    })
+}
+
+/// The schemas of the requests and responses of `serve`.
+fn schemas(product: &Value) -> Value {
+    json!({
+        "Product": product,
+        "Result": {
+            "type": "object",
+            "required": ["score", "grade", "negative", "positive"],
+            "properties": {
+                "score": { "type": "integer", "description": "The total score, lower is better" },
+                "grade": { "enum": ["A", "B", "C", "D", "E"] },
+                "negative": {
+                    "type": "array",
+                    "items": { "$ref": "#/components/schemas/Component" },
+                    "description": "Energy, sugar, saturated fats, sodium or salt and sweeteners",
+                },
+                "positive": {
+                    "type": "array",
+                    "items": { "$ref": "#/components/schemas/Component" },
+                    "description": "Fruits & vegetables, fibers and protein",
+                },
+            },
+        },
+        "Component": {
+            "type": "object",
+            "required": ["name", "value", "points", "max_points", "counted"],
+            "properties": {
+                "name": { "type": "string", "description": "Name of the nutrient" },
+                "value": { "type": "number", "description": "The value the points were looked up with" },
+                "points": { "type": "integer", "minimum": 0 },
+                "max_points": { "type": "integer", "minimum": 0 },
+                "lower": { "type": ["number", "null"], "description": "The cutoff the value is above" },
+                "upper": { "type": ["number", "null"], "description": "The cutoff the value is at or below" },
+                "counted": { "type": "boolean", "description": "Whether the points are part of the score" },
+            },
+        },
+        "NotApplicable": {
+            "type": "object",
+            "required": ["applicable"],
+            "properties": {
+                "applicable": { "const": false },
+                "alcohol": { "type": ["number", "null"], "description": "Alcohol by volume in %" },
+            },
+        },
+        "Error": {
+            "type": "object",
+            "required": ["error"],
+            "properties": { "error": { "type": "string" } },
+        },
+    })
+}
+
+/// The path of `/score/batch` in the `OpenAPI` document.
+fn batch() -> Value {
+    let product = json!({ "$ref": "#/components/schemas/Product" });
+    let result = json!({
+        "oneOf": [
+            { "$ref": "#/components/schemas/Result" },
+            { "$ref": "#/components/schemas/NotApplicable" },
+            { "$ref": "#/components/schemas/Error" },
+        ],
+    });
+    json!({
+        "post": {
+            "summary": "Score several products",
+            "description": "The products are a JSON array or one JSON document per line, and the \
+                results are in the same order and format. A product that is not valid gets an \
+                error without failing the others.",
+            "operationId": "scoreBatch",
+            "requestBody": {
+                "required": true,
+                "content": {
+                    "application/json": { "schema": { "type": "array", "items": product } },
+                    "application/x-ndjson": { "schema": product },
+                },
+            },
+            "responses": {
+                "200": {
+                    "description": "One result, not applicable or error for every product",
+                    "content": {
+                        "application/json": { "schema": { "type": "array", "items": result } },
+                        "application/x-ndjson": { "schema": result },
+                    },
+                },
+                "400": {
+                    "description": "The body is not a JSON array",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } },
+                },
+                "413": {
+                    "description": "The body is larger than 10 MiB",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } },
+                },
+                "405": {
+                    "description": "Another method than POST",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } },
+                },
+            },
+        },
+    })
+}
+
+fn report() -> Value {
+    let error = |description: &str| {
+        json!({
+            "description": description,
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } },
+        })
+    };
+    json!({
+        "get": {
+            "summary": "Show the report of a product",
+            "description": "The page the QR codes of `nutriscore --qr-url` link to.",
+            "operationId": "report",
+            "security": [{}],
+            "parameters": [{
+                "name": "product",
+                "in": "query",
+                "required": true,
+                "description": "The product as JSON, like the body of /score",
+                "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Product" } } },
+            }],
+            "responses": {
+                "200": {
+                    "description": "The HTML report with the grade and the points of every nutrient",
+                    "content": { "text/html": { "schema": { "type": "string" } } },
+                },
+                "400": error("The product is missing or not valid, or the Nutri-Score does not apply to it"),
+                "405": error("Another method than GET"),
+            },
+        },
+    })
+}
+
+fn graphql() -> Value {
+    json!({
+        "post": {
+            "summary": "Answer a GraphQL query, see `nutriscore schema --graphql`",
+            "operationId": "graphql",
+            "requestBody": {
+                "required": true,
+                "content": { "application/json": { "schema": {
+                    "type": "object",
+                    "required": ["query"],
+                    "properties": {
+                        "query": { "type": "string" },
+                        "operationName": { "type": ["string", "null"] },
+                        "variables": { "type": ["object", "null"] },
+                    },
+                } } },
+            },
+            "responses": {
+                "200": {
+                    "description": "The data, and the errors of the fields that failed",
+                    "content": { "application/json": { "schema": { "type": "object" } } },
+                },
+                "400": {
+                    "description": "The query is not valid",
+                    "content": { "application/json": { "schema": { "type": "object" } } },
+                },
+                "413": {
+                    "description": "The body is larger than 10 MiB",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } },
+                },
+            },
+        },
+    })
+}
+
+/// The ways to send the API keys, which servers started without keys do not require.
+fn security_schemes() -> Value {
+    json!({
+        "bearer": { "type": "http", "scheme": "bearer" },
+        "apiKey": { "type": "apiKey", "in": "header", "name": "X-API-Key" },
+    })
+}
+
+/// The `OpenAPI` document of `serve`, with the product of [`product`] as the request.
+pub fn openapi() -> Value {
+    let mut product = product();
+    if let Some(product) = product.as_object_mut() {
+        product.remove("$schema");
+    }
+    let json = |schema: &str| json!({ "application/json": { "schema": { "$ref": format!("#/components/schemas/{schema}") } } });
+    let error = |description: &str| json!({ "description": description, "content": json("Error") });
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "nutriscore",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Calculates the Nutri-Score of food products",
+        },
+        "paths": {
+            "/score": {
+                "post": {
+                    "summary": "Score a product",
+                    "operationId": "score",
+                    "requestBody": { "required": true, "content": json("Product") },
+                    "responses": {
+                        "200": {
+                            "description": "The score, or that the Nutri-Score does not apply to the product",
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "oneOf": [
+                                            { "$ref": "#/components/schemas/Result" },
+                                            { "$ref": "#/components/schemas/NotApplicable" },
+                                        ],
+                                    },
+                                },
+                            },
+                        },
+                        "400": error("The body is not a valid product, with the path of every invalid value"),
+                        "405": error("Another method than POST"),
+                        "413": error("The body is larger than 10 MiB"),
+                    },
+                },
+            },
+            "/score/batch": batch(),
+            "/report": report(),
+            "/graphql": graphql(),
+            "/metrics": {
+                "get": {
+                    "summary": "Metrics for Prometheus",
+                    "operationId": "metrics",
+                    "responses": {
+                        "200": {
+                            "description": "The metrics in the text exposition format",
+                            "content": { "text/plain": { "schema": { "type": "string" } } },
+                        },
+                    },
+                },
+            },
+            "/openapi.json": {
+                "get": {
+                    "summary": "This document",
+                    "operationId": "openapi",
+                    "responses": {
+                        "200": {
+                            "description": "The OpenAPI document",
+                            "content": { "application/json": { "schema": { "type": "object" } } },
+                        },
+                    },
+                },
+            },
+        },
+        "security": [{}, { "bearer": [] }, { "apiKey": [] }],
+        "components": {
+            "schemas": schemas(&product),
+            "securitySchemes": security_schemes(),
+        },
+    })
+}
+
+/// Validates JSON documents against the schema of a product before deserializing them.
+pub struct Validator(jsonschema::Validator);
+
+impl Validator {
+    pub fn new() -> Self {
+        Self(jsonschema::validator_for(&product()).expect("the product schema is valid"))
+    }
+
+    /// Parses a JSON document, with an error that names the path of every invalid value.
+    pub fn parse<T: DeserializeOwned>(&self, text: &str) -> Result<T, String> {
+        self.parse_value(serde_json::from_str(text).map_err(|e| e.to_string())?)
+    }
+
+    /// Deserializes a JSON value like [`Validator::parse`].
+    pub fn parse_value<T: DeserializeOwned>(&self, value: Value) -> Result<T, String> {
+        let errors: Vec<String> = self
+            .0
+            .iter_errors(&value)
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                if path.is_empty() {
+                    e.to_string()
+                } else {
+                    format!("{path}: {e}")
+                }
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors.join("; "));
+        }
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+}
+
+/// Prints the JSON Schema of a product, or the `OpenAPI` document of `serve`.
+pub fn run(openapi: bool) -> io::Result<()> {
+    let document = if openapi { self::openapi() } else { product() };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
@@ -0,0 +1,301 @@
+use crate::{cache, error, lookup, Format, TextOptions};
+use clap::Args;
+use console::Term;
+use dialoguer::Select;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use nutriscore::off::{Nutriments, Product};
+use nutriscore::{Algorithm, Category};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+
+/// The flags of `search`.
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    /// Part of the name or brand, letters may be left out, like "nutela ferero"
+    query: String,
+    /// Also search this Open Food Facts CSV export, en.openfoodfacts.org.products.csv
+    #[clap(long, value_name = "FILE")]
+    dump: Option<PathBuf>,
+    /// Show at most this many matches
+    #[clap(long, default_value_t = 10)]
+    limit: usize,
+    /// Score the picked product in this category instead of the suggested one
+    #[clap(long)]
+    category: Option<Category>,
+}
+
+/// A product that matches the query.
+pub struct Hit<T> {
+    pub score: i64,
+    pub barcode: String,
+    pub product: T,
+}
+
+/// The best hits, with the earlier one first if two match equally well.
+struct Best<T> {
+    limit: usize,
+    hits: Vec<Hit<T>>,
+}
+
+impl<T> Best<T> {
+    const fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            hits: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, hit: Hit<T>) {
+        self.hits.push(hit);
+        // sorting only now and then keeps this linear in the number of products
+        if self.hits.len() >= 2 * self.limit.max(1) {
+            self.truncate();
+        }
+    }
+
+    fn truncate(&mut self) {
+        self.hits.sort_by_key(|hit| Reverse(hit.score));
+        self.hits.truncate(self.limit);
+    }
+
+    fn into_hits(mut self) -> Vec<Hit<T>> {
+        self.truncate();
+        self.hits
+    }
+}
+
+/// The name and brands of a product, which the query is matched against.
+fn label(name: Option<&str>, brands: Option<&str>) -> String {
+    match (name, brands.filter(|brands| !brands.is_empty())) {
+        (Some(name), Some(brands)) => format!("{name} - {brands}"),
+        (Some(name), None) => name.to_owned(),
+        (None, Some(brands)) => brands.to_owned(),
+        (None, None) => String::new(),
+    }
+}
+
+/// The products of the cache that match, the barcode of a key is the part after the URL of the
+/// instance.
+fn search_cache(matcher: &SkimMatcherV2, query: &str, best: &mut Best<Product>) -> io::Result<()> {
+    for (key, product) in cache::entries::<Product>("off")? {
+        let text = label(product.name.as_deref(), product.brands.as_deref());
+        if let Some(score) = matcher.fuzzy_match(&text, query) {
+            let barcode = key.rsplit('/').next().unwrap_or(&key).to_owned();
+            best.push(Hit {
+                score,
+                barcode,
+                product,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The columns of the Open Food Facts CSV export that products are read from.
+pub struct Export {
+    headers: csv::StringRecord,
+    code: usize,
+    name: Option<usize>,
+    brands: Option<usize>,
+    categories: Option<usize>,
+    grade: Option<usize>,
+}
+
+impl Export {
+    /// Opens the export, the rows that cannot be read like ones with invalid UTF-8 are skipped.
+    pub fn open(
+        path: &Path,
+    ) -> io::Result<(Self, impl Iterator<Item = io::Result<csv::StringRecord>>)> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .quoting(false)
+            .flexible(true)
+            .from_reader(BufReader::new(File::open(path)?));
+        let headers = reader.headers()?.clone();
+        let find = |name: &str| headers.iter().position(|h| h == name);
+        let export = Self {
+            code: find("code").ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "no code column, is this the Open Food Facts CSV export?",
+                )
+            })?,
+            name: find("product_name"),
+            brands: find("brands"),
+            categories: find("categories_tags"),
+            grade: find("nutriscore_grade"),
+            headers,
+        };
+        let records = reader.into_records().filter_map(|record| match record {
+            Ok(record) => Some(Ok(record)),
+            Err(e) if e.is_io_error() => Some(Err(e.into())),
+            Err(_) => None,
+        });
+        Ok((export, records))
+    }
+
+    fn field(record: &csv::StringRecord, column: Option<usize>) -> Option<String> {
+        column
+            .and_then(|column| record.get(column))
+            .filter(|value| !value.is_empty())
+            .map(str::to_owned)
+    }
+
+    pub fn barcode<'a>(&self, record: &'a csv::StringRecord) -> &'a str {
+        record.get(self.code).unwrap_or_default()
+    }
+
+    /// The name and brands of a row, like [`label`].
+    fn label(&self, record: &csv::StringRecord) -> String {
+        let get = |column: Option<usize>| column.and_then(|column| record.get(column));
+        label(get(self.name), get(self.brands))
+    }
+
+    pub fn product(&self, record: &csv::StringRecord) -> io::Result<Product> {
+        let nutriments: Nutriments = record.deserialize(Some(&self.headers))?;
+        Ok(Product {
+            name: Self::field(record, self.name),
+            brands: Self::field(record, self.brands),
+            nutriments,
+            categories_tags: Self::field(record, self.categories)
+                .map(|tags| tags.split(',').map(str::to_owned).collect())
+                .unwrap_or_default(),
+            nutriscore_grade: Self::field(record, self.grade),
+        })
+    }
+}
+
+/// The products of the CSV export that match. Only the rows of the best matches are turned into
+/// products, so a search over the whole export stays fast.
+fn search_dump(
+    matcher: &SkimMatcherV2,
+    query: &str,
+    path: &Path,
+    limit: usize,
+) -> io::Result<Vec<Hit<Product>>> {
+    let (export, records) = Export::open(path)?;
+    let mut best = Best::new(limit);
+    for record in records {
+        let record = record?;
+        if let Some(score) = matcher.fuzzy_match(&export.label(&record), query) {
+            best.push(Hit {
+                score,
+                barcode: export.barcode(&record).to_owned(),
+                product: record,
+            });
+        }
+    }
+    best.into_hits()
+        .into_iter()
+        .map(|hit| {
+            Ok(Hit {
+                score: hit.score,
+                product: export.product(&hit.product)?,
+                barcode: hit.barcode,
+            })
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct JsonHit<'a> {
+    barcode: &'a str,
+    product_name: Option<&'a str>,
+    brands: Option<&'a str>,
+    off_grade: Option<&'a str>,
+}
+
+/// Searches the products of the cache and of the export by name and brand. On a terminal the
+/// matches can be picked from to score one and return its grade, otherwise they are listed.
+pub fn run(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    args: &SearchArgs,
+) -> io::Result<Option<char>> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut best = Best::new(args.limit);
+    search_cache(&matcher, &args.query, &mut best)?;
+    if let Some(dump) = &args.dump {
+        for hit in search_dump(&matcher, &args.query, dump, args.limit)? {
+            best.push(hit);
+        }
+    }
+    let mut seen = HashSet::new();
+    let mut hits = best.into_hits();
+    // a product from the cache comes before the same one from the export
+    hits.retain(|hit| seen.insert(hit.barcode.clone()));
+    if hits.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no product matches {:?}, look products up first or pass --dump",
+                args.query
+            ),
+        ));
+    }
+    pick(algorithm, format, text, &hits, args.category)
+}
+
+/// Lets the user pick one of the products on a terminal to score it and returns its grade.
+/// Otherwise, and in JSON, the products are listed.
+pub fn pick(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    hits: &[Hit<Product>],
+    category: Option<Category>,
+) -> io::Result<Option<char>> {
+    let labels: Vec<String> = hits
+        .iter()
+        .map(|hit| {
+            let product = &hit.product;
+            let label = label(product.name.as_deref(), product.brands.as_deref());
+            format!("{label} ({})", hit.barcode)
+        })
+        .collect();
+    if format == Format::Json {
+        let json: Vec<JsonHit> = hits
+            .iter()
+            .map(|hit| JsonHit {
+                barcode: &hit.barcode,
+                product_name: hit.product.name.as_deref(),
+                brands: hit.product.brands.as_deref(),
+                off_grade: hit.product.nutriscore_grade.as_deref(),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(None);
+    }
+    if !(Term::stdout().is_term() && Term::stderr().is_term()) {
+        for label in labels {
+            println!("{label}");
+        }
+        return Ok(None);
+    }
+    let prompt = "Product to score";
+    let Some(index) = Select::new()
+        .with_prompt(prompt)
+        .items(&labels)
+        .default(0)
+        .interact_opt()
+        .map_err(error::terminal(prompt))?
+    else {
+        return Ok(None);
+    };
+    let hit = &hits[index];
+    lookup::show(
+        algorithm,
+        format,
+        text,
+        &hit.barcode,
+        &hit.product,
+        category,
+    )
+    .map(Some)
+}
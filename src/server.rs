@@ -0,0 +1,428 @@
+use crate::auth::{self, Denied, Keys};
+use crate::cache::{Cache, CacheArgs};
+use crate::config::Config;
+use crate::graphql;
+use crate::lookup::OffArgs;
+use crate::metrics::Metrics;
+use crate::report;
+use crate::schema::{self, Validator};
+use crate::JsonResult;
+use clap::Args;
+use nutriscore::{Algorithm, Product};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt::Write;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+use tiny_http::{Header, Method, Request, Response, Server, SslConfig};
+
+/// The largest body that is read, larger ones are answered with 413.
+const MAX_BODY: usize = 10 * 1024 * 1024;
+
+#[derive(Deserialize)]
+struct ScoreRequest {
+    #[serde(flatten)]
+    product: Product,
+    algorithm: Option<Algorithm>,
+}
+
+struct Reply {
+    status: u16,
+    body: String,
+    content_type: &'static str,
+    /// The grades that were returned, for the metrics.
+    grades: Vec<(Algorithm, char)>,
+}
+
+impl Reply {
+    fn json<T: serde::Serialize>(value: &T) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_string(value).expect("the responses have string keys"),
+            content_type: "application/json",
+            grades: Vec::new(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+            content_type: "application/json",
+            grades: Vec::new(),
+        }
+    }
+}
+
+/// The paths of the server, and `other` for the rest so that the metrics stay small.
+fn known_path(path: &str) -> &'static str {
+    match path {
+        "/score" => "/score",
+        "/score/batch" => "/score/batch",
+        "/graphql" => "/graphql",
+        "/metrics" => "/metrics",
+        "/openapi.json" => "/openapi.json",
+        "/report" => "/report",
+        _ => "other",
+    }
+}
+
+/// The JSON answer of `/score` to a product, with its grade if it has one.
+fn result(algorithm: Algorithm, request: &ScoreRequest) -> (String, Option<(Algorithm, char)>) {
+    let algorithm = request.algorithm.unwrap_or(algorithm);
+    if !request.product.is_applicable() {
+        let json = serde_json::json!({
+            "applicable": false,
+            "alcohol": request.product.alcohol,
+        });
+        return (json.to_string(), None);
+    }
+    let score = request.product.score(algorithm);
+    let grade = request.product.letter(algorithm, &score);
+    let json = serde_json::to_string(&JsonResult {
+        score: score.total,
+        grade,
+        negative: &score.negative,
+        positive: &score.positive,
+    })
+    .expect("the results have string keys");
+    (json, Some((algorithm, grade)))
+}
+
+fn score(algorithm: Algorithm, validator: &Validator, body: &str) -> Reply {
+    let request: ScoreRequest = match validator.parse(body) {
+        Ok(request) => request,
+        Err(e) => return Reply::error(400, &e),
+    };
+    let (body, grade) = result(algorithm, &request);
+    Reply {
+        status: 200,
+        body,
+        content_type: "application/json",
+        grades: grade.into_iter().collect(),
+    }
+}
+
+/// Scores a JSON array of products, or one product per line, and answers in the same format.
+/// Products that are not valid get an `error` instead of failing the others.
+fn batch(algorithm: Algorithm, validator: &Validator, body: &str) -> Reply {
+    let ndjson = !body.trim_start().starts_with('[');
+    let items: Vec<Result<ScoreRequest, String>> = if ndjson {
+        body.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| validator.parse(line))
+            .collect()
+    } else {
+        match serde_json::from_str::<Vec<Value>>(body) {
+            Ok(values) => values
+                .into_iter()
+                .map(|value| validator.parse_value(value))
+                .collect(),
+            Err(e) => return Reply::error(400, &e.to_string()),
+        }
+    };
+    let mut grades = Vec::new();
+    let results: Vec<String> = items
+        .into_iter()
+        .map(|item| match item {
+            Ok(request) => {
+                let (json, grade) = result(algorithm, &request);
+                grades.extend(grade);
+                json
+            }
+            Err(e) => serde_json::json!({ "error": e }).to_string(),
+        })
+        .collect();
+    let (body, content_type) = if ndjson {
+        let mut body = String::new();
+        for json in results {
+            let _ = writeln!(body, "{json}");
+        }
+        (body, "application/x-ndjson")
+    } else {
+        (format!("[{}]", results.join(",")), "application/json")
+    };
+    Reply {
+        status: 200,
+        body,
+        content_type,
+        grades,
+    }
+}
+
+/// The HTML report of the product in the `product` parameter of the query, the JSON of `/score`.
+/// The QR codes of `--qr-url` link here, so that the result opens in the browser of a phone.
+fn report(algorithm: Algorithm, validator: &Validator, query: &str) -> Reply {
+    let Some((_, json)) =
+        form_urlencoded::parse(query.as_bytes()).find(|(key, _)| key == "product")
+    else {
+        return Reply::error(400, "the product parameter is missing");
+    };
+    let request: ScoreRequest = match validator.parse(&json) {
+        Ok(request) => request,
+        Err(e) => return Reply::error(400, &e),
+    };
+    let algorithm = request.algorithm.unwrap_or(algorithm);
+    let product = &request.product;
+    if !product.is_applicable() {
+        return Reply::error(400, "the Nutri-Score does not apply to alcoholic drinks");
+    }
+    let score = product.score(algorithm);
+    let grade = product.letter(algorithm, &score);
+    Reply {
+        status: 200,
+        body: report::html(algorithm, product, &score, grade),
+        content_type: "text/html; charset=utf-8",
+        grades: vec![(algorithm, grade)],
+    }
+}
+
+/// What every request is answered with.
+struct State {
+    validator: Validator,
+    schema: graphql::Schema,
+    /// With the default algorithm.
+    context: graphql::Context,
+}
+
+fn graphql(state: &State, body: &str) -> Reply {
+    let (ok, body, grades) = graphql::execute(&state.schema, &state.context, body);
+    Reply {
+        status: if ok { 200 } else { 400 },
+        body,
+        content_type: "application/json",
+        grades,
+    }
+}
+
+fn route(state: &State, metrics: &Metrics, method: &Method, url: &str, body: &str) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    match (method, path) {
+        (Method::Post, "/score") => score(state.context.algorithm, &state.validator, body),
+        (Method::Post, "/score/batch") => batch(state.context.algorithm, &state.validator, body),
+        (Method::Post, "/graphql") => graphql(state, body),
+        (Method::Get, "/openapi.json") => Reply::json(&schema::openapi()),
+        (Method::Get, "/report") => report(state.context.algorithm, &state.validator, query),
+        (Method::Get, "/metrics") => Reply {
+            status: 200,
+            body: metrics.render(&state.context.cache),
+            content_type: "text/plain; version=0.0.4",
+            grades: Vec::new(),
+        },
+        (_, "/score" | "/score/batch" | "/graphql" | "/metrics" | "/openapi.json" | "/report") => {
+            Reply::error(405, "method not allowed")
+        }
+        _ => Reply::error(404, "not found"),
+    }
+}
+
+/// The value of a header of the request.
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+/// Checks the API key of a request, with the header to send along with the error.
+///
+/// `GET /report` needs no key, since the QR codes that link to it are opened by phones that
+/// cannot send one. It only renders the product in its URL.
+fn authorize(
+    keys: Option<&mut Keys>,
+    request: &Request,
+    path: &str,
+) -> Result<(), (Reply, Header)> {
+    let Some(keys) = keys else {
+        return Ok(());
+    };
+    if *request.method() == Method::Get && path == "/report" {
+        return Ok(());
+    }
+    let secret = auth::secret(
+        header(request, "Authorization"),
+        header(request, "X-API-Key"),
+    );
+    match keys.check(secret) {
+        Ok(name) => {
+            tracing::Span::current().record("key", name);
+            Ok(())
+        }
+        Err(denied) => {
+            let (status, header) = match denied {
+                Denied::Unauthorized => (401, ("WWW-Authenticate", "Bearer".to_owned())),
+                Denied::RateLimited(wait) => {
+                    (429, ("Retry-After", auth::retry_after(wait).to_string()))
+                }
+            };
+            let header = Header::from_bytes(header.0, header.1).expect("the header is valid");
+            Err((Reply::error(status, &denied.message()), header))
+        }
+    }
+}
+
+fn handle(
+    state: &State,
+    keys: Option<&mut Keys>,
+    metrics: &mut Metrics,
+    mut request: Request,
+) -> io::Result<()> {
+    let start = Instant::now();
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        path,
+        key = tracing::field::Empty
+    );
+    let _entered = span.enter();
+    let mut body = String::new();
+    let mut extra = None;
+    let reply = match authorize(keys, &request, &path) {
+        Err((reply, header)) => {
+            extra = Some(header);
+            reply
+        }
+        Ok(()) => match request
+            .as_reader()
+            .take(MAX_BODY as u64 + 1)
+            .read_to_string(&mut body)
+        {
+            Ok(_) if body.len() > MAX_BODY => Reply::error(413, "the body is larger than 10 MiB"),
+            Ok(_) => route(state, metrics, request.method(), request.url(), &body),
+            Err(e) => Reply::error(400, &e.to_string()),
+        },
+    };
+    let elapsed = start.elapsed().as_secs_f64();
+    let elapsed_ms = elapsed * 1000.0;
+    let method = request.method().to_string();
+    metrics.request(&method, known_path(&path), reply.status, elapsed);
+    for &(algorithm, grade) in &reply.grades {
+        metrics.grade(algorithm, grade);
+    }
+    if (200..300).contains(&reply.status) {
+        tracing::info!(status = reply.status, elapsed_ms, "responded");
+    } else {
+        tracing::warn!(
+            status = reply.status,
+            elapsed_ms,
+            body = reply.body,
+            "responded"
+        );
+    }
+    let content_type =
+        Header::from_bytes("Content-Type", reply.content_type).expect("the header is valid");
+    let mut response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(content_type);
+    if let Some(header) = extra {
+        response.add_header(header);
+    }
+    request.respond(response)
+}
+
+/// The flags of `serve`.
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// The address to listen on
+    #[clap(long, default_value = "127.0.0.1")]
+    host: String,
+    /// The port to listen on
+    #[clap(long, default_value_t = 8080)]
+    port: u16,
+    /// Serve HTTPS with the certificate chain in this PEM file
+    #[clap(long, value_name = "FILE", requires = "tls-key")]
+    tls_cert: Option<PathBuf>,
+    /// The private key of the certificate as PEM
+    #[clap(long, value_name = "FILE", requires = "tls-cert")]
+    tls_key: Option<PathBuf>,
+    /// Serve the gRPC service of proto/nutriscore.proto instead (needs --features grpc)
+    #[clap(long)]
+    grpc: bool,
+    /// Require one of the API keys in this file, one per line with optional requests per minute
+    #[clap(long, value_name = "FILE")]
+    api_keys: Option<PathBuf>,
+    /// Allow each API key at most this many requests per minute, 0 for no limit
+    #[clap(long, value_name = "REQUESTS", default_value_t = 0)]
+    key_rate_limit: u32,
+    #[clap(flatten)]
+    off: OffArgs,
+    #[clap(flatten)]
+    cache: CacheArgs,
+}
+
+#[cfg(feature = "grpc")]
+fn grpc(
+    context: graphql::Context,
+    keys: Option<Keys>,
+    address: (&str, u16),
+    tls: Option<(Vec<u8>, Vec<u8>)>,
+) -> io::Result<()> {
+    crate::grpc::run(context, keys, address, tls)
+}
+
+#[cfg(not(feature = "grpc"))]
+fn grpc(
+    _: graphql::Context,
+    _: Option<Keys>,
+    _: (&str, u16),
+    _: Option<(Vec<u8>, Vec<u8>)>,
+) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this build has no gRPC server, build it with --features grpc",
+    ))
+}
+
+/// Answers requests until the process is stopped, over HTTPS if a certificate is given.
+pub fn run(algorithm: Algorithm, config: &Config, args: ServeArgs) -> io::Result<()> {
+    let (host, port) = (args.host.as_str(), args.port);
+    let tls = match args.tls_cert.as_deref().zip(args.tls_key.as_deref()) {
+        Some((certificate, private_key)) => Some((fs::read(certificate)?, fs::read(private_key)?)),
+        None => None,
+    };
+    let context = graphql::Context {
+        algorithm,
+        client: args.off.client(config)?,
+        cache: Cache::new(&args.cache, config.cache_ttl.as_deref())?,
+        grades: Mutex::default(),
+    };
+    let mut keys = Keys::load(config, args.api_keys.as_deref(), args.key_rate_limit)?;
+    if args.grpc {
+        return grpc(context, keys, (host, port), tls);
+    }
+    let (server, scheme) = match tls {
+        Some((certificate, private_key)) => {
+            let config = SslConfig {
+                certificate,
+                private_key,
+            };
+            (Server::https((host, port), config), "https")
+        }
+        None => (Server::http((host, port)), "http"),
+    };
+    let server = server.map_err(io::Error::other)?;
+    eprintln!("Listening on {scheme}://{host}:{port}");
+    tracing::info!(host, port, %algorithm, "listening");
+    let state = State {
+        validator: Validator::new(),
+        schema: graphql::schema(),
+        context,
+    };
+    let mut metrics = Metrics::default();
+    for request in server.incoming_requests() {
+        if let Err(e) = handle(&state, keys.as_mut(), &mut metrics, request) {
+            tracing::error!(error = %e, "could not respond");
+        }
+    }
+    Ok(())
+}
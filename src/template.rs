@@ -0,0 +1,68 @@
+use handlebars::Handlebars;
+use nutriscore::{Algorithm, Component, Nutrition, Product, Score};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The variables of a template.
+#[derive(Serialize)]
+struct Data<'a> {
+    name: Option<&'a str>,
+    category: String,
+    fruits: f32,
+    water: bool,
+    drink: Option<String>,
+    nutrition: &'a Nutrition,
+    algorithm: Algorithm,
+    score: isize,
+    grade: char,
+    negative_points: usize,
+    positive_points: usize,
+    negative: &'a [Component],
+    positive: &'a [Component],
+}
+
+/// Whether the template produces HTML, by the extension before .hbs, like result.html.hbs.
+fn is_html(path: &Path) -> bool {
+    let inner = Path::new(path.file_stem().unwrap_or_default()).extension();
+    inner.is_some_and(|e| e.eq_ignore_ascii_case("html") || e.eq_ignore_ascii_case("htm"))
+}
+
+/// Renders the Handlebars template in `path` with the product and its result. Values are only
+/// escaped in HTML templates, and unknown variables are errors.
+pub fn render(
+    path: &Path,
+    algorithm: Algorithm,
+    product: &Product,
+    score: &Score,
+    letter: char,
+) -> io::Result<String> {
+    let template = fs::read_to_string(path)?;
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    if !is_html(path) {
+        handlebars.register_escape_fn(handlebars::no_escape);
+    }
+    let data = Data {
+        name: product.name.as_deref(),
+        category: product.category.to_string(),
+        fruits: product.fruits,
+        water: product.is_water(),
+        drink: product.drink.map(|d| d.to_string()),
+        nutrition: &product.nutrition,
+        algorithm,
+        score: score.total,
+        grade: letter,
+        negative_points: score.negative_points(),
+        positive_points: score.positive_points(),
+        negative: &score.negative,
+        positive: &score.positive,
+    };
+    handlebars.render_template(&template, &data).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {e}", path.display()),
+        )
+    })
+}
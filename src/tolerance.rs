@@ -0,0 +1,385 @@
+use crate::{Algorithm, Nutrition, Product};
+use serde::Serialize;
+
+/// The share of the samples that need the grade of the declared values for it to be stable.
+const STABLE_SHARE: f32 = 0.95;
+
+/// How much a declared value may differ from the one measured in the product.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tolerance {
+    /// An amount in the unit of the value, like ±2 g.
+    Amount(f32),
+    /// A percentage of the value, like ±20 %.
+    Percent(f32),
+}
+
+impl Tolerance {
+    fn of(self, value: f32) -> f32 {
+        match self {
+            Self::Amount(amount) => amount.abs(),
+            Self::Percent(percent) => (value * percent / 100.0).abs(),
+        }
+    }
+
+    /// The tolerance of sugars, protein and fibers in the EU guidance document, ±2 g below 10 g,
+    /// ±20 % up to 40 g and ±8 g above.
+    fn eu_grams(value: f32) -> Self {
+        match value {
+            v if v < 10.0 => Self::Amount(2.0),
+            v if v <= 40.0 => Self::Percent(20.0),
+            _ => Self::Amount(8.0),
+        }
+    }
+}
+
+/// The tolerances of the values of a product, `None` for the EU defaults.
+///
+/// The defaults are the ones of the EU guidance document for the control of nutrition labels
+/// from 2012, like ±2 g for less than 10 g of sugar. It has none for the energy, which gets
+/// ±20 % like the nutrients in the middle of their ranges, nor for the fruits and vegetables,
+/// which get none.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Tolerances {
+    pub energy: Option<Tolerance>,
+    pub fat: Option<Tolerance>,
+    pub saturated_fats: Option<Tolerance>,
+    pub sugar: Option<Tolerance>,
+    pub proteins: Option<Tolerance>,
+    pub salt: Option<Tolerance>,
+    pub fibers: Option<Tolerance>,
+    pub fruits: Option<Tolerance>,
+}
+
+/// The share of the samples that got a grade, see [`uncertainty`].
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct GradeShare {
+    pub grade: char,
+    /// Between 0 and 1.
+    pub share: f32,
+}
+
+/// The grades a product can get when its values are off by up to their tolerances.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Uncertainty {
+    /// The number of products with random values the grades were estimated from.
+    pub samples: usize,
+    /// The grade of the declared values.
+    pub grade: char,
+    /// The lowest and the highest score within the tolerances.
+    pub best: isize,
+    pub worst: isize,
+    /// The grades of the best and the worst score.
+    pub best_grade: char,
+    pub worst_grade: char,
+    /// The grades of the samples from A to E, leaving out the ones no sample got.
+    pub grades: Vec<GradeShare>,
+    /// Whether at least 95 % of the samples got the grade of the declared values.
+    pub stable: bool,
+}
+
+/// The random numbers of the samples, the same for every run so that results can be compared.
+struct SplitMix(u64);
+
+impl SplitMix {
+    /// A number in `[-1, 1]`.
+    fn next(&mut self) -> f32 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        // the 24 bits that fit into the mantissa of an f32
+        #[allow(clippy::cast_precision_loss)]
+        let unit = (z >> 40) as f32 / (1 << 24) as f32;
+        unit.mul_add(2.0, -1.0)
+    }
+}
+
+/// The values of the product and their tolerances, with whether more of it means more points.
+fn ranges(product: &Product, tolerances: &Tolerances) -> [(f32, f32, bool); 8] {
+    let n = &product.nutrition;
+    let or = |tolerance: Option<Tolerance>, default: Tolerance, value: f32| {
+        tolerance.unwrap_or(default).of(value)
+    };
+    [
+        (
+            n.energy,
+            or(tolerances.energy, Tolerance::Percent(20.0), n.energy),
+            true,
+        ),
+        (
+            n.fat,
+            or(
+                tolerances.fat,
+                match n.fat {
+                    v if v < 10.0 => Tolerance::Amount(1.5),
+                    v if v <= 40.0 => Tolerance::Percent(20.0),
+                    _ => Tolerance::Amount(8.0),
+                },
+                n.fat,
+            ),
+            // only scored in the ratio of saturates for oils and fats, which is higher with less fat
+            false,
+        ),
+        (
+            n.saturated_fats,
+            or(
+                tolerances.saturated_fats,
+                if n.saturated_fats < 4.0 {
+                    Tolerance::Amount(0.8)
+                } else {
+                    Tolerance::Percent(20.0)
+                },
+                n.saturated_fats,
+            ),
+            true,
+        ),
+        (
+            n.sugar,
+            or(tolerances.sugar, Tolerance::eu_grams(n.sugar), n.sugar),
+            true,
+        ),
+        (
+            n.proteins,
+            or(
+                tolerances.proteins,
+                Tolerance::eu_grams(n.proteins),
+                n.proteins,
+            ),
+            false,
+        ),
+        (
+            n.salt,
+            or(
+                tolerances.salt,
+                if n.salt < 1.25 {
+                    Tolerance::Amount(0.375)
+                } else {
+                    Tolerance::Percent(20.0)
+                },
+                n.salt,
+            ),
+            true,
+        ),
+        (
+            n.fibers,
+            or(tolerances.fibers, Tolerance::eu_grams(n.fibers), n.fibers),
+            false,
+        ),
+        (
+            product.fruits,
+            or(tolerances.fruits, Tolerance::Amount(0.0), product.fruits),
+            false,
+        ),
+    ]
+}
+
+/// The product with the values moved by `offsets` times their tolerances, kept between 0 and
+/// 100 g, or 100 % for the fruits and vegetables.
+fn sample(product: &Product, ranges: &[(f32, f32, bool); 8], offsets: [f32; 8]) -> Product {
+    let [energy, fat, saturated_fats, sugar, proteins, salt, fibers, fruits] =
+        std::array::from_fn(|i| {
+            let (value, tolerance, _) = ranges[i];
+            let value = offsets[i].mul_add(tolerance, value).max(0.0);
+            if i == 0 {
+                value
+            } else {
+                value.min(100.0)
+            }
+        });
+    Product {
+        nutrition: Nutrition {
+            energy,
+            fat,
+            saturated_fats,
+            sugar,
+            proteins,
+            salt,
+            fibers,
+            ..product.nutrition
+        },
+        fruits,
+        ..product.clone()
+    }
+}
+
+/// Estimates the grades a product gets when its values are off by up to their tolerances.
+///
+/// The best and the worst score are exact, as more of a nutrient never gives fewer points of its
+/// kind: they are the scores with every value at the end of its tolerance that is better or
+/// worse. How likely the grades are is estimated from `samples` products with values spread
+/// evenly within their tolerances.
+#[must_use]
+pub fn uncertainty(
+    algorithm: Algorithm,
+    product: &Product,
+    tolerances: &Tolerances,
+    samples: usize,
+) -> Uncertainty {
+    let ranges = ranges(product, tolerances);
+    let corner = |sign: f32| {
+        let offsets = ranges.map(|(_, _, more_points)| if more_points { sign } else { -sign });
+        let product = sample(product, &ranges, offsets);
+        let score = product.score(algorithm);
+        (score.total, product.letter(algorithm, &score))
+    };
+    let (best, best_grade) = corner(-1.0);
+    let (worst, worst_grade) = corner(1.0);
+    let grade = product.letter(algorithm, &product.score(algorithm));
+    let mut random = SplitMix(0);
+    let mut counts = [0_usize; 5];
+    for _ in 0..samples {
+        let offsets = std::array::from_fn(|_| random.next());
+        let product = sample(product, &ranges, offsets);
+        let letter = product.letter(algorithm, &product.score(algorithm));
+        if let Some(count) = ('A'..='E')
+            .position(|l| l == letter)
+            .and_then(|i| counts.get_mut(i))
+        {
+            *count += 1;
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let share = |count: usize| count as f32 / samples.max(1) as f32;
+    let grades: Vec<GradeShare> = ('A'..='E')
+        .zip(counts)
+        .filter(|&(_, count)| count > 0)
+        .map(|(grade, count)| GradeShare {
+            grade,
+            share: share(count),
+        })
+        .collect();
+    let stable = grades
+        .iter()
+        .find(|g| g.grade == grade)
+        .is_some_and(|g| g.share >= STABLE_SHARE);
+    Uncertainty {
+        samples,
+        grade,
+        best,
+        worst,
+        best_grade,
+        worst_grade,
+        grades,
+        stable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Category;
+
+    fn product(nutrition: Nutrition, fruits: f32) -> Product {
+        Product {
+            name: None,
+            category: Category::Other,
+            nutrition,
+            fruits,
+            water: false,
+            drink: None,
+            alcohol: None,
+            ingredients: None,
+            eco: None,
+        }
+    }
+
+    fn grades(uncertainty: &Uncertainty) -> String {
+        uncertainty.grades.iter().map(|g| g.grade).collect()
+    }
+
+    #[test]
+    fn products_far_from_a_cutoff_are_stable() {
+        let muesli = product(
+            Nutrition {
+                energy: 100.0,
+                fibers: 10.0,
+                ..Nutrition::default()
+            },
+            90.0,
+        );
+        let uncertainty = uncertainty(Algorithm::V2017, &muesli, &Tolerances::default(), 100);
+        assert_eq!(
+            (uncertainty.best_grade, uncertainty.worst_grade),
+            ('A', 'A')
+        );
+        assert_eq!(grades(&uncertainty), "A");
+        assert!(uncertainty.stable);
+    }
+
+    #[test]
+    fn products_on_a_cutoff_get_two_grades() {
+        // 13.5 g of sugar are 2 points and a B, up to 20 % more are a C, and only the sugar is
+        // off
+        let sweet = product(
+            Nutrition {
+                sugar: 13.5,
+                ..Nutrition::default()
+            },
+            0.0,
+        );
+        let none = Some(Tolerance::Amount(0.0));
+        let tolerances = Tolerances {
+            energy: none,
+            fat: none,
+            saturated_fats: none,
+            sugar: None,
+            proteins: none,
+            salt: none,
+            fibers: none,
+            fruits: none,
+        };
+        let uncertainty = uncertainty(Algorithm::V2017, &sweet, &tolerances, 100);
+        assert_eq!(
+            (
+                uncertainty.grade,
+                uncertainty.best_grade,
+                uncertainty.worst_grade
+            ),
+            ('B', 'B', 'C')
+        );
+        assert_eq!((uncertainty.best, uncertainty.worst), (2, 3));
+        assert_eq!(grades(&uncertainty), "BC");
+        assert!(!uncertainty.stable);
+        let shares: f32 = uncertainty.grades.iter().map(|g| g.share).sum();
+        assert!((shares - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn the_samples_are_the_same_every_time() {
+        let sweet = product(
+            Nutrition {
+                sugar: 13.5,
+                salt: 0.5,
+                ..Nutrition::default()
+            },
+            0.0,
+        );
+        let estimate = || uncertainty(Algorithm::V2023, &sweet, &Tolerances::default(), 50);
+        assert_eq!(estimate(), estimate());
+    }
+
+    #[test]
+    fn tolerances_are_amounts_or_percentages() {
+        assert_eq!(
+            format!(
+                "{:?}",
+                (
+                    Tolerance::Amount(-2.0).of(5.0),
+                    Tolerance::Percent(20.0).of(15.0)
+                )
+            ),
+            "(2.0, 3.0)"
+        );
+        let tolerance = |value| Tolerance::eu_grams(value);
+        assert_eq!(
+            [tolerance(9.9), tolerance(40.0), tolerance(40.1)],
+            [
+                Tolerance::Amount(2.0),
+                Tolerance::Percent(20.0),
+                Tolerance::Amount(8.0)
+            ]
+        );
+    }
+}
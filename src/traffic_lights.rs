@@ -0,0 +1,197 @@
+use crate::{Category, Nutrition};
+use serde::Serialize;
+use strum::Display;
+
+/// The colour of a traffic light, see [`traffic_lights`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Display, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Light {
+    Green,
+    Amber,
+    Red,
+}
+
+/// The traffic light of one nutrient.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct TrafficLight {
+    /// Name of the nutrient.
+    pub nutrient: &'static str,
+    /// The amount in g, per 100g or 100ml or per portion.
+    pub amount: f32,
+    pub light: Light,
+}
+
+/// The UK multiple traffic lights of a product, see [`traffic_lights`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TrafficLights {
+    pub per_100: Vec<TrafficLight>,
+    /// The size of the portion in g or ml, if one was given.
+    pub portion: Option<f32>,
+    /// The lights for the amounts in a portion, empty without a portion size.
+    pub per_portion: Vec<TrafficLight>,
+}
+
+type Field = fn(&Nutrition) -> f32;
+
+// name, field, highest green and amber amount per 100g, lowest red amount per portion
+const FOODS: [(&str, Field, f32, f32, f32); 4] = [
+    ("Fat", |n| n.fat, 3.0, 17.5, 21.0),
+    ("Saturates", |n| n.saturated_fats, 1.5, 5.0, 6.0),
+    ("Sugars", |n| n.sugar, 5.0, 22.5, 27.0),
+    ("Salt", |n| n.salt, 0.3, 1.5, 1.8),
+];
+
+// the same per 100ml
+const DRINKS: [(&str, Field, f32, f32, f32); 4] = [
+    ("Fat", |n| n.fat, 1.5, 8.75, 10.5),
+    ("Saturates", |n| n.saturated_fats, 0.75, 2.5, 3.0),
+    ("Sugars", |n| n.sugar, 2.5, 11.25, 13.5),
+    ("Salt", |n| n.salt, 0.3, 0.75, 0.9),
+];
+
+/// Classifies fat, saturates, sugars and salt as green, amber or red like the front of pack
+/// labels of the UK Food Standards Agency, from the values per 100g or 100ml.
+///
+/// With a `portion` size in g or ml, the amounts in a portion are classified as well. They get the
+/// colour of the value per 100g, except that they are red above the limit per portion for portions
+/// larger than 100g or 150ml. Drinks and milk drinks use the limits for drinks.
+#[must_use]
+pub fn traffic_lights(
+    category: Category,
+    nutrition: &Nutrition,
+    portion: Option<f32>,
+) -> TrafficLights {
+    let drink = matches!(category, Category::Drinks | Category::MilkDrinks);
+    let (table, large_portion) = if drink {
+        (DRINKS, 150.0)
+    } else {
+        (FOODS, 100.0)
+    };
+    let mut per_100 = Vec::new();
+    let mut per_portion = Vec::new();
+    for (nutrient, field, green, amber, portion_red) in table {
+        let amount = field(nutrition);
+        let light = if amount <= green {
+            Light::Green
+        } else if amount <= amber {
+            Light::Amber
+        } else {
+            Light::Red
+        };
+        per_100.push(TrafficLight {
+            nutrient,
+            amount,
+            light,
+        });
+        if let Some(size) = portion {
+            let amount = amount * size / 100.0;
+            per_portion.push(TrafficLight {
+                nutrient,
+                amount,
+                light: if size > large_portion && amount > portion_red {
+                    Light::Red
+                } else {
+                    light
+                },
+            });
+        }
+    }
+    TrafficLights {
+        per_100,
+        portion,
+        per_portion,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colours(lights: &[TrafficLight]) -> Vec<Light> {
+        lights.iter().map(|l| l.light).collect()
+    }
+
+    /// The lights of the sugars and the salt in a portion of 200 g or ml.
+    fn portion_lights(category: Category, sugar: f32, salt: f32) -> (Light, Light) {
+        let nutrition = Nutrition {
+            sugar,
+            salt,
+            ..Nutrition::default()
+        };
+        let lights = traffic_lights(category, &nutrition, Some(200.0));
+        (lights.per_portion[2].light, lights.per_portion[3].light)
+    }
+
+    #[test]
+    fn values_per_100_g_are_green_up_to_the_first_limit_and_red_above_the_second() {
+        let nutrition = Nutrition {
+            fat: 3.0,
+            saturated_fats: 5.0,
+            sugar: 22.6,
+            salt: 0.31,
+            ..Nutrition::default()
+        };
+        let lights = traffic_lights(Category::Other, &nutrition, None);
+        assert_eq!(
+            colours(&lights.per_100),
+            [Light::Green, Light::Amber, Light::Red, Light::Amber]
+        );
+        assert!(lights.per_portion.is_empty());
+    }
+
+    #[test]
+    fn drinks_have_the_limits_per_100_ml() {
+        let nutrition = Nutrition {
+            fat: 1.6,
+            saturated_fats: 0.75,
+            sugar: 11.3,
+            salt: 0.3,
+            ..Nutrition::default()
+        };
+        let lights = traffic_lights(Category::Drinks, &nutrition, None);
+        assert_eq!(
+            colours(&lights.per_100),
+            [Light::Amber, Light::Green, Light::Red, Light::Green]
+        );
+    }
+
+    #[test]
+    fn foods_are_red_above_27_g_sugars_and_1_8_g_salt_per_portion() {
+        // 27 g and 1.8 g in 200 g, at the limits
+        assert_eq!(
+            portion_lights(Category::Other, 13.5, 0.9),
+            (Light::Amber, Light::Amber)
+        );
+        // 27.2 g and 1.82 g
+        assert_eq!(
+            portion_lights(Category::Other, 13.6, 0.91),
+            (Light::Red, Light::Red)
+        );
+    }
+
+    #[test]
+    fn drinks_are_red_above_13_5_g_sugars_and_0_9_g_salt_per_portion() {
+        // 13.5 g and 0.9 g in 200 ml, at the limits
+        assert_eq!(
+            portion_lights(Category::Drinks, 6.75, 0.45),
+            (Light::Amber, Light::Amber)
+        );
+        // 13.6 g and 0.92 g
+        assert_eq!(
+            portion_lights(Category::Drinks, 6.8, 0.46),
+            (Light::Red, Light::Red)
+        );
+    }
+
+    #[test]
+    fn small_portions_have_the_lights_per_100_g() {
+        // 30 g of sugars in a portion of 100 g, which is not larger than 100 g
+        let nutrition = Nutrition {
+            sugar: 20.0,
+            ..Nutrition::default()
+        };
+        let lights = traffic_lights(Category::Other, &nutrition, Some(100.0));
+        assert_eq!(lights.per_portion[2].light, Light::Amber);
+    }
+}
@@ -0,0 +1,448 @@
+use crate::i18n::{self, tr, tr_with};
+use crate::units::{self, Unit};
+use crate::{grade_color, print_text, product_file, TextOptions};
+use nutriscore::{
+    Algorithm, Category, Component, DrinkType, EcoProduct, Nutrition, Product, Score, Severity,
+};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::path::{Path, PathBuf};
+use strum::{EnumCount, EnumIter, IntoEnumIterator};
+
+/// The fields and the border of the form.
+const FORM_HEIGHT: u16 = 13;
+
+/// The index after or before `index` in `len` items, wrapping around.
+const fn next(index: usize, len: usize, forward: bool) -> usize {
+    if forward {
+        (index + 1) % len
+    } else {
+        (index + len - 1) % len
+    }
+}
+
+/// The fields of the form, from top to bottom.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, EnumCount, EnumIter)]
+enum Field {
+    Energy,
+    Fat,
+    SaturatedFats,
+    Sugar,
+    Proteins,
+    Salt,
+    Fibers,
+    Fruits,
+    Category,
+    Drink,
+    Sweeteners,
+}
+
+impl Field {
+    fn label(self) -> String {
+        tr(match self {
+            Self::Energy => "prompt-energy-kj",
+            Self::Fat => "prompt-fat",
+            Self::SaturatedFats => "prompt-saturated-fats",
+            Self::Sugar => "prompt-sugar",
+            Self::Proteins => "prompt-proteins",
+            Self::Salt => "prompt-salt",
+            Self::Fibers => "prompt-fibers",
+            Self::Fruits => "prompt-fruits",
+            Self::Category => "prompt-category",
+            Self::Drink => "prompt-drink",
+            Self::Sweeteners => "prompt-sweeteners",
+        })
+    }
+
+    /// The unit of the fields that are typed in.
+    const fn unit(self) -> Option<Unit> {
+        match self {
+            Self::Energy => Some(Unit::Kj),
+            Self::Fat
+            | Self::SaturatedFats
+            | Self::Sugar
+            | Self::Proteins
+            | Self::Salt
+            | Self::Fibers => Some(Unit::G),
+            Self::Fruits => Some(Unit::Percent),
+            Self::Category | Self::Drink | Self::Sweeteners => None,
+        }
+    }
+}
+
+/// The state of the form, scored again after every key.
+struct Form {
+    algorithm: Algorithm,
+    /// The text of every field that is typed in, indexed by [`Field`].
+    texts: Vec<String>,
+    category: Category,
+    drink: DrinkType,
+    sweeteners: bool,
+    /// Kept from the product file, the form has no field for them.
+    name: Option<String>,
+    carbohydrates: Option<f32>,
+    alcohol: Option<f32>,
+    ingredients: Option<String>,
+    eco: Option<EcoProduct>,
+    selected: Field,
+    file: Option<PathBuf>,
+    /// The result of the last save.
+    message: Option<String>,
+}
+
+impl Form {
+    fn new(algorithm: Algorithm, file: Option<PathBuf>) -> io::Result<Self> {
+        let mut form = Self {
+            algorithm,
+            texts: vec![String::new(); Field::COUNT],
+            category: Category::Other,
+            drink: DrinkType::Other,
+            sweeteners: false,
+            name: None,
+            carbohydrates: None,
+            alcohol: None,
+            ingredients: None,
+            eco: None,
+            selected: Field::Energy,
+            file,
+            message: None,
+        };
+        if let Some(path) = form.file.as_deref().filter(|path| path.exists()) {
+            let product = product_file::read(path)?;
+            let n = &product.nutrition;
+            for (field, value) in [
+                (Field::Energy, n.energy),
+                (Field::Fat, n.fat),
+                (Field::SaturatedFats, n.saturated_fats),
+                (Field::Sugar, n.sugar),
+                (Field::Proteins, n.proteins),
+                (Field::Salt, n.salt),
+                (Field::Fibers, n.fibers),
+                (Field::Fruits, product.fruits),
+            ] {
+                form.texts[field as usize] = value.to_string();
+            }
+            form.category = product.category;
+            form.drink = product.drink.unwrap_or(if product.water {
+                DrinkType::Water
+            } else {
+                DrinkType::Other
+            });
+            form.sweeteners = n.contains_sweeteners;
+            form.name = product.name;
+            form.carbohydrates = n.carbohydrates;
+            form.alcohol = product.alcohol;
+            form.ingredients = product.ingredients;
+            form.eco = product.eco;
+        }
+        Ok(form)
+    }
+
+    /// Whether a field is shown, the drink and sweeteners fields only matter for some drinks.
+    fn applies(&self, field: Field) -> bool {
+        match field {
+            Field::Drink => self.category == Category::Drinks,
+            Field::Sweeteners => {
+                self.algorithm == Algorithm::V2023
+                    && self.category.is_beverage(self.algorithm)
+                    && !self.is_water()
+            }
+            _ => true,
+        }
+    }
+
+    fn is_water(&self) -> bool {
+        self.category == Category::Drinks && self.drink.is_water()
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        Field::iter().filter(|&f| self.applies(f)).collect()
+    }
+
+    /// Selects the next or previous field, wrapping around.
+    fn select(&mut self, forward: bool) {
+        let fields = self.fields();
+        let index = fields
+            .iter()
+            .position(|&f| f == self.selected)
+            .unwrap_or_default();
+        self.selected = fields[next(index, fields.len(), forward)];
+    }
+
+    /// Changes the category, the drink or a yes/no field to the next or previous choice.
+    fn change(&mut self, forward: bool) {
+        match self.selected {
+            Field::Category => {
+                let categories: Vec<Category> = Category::iter().collect();
+                let index = categories
+                    .iter()
+                    .position(|&c| c == self.category)
+                    .unwrap_or_default();
+                self.category = categories[next(index, categories.len(), forward)];
+            }
+            Field::Drink => {
+                let drinks: Vec<DrinkType> = DrinkType::iter().collect();
+                let index = drinks
+                    .iter()
+                    .position(|&d| d == self.drink)
+                    .unwrap_or_default();
+                self.drink = drinks[next(index, drinks.len(), forward)];
+            }
+            Field::Sweeteners => self.sweeteners = !self.sweeteners,
+            _ => {}
+        }
+    }
+
+    fn value(&self, field: Field) -> Result<f32, String> {
+        let text = &self.texts[field as usize];
+        if text.trim().is_empty() {
+            return Err(tr_with("missing-value", &[("value", field.label().into())]));
+        }
+        units::parse(text, field.unit().unwrap_or(Unit::G))
+            .map_err(|e| format!("{}: {e}", field.label()))
+    }
+
+    /// The product entered so far, or why it cannot be scored yet.
+    fn product(&self) -> Result<Product, String> {
+        let product = Product {
+            name: self.name.clone(),
+            category: self.category,
+            nutrition: Nutrition {
+                energy: self.value(Field::Energy)?,
+                fat: self.value(Field::Fat)?,
+                saturated_fats: self.value(Field::SaturatedFats)?,
+                carbohydrates: self.carbohydrates,
+                sugar: self.value(Field::Sugar)?,
+                proteins: self.value(Field::Proteins)?,
+                salt: self.value(Field::Salt)?,
+                fibers: self.value(Field::Fibers)?,
+                contains_sweeteners: self.applies(Field::Sweeteners) && self.sweeteners,
+            },
+            fruits: self.value(Field::Fruits)?,
+            water: self.is_water(),
+            drink: (self.category == Category::Drinks).then_some(self.drink),
+            alcohol: self.alcohol,
+            ingredients: self.ingredients.clone(),
+            eco: self.eco.clone(),
+        };
+        let errors: Vec<String> = product
+            .validate()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| i18n::issue(&issue))
+            .collect();
+        if errors.is_empty() {
+            Ok(product)
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+
+    fn save(&mut self) {
+        self.message = Some(match (&self.file, self.product()) {
+            (None, _) => "Start the TUI with a file to save to, e.g. tui product.toml".to_owned(),
+            (Some(_), Err(e)) => format!("Not saved: {e}"),
+            (Some(path), Ok(product)) => match product_file::write(path, &product) {
+                Ok(()) => format!("Saved {}", path.display()),
+                Err(e) => format!("Not saved: {e}"),
+            },
+        });
+    }
+
+    /// Handles a key and returns whether to quit.
+    fn key(&mut self, key: KeyEvent) -> bool {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        let typed = self.selected.unit().is_some();
+        match key.code {
+            KeyCode::Esc => return true,
+            KeyCode::Char('c') if control => return true,
+            KeyCode::Char('s') if control => self.save(),
+            KeyCode::Up | KeyCode::BackTab => self.select(false),
+            KeyCode::Down | KeyCode::Tab | KeyCode::Enter => self.select(true),
+            KeyCode::Left => self.change(false),
+            KeyCode::Right | KeyCode::Char(' ') if !typed => self.change(true),
+            KeyCode::Backspace if typed => {
+                self.texts[self.selected as usize].pop();
+            }
+            KeyCode::Char(c) if typed && !control => self.texts[self.selected as usize].push(c),
+            _ => {}
+        }
+        false
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, banner, help] = Layout::vertical([
+            Constraint::Min(FORM_HEIGHT),
+            Constraint::Length(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [form, breakdown] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(main);
+        self.draw_form(frame, form);
+        match self.product() {
+            Ok(product) => {
+                let score = product.score(self.algorithm);
+                draw_breakdown(frame, breakdown, Some(&score));
+                let letter = product.letter(self.algorithm, &score);
+                draw_banner(frame, banner, letter, score.total);
+            }
+            Err(e) => {
+                draw_breakdown(frame, breakdown, None);
+                frame.render_widget(
+                    Paragraph::new(e)
+                        .red()
+                        .block(Block::bordered().title("Not scored yet")),
+                    banner,
+                );
+            }
+        }
+        let help_text = self.message.clone().unwrap_or_else(|| {
+            "↑/↓ move  type to edit  ←/→ change  Ctrl+S save  Esc quit".to_owned()
+        });
+        frame.render_widget(Paragraph::new(help_text).dim(), help);
+    }
+
+    fn draw_form(&self, frame: &mut Frame, area: Rect) {
+        let width = Field::iter()
+            .map(|f| f.label().chars().count())
+            .max()
+            .unwrap_or_default();
+        let lines: Vec<Line> = self
+            .fields()
+            .into_iter()
+            .map(|field| {
+                let value = match field {
+                    Field::Category => format!("‹ {} ›", i18n::category(self.category)),
+                    Field::Drink => format!("‹ {} ›", tr(&format!("drink-{}", self.drink))),
+                    Field::Sweeteners => yes_no(self.sweeteners),
+                    _ if field == self.selected => format!("{}▏", self.texts[field as usize]),
+                    _ => self.texts[field as usize].clone(),
+                };
+                let unit = match field.unit() {
+                    Some(Unit::G) => " g",
+                    Some(Unit::Percent) => " %",
+                    _ => "",
+                };
+                let line = Line::from(vec![
+                    Span::raw(format!("{:width$}  ", field.label())),
+                    Span::styled(value, Style::new().bold()),
+                    Span::raw(unit),
+                ]);
+                if field == self.selected {
+                    line.style(Style::new().add_modifier(Modifier::REVERSED))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        let title = self
+            .file
+            .as_deref()
+            .map_or_else(|| "Product".to_owned(), |path| path.display().to_string());
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(title)),
+            area,
+        );
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    format!("‹ {} ›", tr(if value { "yes" } else { "no" }))
+}
+
+fn component_line(c: &Component) -> Line<'static> {
+    let (style, counted) = if c.counted {
+        (Style::new(), String::new())
+    } else {
+        (
+            Style::new().dim(),
+            format!(" {}", tr("breakdown-not-counted")),
+        )
+    };
+    Line::from(vec![
+        Span::raw(format!(
+            "{:14} {:2}/{:<2} ",
+            i18n::component(c.name),
+            c.points,
+            c.max_points
+        )),
+        Span::raw("█".repeat(c.points)),
+        Span::raw("░".repeat(c.max_points.saturating_sub(c.points))),
+        Span::raw(counted),
+    ])
+    .style(style)
+}
+
+fn draw_breakdown(frame: &mut Frame, area: Rect, score: Option<&Score>) {
+    let lines: Vec<Line> = score.map_or_else(Vec::new, |score| {
+        let mut lines: Vec<Line> = vec![Line::from(tr("breakdown-negative")).italic()];
+        lines.extend(score.negative.iter().map(component_line));
+        lines.push(Line::from(tr("breakdown-positive")).italic());
+        lines.extend(score.positive.iter().map(component_line));
+        lines
+    });
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title("Breakdown")),
+        area,
+    );
+}
+
+fn draw_banner(frame: &mut Frame, area: Rect, letter: char, total: isize) {
+    let style = Style::new()
+        .fg(Color::Black)
+        .bg(Color::Indexed(grade_color(letter)))
+        .bold();
+    let text = vec![
+        Line::from(format!("Nutri-Score {letter}")),
+        Line::from(format!("{} {total}", tr("total-score"))),
+    ];
+    frame.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .style(style)
+            .block(Block::bordered()),
+        area,
+    );
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, form: &mut Form) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| form.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                form.message = None;
+                if form.key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Shows a form for all values with the breakdown and the grade updated after every key. The
+/// product is read from `file` if it exists and saved to it with Ctrl+S. The result is printed
+/// after quitting.
+pub fn run(
+    algorithm: Algorithm,
+    text: TextOptions,
+    file: Option<&Path>,
+) -> io::Result<Option<char>> {
+    let mut form = Form::new(algorithm, file.map(Path::to_owned))?;
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut form);
+    ratatui::restore();
+    result?;
+    Ok(form.product().ok().map(|product| {
+        let score = product.score(algorithm);
+        let letter = product.letter(algorithm, &score);
+        print_text(algorithm, text, &product.nutrition, &score, letter);
+        letter
+    }))
+}
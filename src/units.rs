@@ -0,0 +1,152 @@
+use crate::i18n::{self, tr_with};
+use nutriscore::Nutrition;
+use std::fmt;
+
+/// The units that values can be entered with, like `12g`, `450 kJ` or `600 mg`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Unit {
+    Kj,
+    Kcal,
+    G,
+    Mg,
+    Ug,
+    Percent,
+}
+
+impl Unit {
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        Some(match suffix.to_lowercase().as_str() {
+            "kj" => Self::Kj,
+            "kcal" => Self::Kcal,
+            "g" => Self::G,
+            "mg" => Self::Mg,
+            "µg" | "μg" | "ug" | "mcg" => Self::Ug,
+            "%" => Self::Percent,
+            _ => return None,
+        })
+    }
+
+    /// The units that can be converted into each other have the same base unit, kJ, g or %.
+    const fn base(self) -> Self {
+        match self {
+            Self::Kj | Self::Kcal => Self::Kj,
+            Self::G | Self::Mg | Self::Ug => Self::G,
+            Self::Percent => Self::Percent,
+        }
+    }
+
+    /// How many of the base unit one of this unit is.
+    fn factor(self) -> f32 {
+        match self {
+            Self::Kj | Self::G | Self::Percent => 1.0,
+            Self::Kcal => Nutrition::kj_from_kcal(1.0),
+            Self::Mg => 1e-3,
+            Self::Ug => 1e-6,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Kj => "kJ",
+            Self::Kcal => "kcal",
+            Self::G => "g",
+            Self::Mg => "mg",
+            Self::Ug => "µg",
+            Self::Percent => "%",
+        })
+    }
+}
+
+/// Parses a value like `12`, `12g`, `120kcal`, `600 mg` or `1.2e3 mg` and converts it into
+/// `unit`. Values without a unit are taken to be in `unit` already.
+pub fn parse(text: &str, unit: Unit) -> Result<f32, String> {
+    let text = text.trim();
+    // the unit is at the end, so that the e of 1e3 is part of the number
+    let number = text.trim_end_matches(|c: char| c.is_alphabetic() || c == '%');
+    let suffix = text[number.len()..].trim();
+    let value = if number.trim().is_empty() {
+        // a unit without a number, name the whole text
+        i18n::number(text)?
+    } else {
+        i18n::number(number)?
+    };
+    let value = if suffix.is_empty() {
+        value
+    } else {
+        let from = Unit::from_suffix(suffix)
+            .ok_or_else(|| tr_with("unknown-unit", &[("unit", suffix.into())]))?;
+        if from.base() != unit.base() {
+            return Err(tr_with(
+                "wrong-unit",
+                &[("text", text.into()), ("unit", unit.to_string().into())],
+            ));
+        }
+        value * from.factor() / unit.factor()
+    };
+    // like 1e39, which is too large for an f32
+    if !value.is_finite() {
+        return Err(tr_with("invalid-number", &[("text", text.into())]));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_parses(text: &str, unit: Unit, expected: f32) {
+        let value = parse(text, unit).unwrap();
+        assert!(
+            (value - expected).abs() <= expected.abs() * 1e-6,
+            "{text}: {value}"
+        );
+    }
+
+    #[test]
+    fn values_without_a_unit_are_in_the_unit_asked_for() {
+        assert_parses("12", Unit::G, 12.0);
+        assert_parses(" 450 ", Unit::Kj, 450.0);
+        assert_parses("600", Unit::Mg, 600.0);
+    }
+
+    #[test]
+    fn units_are_converted() {
+        assert_parses("120kcal", Unit::Kj, 502.08);
+        assert_parses("450 kJ", Unit::Kj, 450.0);
+        assert_parses("600 mg", Unit::G, 0.6);
+        assert_parses("1.2 g", Unit::Mg, 1200.0);
+        assert_parses("500µg", Unit::G, 0.0005);
+        assert_parses("500 mcg", Unit::Mg, 0.5);
+        assert_parses("12 %", Unit::Percent, 12.0);
+    }
+
+    #[test]
+    fn units_ignore_the_case() {
+        assert_parses("450 KJ", Unit::Kj, 450.0);
+        assert_parses("100 Kcal", Unit::Kj, 418.4);
+        assert_parses("12 G", Unit::G, 12.0);
+    }
+
+    #[test]
+    fn numbers_can_have_an_exponent() {
+        assert_parses("1e3", Unit::Kj, 1000.0);
+        assert_parses("2.5E1g", Unit::G, 25.0);
+        assert_parses("1.2e3 mg", Unit::G, 1.2);
+    }
+
+    #[test]
+    fn units_of_another_kind_are_wrong() {
+        assert!(parse("12 kJ", Unit::G).is_err());
+        assert!(parse("12 g", Unit::Percent).is_err());
+        assert!(parse("12 oz", Unit::G).is_err());
+    }
+
+    #[test]
+    fn values_that_are_no_numbers_are_invalid() {
+        for text in ["", "g", "inf", "NaN", "-inf", "1e39", "twelve"] {
+            assert!(parse(text, Unit::G).is_err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::cache::Cache;
+use crate::i18n::{self, tr_with};
+use crate::{
+    draw_score, grade_style, print_breakdown, print_quiet, Format, JsonResult, TextOptions,
+};
+use nutriscore::off::{Network, USER_AGENT};
+use nutriscore::{calculate_nutriscore, Algorithm, Category, Nutrition};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io;
+
+/// The public key of api.data.gov, which is heavily rate limited.
+const DEMO_KEY: &str = "DEMO_KEY";
+
+const SEARCH_URL: &str = "https://api.nal.usda.gov/fdc/v1/foods/search";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResult {
+    #[serde(default)]
+    foods: Vec<Food>,
+}
+
+/// A food of the USDA database.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Food {
+    fdc_id: u64,
+    description: String,
+    #[serde(default, rename = "foodNutrients")]
+    nutrients: Vec<FoodNutrient>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoodNutrient {
+    #[serde(default)]
+    nutrient_number: String,
+    #[serde(default)]
+    unit_name: String,
+    value: Option<f64>,
+}
+
+impl Food {
+    /// The value of the first of the nutrients with these numbers, converted into g.
+    fn grams(&self, numbers: &[&str]) -> Option<f64> {
+        numbers.iter().find_map(|number| {
+            let n = self
+                .nutrients
+                .iter()
+                .find(|n| n.nutrient_number == *number)?;
+            let factor = match n.unit_name.to_ascii_uppercase().as_str() {
+                "G" => 1.0,
+                "MG" => 1e-3,
+                "UG" => 1e-6,
+                _ => return None,
+            };
+            Some(n.value? * factor)
+        })
+    }
+
+    /// The energy in kJ, converted from kcal if necessary.
+    fn energy(&self) -> Option<f64> {
+        let value = |unit: &str| {
+            self.nutrients
+                .iter()
+                .find(|n| n.unit_name.eq_ignore_ascii_case(unit))
+                .and_then(|n| n.value)
+        };
+        value("kJ").or_else(|| value("KCAL").map(|kcal| kcal * 4.184))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn nutrition(&self) -> Nutrition {
+        let value = |name: &str, v: Option<f64>| {
+            v.map_or_else(
+                || {
+                    let name = i18n::nutrient(name);
+                    eprintln!("{}", tr_with("assuming-zero", &[("name", name.into())]));
+                    0.0
+                },
+                |v| v as f32,
+            )
+        };
+        // nutrient numbers of FoodData Central, sodium is given in mg
+        let salt = self.grams(&["307"]).map(|sodium| sodium * 2.5);
+        Nutrition {
+            energy: value("energy", self.energy()),
+            fat: value("fat", self.grams(&["204"])),
+            saturated_fats: value("saturated fats", self.grams(&["606"])),
+            sugar: value("sugar", self.grams(&["269", "269.3"])),
+            carbohydrates: self.grams(&["205"]).map(|v| v as f32),
+            proteins: value("proteins", self.grams(&["203"])),
+            salt: value("salt", salt),
+            fibers: value("fibers", self.grams(&["291"])),
+            contains_sweeteners: false,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonUsda<'a> {
+    fdc_id: u64,
+    description: &'a str,
+    category: String,
+    fruits: f32,
+    #[serde(flatten)]
+    result: JsonResult<'a>,
+}
+
+/// The best matches of a search, from the cache if it was done before.
+pub fn search(
+    query: &str,
+    api_key: Option<String>,
+    cache: &Cache,
+    network: &Network,
+) -> io::Result<Vec<Food>> {
+    let api_key = api_key
+        .or_else(|| env::var("FDC_API_KEY").ok())
+        .unwrap_or_else(|| DEMO_KEY.to_owned());
+    cache.get("usda", &query.to_lowercase(), || {
+        let result: SearchResult = network
+            .agent(SEARCH_URL)?
+            .get(SEARCH_URL)
+            .set("User-Agent", USER_AGENT)
+            .query("query", query)
+            .query("dataType", "Foundation,SR Legacy,Survey (FNDDS)")
+            .query("pageSize", "5")
+            .query("api_key", &api_key)
+            .call()
+            .map_err(io::Error::other)?
+            .into_json()?;
+        if result.foods.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no food found for {query:?}"),
+            ));
+        }
+        Ok(result.foods)
+    })
+}
+
+/// Prints the score of the best match of a search and returns its grade.
+pub fn run(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    foods: &[Food],
+    category: Category,
+    fruits: f32,
+) -> io::Result<char> {
+    let food = foods
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no food found"))?;
+    let nutrition = food.nutrition();
+    let score = calculate_nutriscore(algorithm, category, &nutrition, fruits);
+    let letter = category.score_to_letter(algorithm, score.total, false);
+    match format {
+        Format::Text if text.quiet => print_quiet(text, score.total, letter),
+        Format::Text => {
+            println!("{} (FDC ID {})", food.description, food.fdc_id);
+            println!("Category: {category}, fruits and vegetables: {fruits}%");
+            println!("Override them with --category and --fruits\n");
+            draw_score(algorithm, text, &score);
+            if text.breakdown {
+                print_breakdown(&nutrition, &score);
+            }
+            println!(
+                "\n{}",
+                grade_style(letter)
+                    .apply_to(format!("Computed grade: {letter} (score {})", score.total))
+            );
+            if foods.len() > 1 {
+                println!("\nOther matches:");
+                for other in &foods[1..] {
+                    println!("  {} (FDC ID {})", other.description, other.fdc_id);
+                }
+            }
+        }
+        Format::Json => {
+            let result = JsonUsda {
+                fdc_id: food.fdc_id,
+                description: &food.description,
+                category: category.to_string(),
+                fruits,
+                result: JsonResult {
+                    score: score.total,
+                    grade: letter,
+                    negative: &score.negative,
+                    positive: &score.positive,
+                },
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+    Ok(letter)
+}
@@ -0,0 +1,267 @@
+use crate::{Algorithm, Nutrition, Product};
+use serde::Serialize;
+use Field::{Carbohydrates, Energy, Fat, Fibers, Fruits, Proteins, Salt, SaturatedFats, Sugar};
+
+/// How serious an [`Issue`] is.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The values are impossible.
+    Error,
+    /// The values are possible but unlikely, e.g. because of a typo or a wrong unit.
+    Warning,
+}
+
+/// A value of a product an [`Issue`] is about, like the fields of [`Nutrition`] and [`Product`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Field {
+    Energy,
+    Fat,
+    SaturatedFats,
+    Carbohydrates,
+    Sugar,
+    Proteins,
+    Salt,
+    Fibers,
+    Fruits,
+}
+
+/// A problem with the values of a product, see [`Nutrition::validate`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Issue {
+    pub severity: Severity,
+    /// The fields involved.
+    pub fields: &'static [Field],
+    /// The message in English.
+    pub message: String,
+    /// The id of the message for translations, like `issue-negative`. They name the first field
+    /// `name`.
+    #[serde(skip)]
+    pub id: &'static str,
+    /// The values in the message with their names in the translations, like `("value", -1.0)`.
+    #[serde(skip)]
+    pub values: Vec<(&'static str, f32)>,
+}
+
+/// The id and the values of the message of an [`Issue`].
+type Message = (&'static str, Vec<(&'static str, f32)>);
+
+impl Issue {
+    fn error(fields: &'static [Field], (id, values): Message, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            fields,
+            message,
+            id,
+            values,
+        }
+    }
+
+    fn warning(fields: &'static [Field], (id, values): Message, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            fields,
+            message,
+            id,
+            values,
+        }
+    }
+}
+
+/// More than pure fat, with some room for rounding and values converted from kcal.
+const MAX_ENERGY: f32 = 3800.0;
+
+type Value = fn(&Nutrition) -> Option<f32>;
+
+// fields, name, value if known
+const GRAMS: [(&[Field], &str, Value); 7] = [
+    (&[Fat], "Fats", |n| Some(n.fat)),
+    (&[SaturatedFats], "Saturated fats", |n| {
+        Some(n.saturated_fats)
+    }),
+    (&[Carbohydrates], "Carbohydrates", |n| n.carbohydrates),
+    (&[Sugar], "Sugar", |n| Some(n.sugar)),
+    (&[Proteins], "Proteins", |n| Some(n.proteins)),
+    (&[Salt], "Salt", |n| Some(n.salt)),
+    (&[Fibers], "Fibers", |n| Some(n.fibers)),
+];
+
+impl Nutrition {
+    /// Checks the values per 100g or 100ml for impossible and implausible combinations.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        if self.energy.is_nan() || self.energy < 0.0 {
+            issues.push(Issue::error(
+                &[Energy],
+                ("issue-energy-negative", vec![("energy", self.energy)]),
+                format!("Energy must not be negative, got {}", self.energy),
+            ));
+        } else if self.energy > MAX_ENERGY {
+            issues.push(Issue::error(
+                &[Energy],
+                (
+                    "issue-energy-too-high",
+                    vec![("energy", self.energy), ("max", MAX_ENERGY)],
+                ),
+                format!(
+                    "Energy must not be more than {MAX_ENERGY} kJ per 100g, got {}",
+                    self.energy
+                ),
+            ));
+        }
+        for (fields, name, get) in GRAMS {
+            let Some(value) = get(self) else {
+                continue;
+            };
+            if value.is_nan() || value < 0.0 {
+                issues.push(Issue::error(
+                    fields,
+                    ("issue-negative", vec![("value", value)]),
+                    format!("{name} must not be negative, got {value}"),
+                ));
+            } else if value > 100.0 {
+                issues.push(Issue::error(
+                    fields,
+                    ("issue-too-much", vec![("value", value)]),
+                    format!("{name} must not be more than 100g per 100g, got {value}"),
+                ));
+            }
+        }
+        if !issues.is_empty() {
+            return issues;
+        }
+
+        if self.saturated_fats > self.fat {
+            issues.push(Issue::error(
+                &[Fat, SaturatedFats],
+                (
+                    "issue-saturates-above-fat",
+                    vec![("saturated-fats", self.saturated_fats), ("fat", self.fat)],
+                ),
+                format!(
+                    "Saturated fats ({}) must not be more than fats ({})",
+                    self.saturated_fats, self.fat
+                ),
+            ));
+        }
+        if let Some(carbohydrates) = self.carbohydrates {
+            if self.sugar > carbohydrates {
+                issues.push(Issue::error(
+                    &[Carbohydrates, Sugar],
+                    (
+                        "issue-sugar-above-carbohydrates",
+                        vec![("sugar", self.sugar), ("carbohydrates", carbohydrates)],
+                    ),
+                    format!(
+                        "Sugar ({}) must not be more than carbohydrates ({carbohydrates})",
+                        self.sugar
+                    ),
+                ));
+            }
+        }
+        let carbohydrates = self.carbohydrates.unwrap_or(self.sugar).max(self.sugar);
+        let total = self.fat + carbohydrates + self.proteins + self.salt + self.fibers;
+        if total > 100.0 {
+            let (fields, names, id): (&[Field], _, _) = if self.carbohydrates.is_some() {
+                (
+                    &[Fat, Carbohydrates, Proteins, Salt, Fibers],
+                    "carbohydrates",
+                    "issue-total-carbohydrates",
+                )
+            } else {
+                (
+                    &[Fat, Sugar, Proteins, Salt, Fibers],
+                    "sugar",
+                    "issue-total-sugar",
+                )
+            };
+            issues.push(Issue::error(
+                fields,
+                (id, vec![("total", total)]),
+                format!(
+                    "Fats, {names}, proteins, salt and fibers add up to {total}g, more than 100g"
+                ),
+            ));
+        }
+        issues.extend(self.energy_warnings(carbohydrates));
+        issues
+    }
+
+    /// Warns about an energy that does not fit the fats, proteins and `carbohydrates`, which are
+    /// at least the sugar.
+    fn energy_warnings(&self, carbohydrates: f32) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        // kJ per g of fat, protein and carbohydrates
+        let minimum = 37.0f32.mul_add(self.fat, 17.0 * (self.proteins + carbohydrates));
+        if self.energy < 0.8f32.mul_add(minimum, -40.0) {
+            let (name, id) = if self.carbohydrates.is_some() {
+                ("carbohydrates", "issue-energy-low-carbohydrates")
+            } else {
+                ("sugar", "issue-energy-low-sugar")
+            };
+            let message = format!(
+                "Energy of {} kJ is much lower than the {minimum:.0} kJ that fats, proteins and \
+                 {name} provide, was it entered in kcal?",
+                self.energy
+            );
+            let values = vec![("energy", self.energy), ("minimum", minimum.round())];
+            issues.push(Issue::warning(&[Energy], (id, values), message));
+        }
+        if self.carbohydrates.is_some() {
+            // with all macronutrients known, the energy can be estimated, fibers have 8 kJ per g
+            let estimate = 8.0f32.mul_add(self.fibers, minimum);
+            if self.energy > 1.2f32.mul_add(estimate, 80.0) {
+                let message = format!(
+                    "Energy of {} kJ is much higher than the {estimate:.0} kJ estimated from the \
+                     other values",
+                    self.energy
+                );
+                let values = vec![("energy", self.energy), ("estimate", estimate.round())];
+                issues.push(Issue::warning(
+                    &[Energy],
+                    ("issue-energy-high", values),
+                    message,
+                ));
+            }
+        }
+        issues
+    }
+}
+
+impl Product {
+    /// Checks the nutrition with [`Nutrition::validate`], the percentage of fruits and that the
+    /// product contains fat if its category scores the saturated fats as a share of it, see
+    /// [`CategoryDefinition::saturates_ratio`](crate::CategoryDefinition::saturates_ratio).
+    #[must_use]
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = self.nutrition.validate();
+        let fat_invalid = issues.iter().any(|issue| issue.fields.contains(&Fat));
+        let saturates_ratio = [Algorithm::V2017, Algorithm::V2023]
+            .into_iter()
+            .any(|algorithm| self.category.definition(algorithm).saturates_ratio);
+        if saturates_ratio && self.nutrition.fat <= 0.0 && !fat_invalid {
+            issues.push(Issue::error(
+                &[Fat],
+                ("issue-ratio-without-fat", vec![("fat", self.nutrition.fat)]),
+                format!(
+                    "The saturated fats are scored as a share of the fat, which must not be 0, \
+                     got {}",
+                    self.nutrition.fat
+                ),
+            ));
+        }
+        if !(0.0..=100.0).contains(&self.fruits) {
+            issues.push(Issue::error(
+                &[Fruits],
+                ("issue-fruits-out-of-range", vec![("fruits", self.fruits)]),
+                format!(
+                    "The percentage of fruits and vegetables must be between 0 and 100, got {}",
+                    self.fruits
+                ),
+            ));
+        }
+        issues
+    }
+}
@@ -0,0 +1,39 @@
+//! JavaScript bindings, enabled with the `wasm` feature.
+
+use crate::{Algorithm, Component, Product};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize)]
+struct WasmResult {
+    score: isize,
+    grade: char,
+    negative: Vec<Component>,
+    positive: Vec<Component>,
+}
+
+/// Calculates the Nutri-Score of a product object, with the same fields as [`Product`].
+///
+/// `algorithm` is `"2017"` or `"2023"` and defaults to 2017.
+///
+/// # Errors
+///
+/// Fails if the product or the algorithm is invalid.
+#[wasm_bindgen]
+pub fn score(product: JsValue, algorithm: Option<String>) -> Result<JsValue, JsError> {
+    let product: Product = serde_wasm_bindgen::from_value(product)?;
+    let algorithm: Algorithm = match algorithm {
+        Some(algorithm) => algorithm
+            .parse()
+            .map_err(|_| JsError::new(&format!("unknown algorithm {algorithm:?}")))?,
+        None => Algorithm::default(),
+    };
+    let score = product.score(algorithm);
+    let result = WasmResult {
+        score: score.total,
+        grade: product.letter(algorithm, &score),
+        negative: score.negative,
+        positive: score.positive,
+    };
+    Ok(serde_wasm_bindgen::to_value(&result)?)
+}
@@ -0,0 +1,55 @@
+use crate::{product_file, show, Format, Outputs, TextOptions};
+use console::Term;
+use nutriscore::Algorithm;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the file is checked for changes.
+const INTERVAL: Duration = Duration::from_millis(500);
+
+fn modified(file: &Path) -> Option<SystemTime> {
+    fs::metadata(file).and_then(|m| m.modified()).ok()
+}
+
+/// Scores a product file like `score` and again whenever it is saved, until interrupted.
+///
+/// Errors in the file are printed and the file is watched further, since it is probably still
+/// being edited.
+pub fn run(
+    algorithm: Algorithm,
+    format: Format,
+    text: TextOptions,
+    file: &Path,
+    outputs: &Outputs,
+) -> io::Result<()> {
+    let text = TextOptions {
+        breakdown: true,
+        ..text
+    };
+    let term = Term::stdout();
+    let mut last = None;
+    loop {
+        let current = modified(file);
+        if current.is_some() && current != last {
+            last = current;
+            if term.is_term() && format == Format::Text {
+                term.clear_screen()?;
+            }
+            if format == Format::Text {
+                println!(
+                    "{} (watching for changes, Ctrl+C to stop)\n",
+                    file.display()
+                );
+            }
+            if let Err(e) = product_file::read(file)
+                .and_then(|product| show(algorithm, format, text, product, false, outputs))
+            {
+                eprintln!("{e}");
+            }
+        }
+        thread::sleep(INTERVAL);
+    }
+}
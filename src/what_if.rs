@@ -0,0 +1,103 @@
+use crate::error;
+use crate::i18n::{self, tr, tr_with};
+use crate::units::{self, Unit};
+use crate::{print_text, TextOptions};
+use dialoguer::Input;
+use nutriscore::{Algorithm, Product, Severity};
+use std::io;
+
+/// The value and the unit it is stored in.
+fn field<'a>(product: &'a mut Product, name: &str) -> Option<(&'a mut f32, Unit)> {
+    let nutrition = &mut product.nutrition;
+    Some(match name {
+        "energy" => (&mut nutrition.energy, Unit::Kj),
+        "fat" | "fats" => (&mut nutrition.fat, Unit::G),
+        "saturated" | "saturated-fats" | "saturated_fats" => {
+            (&mut nutrition.saturated_fats, Unit::G)
+        }
+        "sugar" | "sugars" => (&mut nutrition.sugar, Unit::G),
+        "carbohydrates" | "carbs" => (nutrition.carbohydrates.get_or_insert(0.0), Unit::G),
+        "protein" | "proteins" => (&mut nutrition.proteins, Unit::G),
+        "salt" => (&mut nutrition.salt, Unit::G),
+        "fiber" | "fibers" => (&mut nutrition.fibers, Unit::G),
+        "fruits" => (&mut product.fruits, Unit::Percent),
+        _ => return None,
+    })
+}
+
+/// Parses `set sugar to 8`, `sugar = 8g` or `sugar 8 g`.
+fn parse(command: &str) -> Option<(String, String)> {
+    let command = command.trim().to_lowercase().replace('=', " ");
+    let mut words: Vec<&str> = command.split_whitespace().collect();
+    if words.first() == Some(&"set") {
+        words.remove(0);
+    }
+    if words.get(1) == Some(&"to") {
+        words.remove(1);
+    }
+    match words[..] {
+        [name, ref value @ ..] if !value.is_empty() => Some((name.to_owned(), value.concat())),
+        _ => None,
+    }
+}
+
+pub fn run(algorithm: Algorithm, text: TextOptions, mut product: Product) -> io::Result<()> {
+    loop {
+        let prompt = tr("prompt-what-if");
+        let command: String = Input::new()
+            .with_prompt(&prompt)
+            .allow_empty(true)
+            .interact()
+            .map_err(error::terminal(&prompt))?;
+        if command.trim().is_empty() {
+            return Ok(());
+        }
+        let Some((name, value)) = parse(&command) else {
+            let command = format!("{command:?}");
+            eprintln!(
+                "{}",
+                tr_with("what-if-not-understood", &[("command", command.into())])
+            );
+            continue;
+        };
+        let previous = product.clone();
+        let Some((field, unit)) = field(&mut product, &name) else {
+            let name = format!("{name:?}");
+            eprintln!(
+                "{}",
+                tr_with("what-if-unknown-nutrient", &[("name", name.into())])
+            );
+            continue;
+        };
+        match units::parse(&value, unit) {
+            Ok(value) => *field = value,
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        }
+        // impossible values are undone, so that the product can still be scored
+        let issues = product.validate();
+        let (errors, warnings): (Vec<_>, Vec<_>) = issues
+            .iter()
+            .partition(|issue| issue.severity == Severity::Error);
+        if !errors.is_empty() {
+            for error in errors {
+                eprintln!("{}", i18n::issue(error));
+            }
+            eprintln!("{}", tr("what-if-undone"));
+            product = previous;
+            continue;
+        }
+        for warning in warnings {
+            eprintln!(
+                "{}",
+                tr_with("warning", &[("message", i18n::issue(warning).into())])
+            );
+        }
+        let score = product.score(algorithm);
+        let letter = product.letter(algorithm, &score);
+        println!();
+        print_text(algorithm, text, &product.nutrition, &score, letter);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{Component, Product, Score, ScoringModel};
+use strum::{Display, EnumIter, EnumString};
+
+/// The food categories of the WHO Europe nutrient profile model, see [`WhoEurope`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum WhoCategory {
+    /// Chocolate, sugar confectionery, energy bars, sweet toppings and desserts.
+    Confectionery,
+    /// Cakes, sweet biscuits and pastries.
+    Cakes,
+    SavourySnacks,
+    Juices,
+    MilkDrinks,
+    EnergyDrinks,
+    OtherBeverages,
+    EdibleIces,
+    BreakfastCereals,
+    /// Yoghurts, sour milk, cream and similar foods.
+    Yoghurts,
+    Cheese,
+    ReadyMeals,
+    /// Butter and other fats and oils.
+    FatsAndOils,
+    Bread,
+    /// Fresh or dried pasta, rice and grains.
+    Pasta,
+    /// Fresh and frozen meat, poultry, fish and similar.
+    FreshMeat,
+    /// Processed meat, poultry, fish and similar.
+    ProcessedMeat,
+    /// Fresh and frozen fruit, vegetables and legumes.
+    FreshFruitsAndVegetables,
+    /// Processed fruit, vegetables and legumes.
+    ProcessedFruitsAndVegetables,
+    /// Sauces, dips and dressings.
+    Sauces,
+}
+
+/// The highest amounts per 100g that are permitted, `None` for no limit.
+#[derive(Copy, Clone, Debug)]
+struct Limits {
+    fat: Option<f32>,
+    saturated_fats: Option<f32>,
+    sugar: Option<f32>,
+    salt: Option<f32>,
+}
+
+const fn limits(
+    fat: Option<f32>,
+    saturated_fats: Option<f32>,
+    sugar: Option<f32>,
+    salt: Option<f32>,
+) -> Limits {
+    Limits {
+        fat,
+        saturated_fats,
+        sugar,
+        salt,
+    }
+}
+
+impl WhoCategory {
+    /// The limits of the category, or `None` if it is never permitted.
+    const fn limits(self) -> Option<Limits> {
+        Some(match self {
+            Self::Confectionery
+            | Self::Cakes
+            | Self::SavourySnacks
+            | Self::Juices
+            | Self::MilkDrinks
+            | Self::EnergyDrinks
+            | Self::EdibleIces => return None,
+            // no added sugar, which is not known, so no sugar at all
+            Self::OtherBeverages => limits(None, None, Some(0.0), None),
+            Self::BreakfastCereals => limits(Some(10.0), None, Some(15.0), Some(1.6)),
+            Self::Yoghurts => limits(Some(2.5), Some(2.0), Some(10.0), Some(0.2)),
+            Self::Cheese => limits(Some(20.0), None, None, Some(1.3)),
+            Self::ReadyMeals => limits(Some(10.0), Some(4.0), Some(10.0), Some(1.0)),
+            Self::FatsAndOils => limits(None, Some(20.0), None, Some(1.3)),
+            Self::Bread | Self::Pasta => limits(Some(10.0), None, Some(10.0), Some(1.2)),
+            Self::FreshMeat | Self::ProcessedMeat => limits(Some(20.0), None, None, Some(1.7)),
+            Self::FreshFruitsAndVegetables => limits(None, None, None, None),
+            Self::ProcessedFruitsAndVegetables | Self::Sauces => {
+                limits(Some(5.0), None, Some(10.0), Some(1.0))
+            }
+        })
+    }
+}
+
+/// The nutrient profile model of the WHO Regional Office for Europe, which decides whether a
+/// product may be marketed to children.
+///
+/// Every limit of the category is a component with one point if the product exceeds it, and the
+/// product is permitted without points. Some categories, like confectionery, are never permitted,
+/// which gives a point for the category. Other beverages are only permitted without sugar and
+/// sweeteners.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WhoEurope {
+    pub category: WhoCategory,
+}
+
+fn limit(name: &'static str, value: f32, limit: Option<f32>) -> Option<Component> {
+    let limit = limit?;
+    let exceeded = value > limit;
+    Some(Component {
+        name,
+        value,
+        points: usize::from(exceeded),
+        max_points: 1,
+        lower: None,
+        upper: Some(limit),
+        counted: true,
+        cutoffs: &[],
+    })
+}
+
+impl ScoringModel for WhoEurope {
+    fn name(&self) -> String {
+        "WHO Europe nutrient profile".to_owned()
+    }
+
+    fn score(&self, product: &Product) -> Score {
+        let n = &product.nutrition;
+        let category = self.category.limits();
+        let limits = category.unwrap_or(limits(None, None, None, None));
+        let negative: Vec<Component> = [
+            // a category that is never permitted exceeds every limit
+            limit("Category", 1.0, category.is_none().then_some(0.0)),
+            limit("Fat", n.fat, limits.fat),
+            limit("Saturates", n.saturated_fats, limits.saturated_fats),
+            limit("Sugars", n.sugar, limits.sugar),
+            limit("Salt", n.salt, limits.salt),
+            limit(
+                "Sweeteners",
+                f32::from(u8::from(n.contains_sweeteners)),
+                (self.category == WhoCategory::OtherBeverages).then_some(0.0),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let points: usize = negative.iter().map(|c| c.points).sum();
+        Score {
+            negative,
+            positive: Vec::new(),
+            total: isize::try_from(points).unwrap_or(isize::MAX),
+        }
+    }
+
+    fn grade(&self, _product: &Product, score: &Score) -> String {
+        if score.total == 0 {
+            "permitted".to_owned()
+        } else {
+            "not permitted".to_owned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Category, Nutrition};
+
+    fn product(nutrition: Nutrition) -> Product {
+        Product {
+            name: None,
+            category: Category::Other,
+            nutrition,
+            fruits: 0.0,
+            water: false,
+            ingredients: None,
+            eco: None,
+            drink: None,
+            alcohol: None,
+        }
+    }
+
+    /// The grade and the components that exceed their limits.
+    fn check(category: WhoCategory, nutrition: Nutrition) -> (String, Vec<&'static str>) {
+        let model = WhoEurope { category };
+        let product = product(nutrition);
+        let score = model.score(&product);
+        let exceeded = score
+            .negative
+            .iter()
+            .filter(|c| c.points > 0)
+            .map(|c| c.name)
+            .collect();
+        (model.grade(&product, &score), exceeded)
+    }
+
+    #[test]
+    fn products_within_the_limits_are_permitted() {
+        let bread = Nutrition {
+            fat: 5.0,
+            sugar: 5.0,
+            salt: 1.2,
+            ..Nutrition::default()
+        };
+        assert_eq!(
+            check(WhoCategory::Bread, bread),
+            ("permitted".to_owned(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn every_exceeded_limit_is_a_point() {
+        let bread = Nutrition {
+            fat: 5.0,
+            sugar: 12.0,
+            salt: 1.3,
+            ..Nutrition::default()
+        };
+        assert_eq!(
+            check(WhoCategory::Bread, bread),
+            ("not permitted".to_owned(), vec!["Sugars", "Salt"])
+        );
+    }
+
+    #[test]
+    fn some_categories_are_never_permitted() {
+        assert_eq!(
+            check(WhoCategory::Confectionery, Nutrition::default()),
+            ("not permitted".to_owned(), vec!["Category"])
+        );
+    }
+
+    #[test]
+    fn other_beverages_are_only_permitted_without_sugar_and_sweeteners() {
+        let sweetened = Nutrition {
+            contains_sweeteners: true,
+            ..Nutrition::default()
+        };
+        assert_eq!(
+            check(WhoCategory::OtherBeverages, sweetened),
+            ("not permitted".to_owned(), vec!["Sweeteners"])
+        );
+        let sugared = Nutrition {
+            sugar: 0.5,
+            ..Nutrition::default()
+        };
+        assert_eq!(
+            check(WhoCategory::OtherBeverages, sugared),
+            ("not permitted".to_owned(), vec!["Sugars"])
+        );
+        // sweeteners are only limited in beverages
+        assert_eq!(
+            check(WhoCategory::Yoghurts, sweetened).1,
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn fresh_fruits_and_vegetables_have_no_limits() {
+        let product = product(Nutrition {
+            fat: 50.0,
+            sugar: 60.0,
+            ..Nutrition::default()
+        });
+        let model = WhoEurope {
+            category: WhoCategory::FreshFruitsAndVegetables,
+        };
+        assert!(model.score(&product).negative.is_empty());
+    }
+}
@@ -0,0 +1,683 @@
+use crate::config;
+use crate::error;
+use crate::i18n::{self, tr, tr_with};
+use crate::units::{self, Unit};
+use crate::{cache, database, fruits_wizard, EnergyUnit, NutritionArgs};
+use dialoguer::{Completion, Input, Select};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use nutriscore::off;
+use nutriscore::{
+    Algorithm, Category, DrinkType, EcoCategory, EcoLabel, Origin, Packaging, Product,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use strum::{Display, EnumCount, EnumIter, EnumString, IntoEnumIterator};
+
+/// What can be entered instead of an answer to return to the previous question.
+const BACK: &str = "back";
+/// What can be entered instead of an answer to save the answers and stop.
+const QUIT: &str = "quit";
+
+/// The questions of the wizard, in the order they are asked.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+enum Question {
+    /// Only asked if there are saved products to complete the name from.
+    Name,
+    Energy,
+    Fat,
+    SaturatedFats,
+    Carbohydrates,
+    Sugar,
+    Proteins,
+    Salt,
+    /// Only asked if the salt is left empty.
+    Sodium,
+    Fibers,
+    Category,
+    Fruits,
+    /// The kind of drink, only asked for drinks.
+    Drink,
+    /// Only asked for drinks.
+    Alcohol,
+    Sweeteners,
+    EcoCategory,
+    Packaging,
+    Origin,
+    Labels,
+    PalmOil,
+}
+
+/// What was entered for a question.
+enum Answer {
+    Value(String),
+    /// Return to the previous question.
+    Back,
+    /// Save the answers so far and stop.
+    Quit,
+}
+
+/// Asks for the values that are not given on the command line, one question after another, and
+/// lets the user return to earlier questions to correct them.
+struct Wizard<'a> {
+    algorithm: Algorithm,
+    args: &'a NutritionArgs,
+    allow_unknown: bool,
+    /// The questions answered so far, in the order they were asked. Empty answers are left
+    /// unknown.
+    answers: Vec<(Question, String)>,
+    /// Where the answers are saved after every question.
+    session: Option<PathBuf>,
+    /// The answers offered for editing, from the last run or from before going back.
+    defaults: Vec<(Question, String)>,
+    /// The saved and cached products whose names are completed, to fill in their values.
+    known: Vec<Product>,
+}
+
+/// Completes a product name with the best match among the known ones.
+struct Names<'a>(&'a [Product]);
+
+impl Completion for Names<'_> {
+    fn get(&self, input: &str) -> Option<String> {
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let mut best: Option<(i64, &str)> = None;
+        for name in self.0.iter().filter_map(|product| product.name.as_deref()) {
+            let Some(score) = matcher.fuzzy_match(name, input.trim()) else {
+                continue;
+            };
+            // the first of equally good matches, a saved product before a cached one
+            if best.is_none_or(|(best, _)| score > best) {
+                best = Some((score, name));
+            }
+        }
+        best.map(|(_, name)| name.to_owned())
+    }
+}
+
+impl Wizard<'_> {
+    fn default(&self, question: Question) -> Option<&str> {
+        self.defaults
+            .iter()
+            .find(|(q, _)| *q == question)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    fn answer(&self, question: Question) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|(q, _)| *q == question)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    fn category(&self) -> Option<Category> {
+        match self.args.category {
+            Some(category) => Some(category),
+            None if self.args.milk => Some(Category::MilkDrinks),
+            None => self.answer(Question::Category)?.parse().ok(),
+        }
+    }
+
+    fn is_water(&self) -> bool {
+        let drink = self
+            .args
+            .drink
+            .or_else(|| self.answer(Question::Drink)?.parse().ok());
+        self.args.water || drink.is_some_and(DrinkType::is_water)
+    }
+
+    /// Whether a question has to be asked, given the arguments and the answers so far.
+    fn applies(&self, question: Question) -> bool {
+        let args = self.args;
+        match question {
+            Question::Name => args.product_name.is_none() && !self.known.is_empty(),
+            Question::Energy => args.energy.is_none(),
+            Question::Fat => args.fat.is_none(),
+            Question::SaturatedFats => args.saturated_fats.is_none(),
+            Question::Carbohydrates => args.carbohydrates.is_none() && args.sugar.is_none(),
+            Question::Sugar => args.sugar.is_none(),
+            Question::Proteins => args.proteins.is_none(),
+            Question::Salt => args.salt.is_none() && args.sodium.is_none(),
+            Question::Sodium => args.sodium.is_none() && self.answer(Question::Salt) == Some(""),
+            Question::Fibers => args.fibers.is_none(),
+            Question::Category => args.category.is_none() && !args.milk,
+            Question::Fruits => args.fruits.is_none(),
+            Question::Drink => {
+                !args.water && args.drink.is_none() && self.category() == Some(Category::Drinks)
+            }
+            Question::Alcohol => {
+                args.alcohol.is_none()
+                    && self
+                        .category()
+                        .is_some_and(|c| matches!(c, Category::Drinks | Category::MilkDrinks))
+                    && !self.is_water()
+            }
+            Question::Sweeteners => {
+                !args.sweeteners
+                    && self.algorithm == Algorithm::V2023
+                    && self
+                        .category()
+                        .is_some_and(|c| c.is_beverage(self.algorithm))
+                    && !self.is_water()
+            }
+            Question::EcoCategory => args.wants_eco() && args.eco_category.is_none(),
+            Question::Packaging => args.wants_eco() && args.packaging.is_none(),
+            Question::Origin => args.wants_eco() && args.origin.is_none(),
+            Question::Labels => args.wants_eco() && args.labels.is_none(),
+            Question::PalmOil => args.wants_eco() && !args.palm_oil,
+        }
+    }
+
+    /// The next question after the last answered one.
+    fn next(&self) -> Option<Question> {
+        let start = self
+            .answers
+            .last()
+            .map_or(0, |&(question, _)| question as usize + 1);
+        Question::iter()
+            .skip(start)
+            .find(|&question| self.applies(question))
+    }
+
+    fn run(mut self) -> io::Result<NutritionArgs> {
+        if self.next().is_some() {
+            println!("{}", tr("back-hint"));
+        }
+        while let Some(question) = self.next() {
+            match self.ask(question)? {
+                Answer::Value(answer) => {
+                    let known = self
+                        .known
+                        .iter()
+                        .find(|product| {
+                            question == Question::Name && product.name.as_deref() == Some(&answer)
+                        })
+                        .cloned();
+                    self.answers.push((question, answer));
+                    if let Some(product) = known {
+                        self.prefill(&product);
+                    }
+                }
+                Answer::Back => {
+                    if let Some(answer) = self.answers.pop() {
+                        self.defaults.retain(|(q, _)| *q != answer.0);
+                        self.defaults.push(answer);
+                    }
+                }
+                Answer::Quit => {
+                    let path = match &self.session {
+                        Some(path) => path.clone(),
+                        None => default_session()?,
+                    };
+                    self.save(&path)?;
+                    return Err(io::Error::new(
+                        io::ErrorKind::Interrupted,
+                        tr_with(
+                            "session-saved",
+                            &[("path", path.display().to_string().into())],
+                        ),
+                    ));
+                }
+            }
+            if let Some(path) = &self.session {
+                self.save(path)?;
+            }
+        }
+        if let Err(e) = last_run().and_then(|path| self.save(&path)) {
+            eprintln!("Could not save the answers for the next run: {e}");
+        }
+        Ok(self.fill())
+    }
+
+    /// Answers the next questions with the values of `product`, up to the first it has no value
+    /// for. They can be changed by going back.
+    fn prefill(&mut self, product: &Product) {
+        if self.args.serving_size.is_some() {
+            return;
+        }
+        let mut values = answers(product);
+        // with units, in case the questions ask for kcal or sodium in mg
+        for (question, value) in &mut values {
+            match question {
+                Question::Energy => value.push_str(" kJ"),
+                Question::Salt => value.push_str(" g"),
+                _ => {}
+            }
+        }
+        while let Some(question) = self.next() {
+            let Some((_, value)) = values.iter().find(|(q, _)| *q == question) else {
+                break;
+            };
+            self.answers.push((question, value.clone()));
+        }
+    }
+
+    fn ask(&self, question: Question) -> io::Result<Answer> {
+        let args = self.args;
+        let if_unknown = |prompt: &str| {
+            if self.allow_unknown {
+                (
+                    tr_with("prompt-if-unknown", &[("prompt", tr(prompt).into())]),
+                    true,
+                )
+            } else {
+                (tr(prompt), false)
+            }
+        };
+        let (prompt, unit, optional) = match question {
+            Question::Name => return self.ask_name(),
+            Question::Energy if args.energy_unit == Some(EnergyUnit::Kcal) => {
+                (tr("prompt-energy-kcal"), Unit::Kcal, false)
+            }
+            Question::Energy => (tr("prompt-energy-kj"), Unit::Kj, false),
+            Question::Fat => (tr("prompt-fat"), Unit::G, false),
+            Question::SaturatedFats => (tr("prompt-saturated-fats"), Unit::G, false),
+            Question::Carbohydrates => (
+                tr_with(
+                    "prompt-if-unknown",
+                    &[("prompt", tr("prompt-carbohydrates").into())],
+                ),
+                Unit::G,
+                true,
+            ),
+            Question::Sugar => (tr("prompt-sugar"), Unit::G, false),
+            Question::Proteins => (tr("prompt-proteins"), Unit::G, false),
+            Question::Salt => (tr("prompt-salt-or-sodium"), Unit::G, true),
+            Question::Sodium => (tr("prompt-sodium"), Unit::Mg, false),
+            Question::Fibers => {
+                let (prompt, optional) = if_unknown("prompt-fibers");
+                (prompt, Unit::G, optional)
+            }
+            Question::Category => return self.ask_category(),
+            Question::Fruits if args.fruits_wizard => {
+                return Ok(Answer::Value(fruits_wizard()?.to_string()));
+            }
+            Question::Fruits => {
+                let (prompt, optional) = if_unknown("prompt-fruits");
+                (prompt, Unit::Percent, optional)
+            }
+            Question::Drink => {
+                let items = DrinkType::iter()
+                    .map(|d| (tr(&format!("drink-{d}")), d.to_string()))
+                    .collect();
+                let other = DrinkType::iter().count() - 1;
+                return self.select(question, &tr("prompt-drink"), items, other);
+            }
+            Question::Alcohol => (tr("prompt-alcohol"), Unit::Percent, true),
+            Question::Sweeteners => {
+                return self.ask_yes_no(question, &tr("prompt-sweeteners"));
+            }
+            Question::EcoCategory => {
+                let items = EcoCategory::iter()
+                    .map(|c| (tr(&format!("eco-category-{c}")), c.to_string()))
+                    .collect();
+                let other = EcoCategory::iter().count() - 1;
+                return self.select(question, &tr("prompt-eco-category"), items, other);
+            }
+            Question::Packaging => {
+                let items = Packaging::iter()
+                    .map(|p| (tr(&format!("packaging-{p}")), p.to_string()))
+                    .collect();
+                return self.select(question, &tr("prompt-packaging"), items, 0);
+            }
+            Question::Origin => {
+                let items = Origin::iter()
+                    .map(|o| (tr(&format!("origin-{o}")), o.to_string()))
+                    .collect();
+                let unknown = Origin::iter().count() - 1;
+                return self.select(question, &tr("prompt-origin"), items, unknown);
+            }
+            Question::Labels => return self.ask_labels(),
+            Question::PalmOil => return self.ask_yes_no(question, &tr("prompt-palm-oil")),
+        };
+        let mut input = Input::<String>::new();
+        input
+            .with_prompt(&prompt)
+            .allow_empty(optional)
+            .validate_with(|s: &String| {
+                if [BACK, QUIT].contains(&s.trim()) || (optional && s.is_empty()) {
+                    Ok(())
+                } else {
+                    units::parse(s, unit).map(drop)
+                }
+            });
+        if let Some(default) = self.default(question) {
+            input.with_initial_text(default);
+        }
+        let input = input.interact().map_err(error::terminal(&prompt))?;
+        Ok(match input.trim() {
+            BACK => Answer::Back,
+            QUIT => Answer::Quit,
+            _ => Answer::Value(input),
+        })
+    }
+
+    /// A selection of the labels of `items`, with items to go back unless this is the first
+    /// question and to quit. The value of the last answer is selected, or else `default`.
+    fn select(
+        &self,
+        question: Question,
+        prompt: &str,
+        items: Vec<(String, String)>,
+        default: usize,
+    ) -> io::Result<Answer> {
+        let (mut labels, values): (Vec<String>, Vec<String>) = items.into_iter().unzip();
+        let default = values
+            .iter()
+            .position(|value| Some(value.as_str()) == self.default(question))
+            .unwrap_or(default);
+        let mut answers: Vec<Answer> = values.into_iter().map(Answer::Value).collect();
+        if !self.answers.is_empty() {
+            labels.push(tr("back"));
+            answers.push(Answer::Back);
+        }
+        labels.push(tr("quit"));
+        answers.push(Answer::Quit);
+        let index = Select::new()
+            .with_prompt(prompt)
+            .items(&labels)
+            .default(default)
+            .interact()
+            .map_err(error::terminal(prompt))?;
+        Ok(answers.swap_remove(index))
+    }
+
+    fn ask_category(&self) -> io::Result<Answer> {
+        let items = Category::iter()
+            .map(|c| (i18n::category(c), c.to_string()))
+            .collect();
+        self.select(
+            Question::Category,
+            &tr("prompt-category"),
+            items,
+            Category::COUNT - 1,
+        )
+    }
+
+    /// Asks for the labels separated by commas, which can be left empty.
+    fn ask_labels(&self) -> io::Result<Answer> {
+        let names: Vec<String> = EcoLabel::iter().map(|l| l.to_string()).collect();
+        let prompt = tr_with("prompt-labels", &[("labels", names.join(", ").into())]);
+        let mut input = Input::<String>::new();
+        input
+            .with_prompt(&prompt)
+            .allow_empty(true)
+            .validate_with(|s: &String| {
+                if [BACK, QUIT].contains(&s.trim()) {
+                    Ok(())
+                } else {
+                    labels(s).map(drop)
+                }
+            });
+        if let Some(default) = self.default(Question::Labels) {
+            input.with_initial_text(default);
+        }
+        let input = input.interact().map_err(error::terminal(&prompt))?;
+        Ok(match input.trim() {
+            BACK => Answer::Back,
+            QUIT => Answer::Quit,
+            _ => Answer::Value(input),
+        })
+    }
+
+    /// Asks for the name, which Tab completes with a known product.
+    fn ask_name(&self) -> io::Result<Answer> {
+        let prompt = tr("prompt-name");
+        let names = Names(&self.known);
+        let mut input = Input::<String>::new();
+        input
+            .with_prompt(&prompt)
+            .allow_empty(true)
+            .completion_with(&names);
+        if let Some(default) = self.default(Question::Name) {
+            input.with_initial_text(default);
+        }
+        // only reading key by key completes, without a terminal the name is left empty
+        let input = input.interact_text().map_err(error::terminal(&prompt))?;
+        Ok(match input.trim() {
+            BACK => Answer::Back,
+            QUIT => Answer::Quit,
+            name => Answer::Value(name.to_owned()),
+        })
+    }
+
+    fn ask_yes_no(&self, question: Question, prompt: &str) -> io::Result<Answer> {
+        let items = vec![(tr("yes"), true.to_string()), (tr("no"), false.to_string())];
+        self.select(question, prompt, items, 1)
+    }
+
+    /// Saves the answers so far, to continue with `--resume`.
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let answers: toml::map::Map<String, toml::Value> = self
+            .answers
+            .iter()
+            .map(|(question, answer)| (question.to_string(), answer.clone().into()))
+            .collect();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string(&answers).map_err(io::Error::other)?)
+    }
+
+    /// The arguments with the answers filled in.
+    fn fill(&self) -> NutritionArgs {
+        let mut args = self.args.clone();
+        for (question, answer) in &self.answers {
+            let text = (!answer.is_empty()).then(|| answer.clone());
+            match question {
+                Question::Name => args.product_name = text,
+                Question::Energy => args.energy = text,
+                Question::Fat => args.fat = text,
+                Question::SaturatedFats => args.saturated_fats = text,
+                Question::Carbohydrates => args.carbohydrates = text,
+                Question::Sugar => args.sugar = text,
+                Question::Proteins => args.proteins = text,
+                Question::Salt => args.salt = text,
+                Question::Sodium => args.sodium = text,
+                Question::Fibers => args.fibers = text,
+                Question::Category => args.category = answer.parse().ok(),
+                Question::Fruits => args.fruits = text,
+                Question::Drink => args.drink = answer.parse().ok(),
+                Question::Alcohol => args.alcohol = text,
+                Question::Sweeteners => args.sweeteners = answer == "true",
+                Question::EcoCategory => args.eco_category = answer.parse().ok(),
+                Question::Packaging => args.packaging = answer.parse().ok(),
+                Question::Origin => args.origin = answer.parse().ok(),
+                Question::Labels => args.labels = Some(answer.clone()),
+                Question::PalmOil => args.palm_oil = answer == "true",
+            }
+        }
+        args
+    }
+}
+
+/// Parses labels separated by commas, like `organic, msc`.
+pub fn labels(text: &str) -> Result<Vec<EcoLabel>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            name.parse()
+                .map_err(|_| tr_with("unknown-label", &[("label", name.into())]))
+        })
+        .collect()
+}
+
+/// The session file for `quit` and `--resume` without a file,
+/// ~/.local/state/nutriscore/session.toml.
+fn default_session() -> io::Result<PathBuf> {
+    config::user_file("XDG_STATE_HOME", ".local/state", "session.toml").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "cannot find the home directory for the session",
+        )
+    })
+}
+
+/// The answers of the last run, offered as defaults in the next one.
+fn last_run() -> io::Result<PathBuf> {
+    config::user_file("XDG_STATE_HOME", ".local/state", "last-run.toml").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "cannot find the home directory for the last run",
+        )
+    })
+}
+
+/// Reads the answers saved by [`Wizard::save`].
+fn load(path: &Path) -> io::Result<Vec<(Question, String)>> {
+    let invalid = |message: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {message}", path.display()),
+        )
+    };
+    let text = fs::read_to_string(path)?;
+    let answers: BTreeMap<String, String> =
+        toml::from_str(&text).map_err(|e| error::toml(path, text.clone(), &e))?;
+    let mut answers = answers
+        .into_iter()
+        .map(|(name, answer)| {
+            name.parse()
+                .map(|question| (question, answer))
+                .map_err(|_| invalid(format!("unknown question {name:?}")))
+        })
+        .collect::<io::Result<Vec<(Question, String)>>>()?;
+    answers.sort_by_key(|&(question, _)| question as usize);
+    Ok(answers)
+}
+
+/// The products of the default database and of the Open Food Facts cache that have a name.
+fn known_products() -> Vec<Product> {
+    // products that cannot be read are only not completed
+    let mut products = database::saved_products().unwrap_or_default();
+    let cached = cache::entries::<off::Product>("off").unwrap_or_default();
+    products.extend(cached.into_iter().map(|(_, off)| Product {
+        category: off.category().unwrap_or(Category::Other),
+        nutrition: off.nutrition(),
+        fruits: off.fruits(),
+        water: off.is_water(),
+        name: off.name,
+        drink: None,
+        alcohol: None,
+        ingredients: None,
+        eco: None,
+    }));
+    products.retain(|product| product.name.is_some());
+    products
+}
+
+/// Asks for everything `args` is missing and returns them completed. With `allow_unknown`,
+/// fibers and fruits can be left empty.
+///
+/// Without `--name`, the name is asked first and completed from the saved and cached products,
+/// whose values then answer the following questions.
+///
+/// The answers are saved in `--save-session` after every question, and the questions continue
+/// after the answers of `--resume`.
+pub fn run(
+    algorithm: Algorithm,
+    args: &NutritionArgs,
+    allow_unknown: bool,
+) -> io::Result<NutritionArgs> {
+    let resume = match &args.resume {
+        Some(Some(path)) => Some(path.clone()),
+        Some(None) => Some(default_session()?),
+        None => None,
+    };
+    let answers = match &resume {
+        Some(path) => load(path)?,
+        None => Vec::new(),
+    };
+    // a missing or broken file only means that there are no defaults
+    let defaults = if args.no_prefill {
+        Vec::new()
+    } else {
+        last_run().and_then(|path| load(&path)).unwrap_or_default()
+    };
+    let known = if args.product_name.is_none() {
+        known_products()
+    } else {
+        Vec::new()
+    };
+    Wizard {
+        algorithm,
+        args,
+        allow_unknown,
+        answers,
+        session: args.save_session.clone().or(resume),
+        defaults,
+        known,
+    }
+    .run()
+}
+
+/// The answers to the questions that give the values of `product`.
+fn answers(product: &Product) -> Vec<(Question, String)> {
+    let n = &product.nutrition;
+    let mut answers = vec![
+        (Question::Energy, n.energy.to_string()),
+        (Question::Fat, n.fat.to_string()),
+        (Question::SaturatedFats, n.saturated_fats.to_string()),
+        (Question::Sugar, n.sugar.to_string()),
+        (Question::Proteins, n.proteins.to_string()),
+        (Question::Salt, n.salt.to_string()),
+        (Question::Fibers, n.fibers.to_string()),
+        (Question::Category, product.category.to_string()),
+        (Question::Fruits, product.fruits.to_string()),
+        (
+            Question::Drink,
+            product
+                .drink
+                .or_else(|| product.water.then_some(DrinkType::Water))
+                .unwrap_or(DrinkType::Other)
+                .to_string(),
+        ),
+        (
+            Question::Alcohol,
+            product
+                .alcohol
+                .map(|abv| abv.to_string())
+                .unwrap_or_default(),
+        ),
+        (Question::Sweeteners, n.contains_sweeteners.to_string()),
+        (
+            Question::Carbohydrates,
+            n.carbohydrates
+                .map(|carbohydrates| carbohydrates.to_string())
+                .unwrap_or_default(),
+        ),
+    ];
+    if let Some(eco) = &product.eco {
+        let labels: Vec<String> = eco.labels.iter().map(ToString::to_string).collect();
+        answers.extend([
+            (Question::EcoCategory, eco.category.to_string()),
+            (Question::Packaging, eco.packaging.to_string()),
+            (Question::Origin, eco.origin.to_string()),
+            (Question::Labels, labels.join(", ")),
+            (Question::PalmOil, eco.palm_oil.to_string()),
+        ]);
+    }
+    answers
+}
+
+/// Asks every question again with the values of `product` offered for editing, for
+/// `product edit`.
+pub fn edit(
+    algorithm: Algorithm,
+    args: &NutritionArgs,
+    product: &Product,
+) -> io::Result<NutritionArgs> {
+    Wizard {
+        algorithm,
+        args,
+        allow_unknown: false,
+        answers: Vec::new(),
+        session: None,
+        defaults: answers(product),
+        known: Vec::new(),
+    }
+    .run()
+}